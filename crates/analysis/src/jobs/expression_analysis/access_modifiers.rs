@@ -0,0 +1,183 @@
+use lsp_types as lsp;
+use witcherscript::attribs::AccessModifier;
+use witcherscript::tokens::*;
+use witcherscript::ast::*;
+use witcherscript_diagnostics::{Diagnostic, DiagnosticKind};
+use crate::symbol_analysis::symbol_path::SymbolPath;
+use crate::symbol_analysis::symbols::*;
+use super::types::is_derived_from;
+use super::AnalysisContext;
+
+
+/// Checks whether private and protected members are only used from places that can access them.
+/// Private members can be used inside of the class they belong to and its states,
+/// protected members additionally inside of any class derived from it.
+pub(super) struct AccessModifiersVisitor<'a> {
+    ctx: AnalysisContext<'a>,
+    pub diagnostics: Vec<Diagnostic>
+}
+
+impl<'a> AccessModifiersVisitor<'a> {
+    pub fn new(ctx: AnalysisContext<'a>) -> Self {
+        Self {
+            ctx,
+            diagnostics: Vec::new()
+        }
+    }
+
+    fn check_identifier(&mut self, n: &IdentifierNode, ctx: &TraversalContextStack) {
+        let marcher = &self.ctx.symtab_marcher;
+        let mut member_path = self.ctx.evaluate(n.clone().into(), ctx.top());
+
+        if !marcher.contains_symbol(&member_path) {
+            // private members of base classes are not put into the lookup, so they need to be searched for separately
+            let name = n.value(self.ctx.doc);
+            let category = if ctx.top() == TraversalContext::FunctionCallExpressionFunc {
+                SymbolCategory::Callable
+            } else {
+                SymbolCategory::Data
+            };
+
+            let found = self.ctx.scope_type_path()
+                .and_then(|scope_type_path| self.ctx.member_scope(&scope_type_path))
+                .and_then(|scope| scope.into_iter()
+                    .map(|type_path| type_path.join_component(&name, category))
+                    .find(|path| marcher.contains_symbol(path)));
+
+            match found {
+                Some(path) => member_path = path,
+                None => return
+            }
+        }
+
+        self.check_access(&member_path, n.range());
+    }
+
+    fn check_member_access(&mut self, n: &MemberAccessExpressionNode, ctx: &TraversalContextStack) {
+        let member = n.member();
+        if member.is_missing() {
+            return;
+        }
+
+        let member_path = self.ctx.evaluate(n.clone().into(), ctx.top());
+        self.check_access(&member_path, member.range());
+    }
+
+    fn check_access(&mut self, member_path: &SymbolPath, range: lsp::Range) {
+        let symvar = match self.ctx.symtab_marcher.get_symbol(member_path) {
+            Some(symvar) => symvar,
+            None => return
+        };
+        let access_modifier = match member_access_modifier(symvar) {
+            Some(am) => am,
+            None => return
+        };
+        let owner_path = match member_path.parent() {
+            Some(path) => path,
+            None => return
+        };
+
+        if self.is_accessible(owner_path, access_modifier) {
+            return;
+        }
+
+        self.diagnostics.push(Diagnostic {
+            range,
+            kind: DiagnosticKind::InaccessibleMember {
+                member_name: symvar.name().to_string(),
+                owner_name: owner_path.to_string(),
+                access_modifier: Keyword::from(access_modifier).to_string()
+            }
+        });
+    }
+
+    /// Whether a member of the owner type with given access modifier can be used from the code currently being visited
+    fn is_accessible(&self, owner_path: &SymbolPath, access_modifier: AccessModifier) -> bool {
+        let marcher = &self.ctx.symtab_marcher;
+        let scope_type_path = match self.ctx.scope_type_path() {
+            Some(path) => path,
+            None => return false
+        };
+
+        if scope_type_path.as_sympath() == owner_path {
+            return true;
+        }
+
+        let state_parent_class_path = match marcher.get_symbol(&scope_type_path) {
+            Some(SymbolVariant::State(s)) => Some(s.parent_class_path()),
+            _ => None
+        };
+
+        match access_modifier {
+            AccessModifier::Private => {
+                state_parent_class_path == Some(owner_path)
+            },
+            AccessModifier::Protected => {
+                is_derived_from(&scope_type_path, owner_path, marcher)
+                || state_parent_class_path.map(|p| is_derived_from(p, owner_path, marcher)).unwrap_or(false)
+            },
+            AccessModifier::Public => true
+        }
+    }
+}
+
+fn member_access_modifier(symvar: &SymbolVariant) -> Option<AccessModifier> {
+    let has = |private: bool, protected: bool| {
+        if private {
+            Some(AccessModifier::Private)
+        } else if protected {
+            Some(AccessModifier::Protected)
+        } else {
+            None
+        }
+    };
+
+    let func = match symvar {
+        SymbolVariant::MemberFunc(s) => Some(s),
+        SymbolVariant::MemberFuncInjector(s) => Some(&s.inner),
+        SymbolVariant::MemberFuncReplacer(s) => Some(&s.inner),
+        SymbolVariant::MemberFuncWrapper(s) => Some(&s.inner),
+        _ => None
+    };
+    if let Some(s) = func {
+        return has(
+            s.specifiers.contains(AccessModifier::Private.into()),
+            s.specifiers.contains(AccessModifier::Protected.into())
+        );
+    }
+
+    match symvar {
+        SymbolVariant::MemberVar(s) => has(
+            s.specifiers.contains(AccessModifier::Private.into()),
+            s.specifiers.contains(AccessModifier::Protected.into())
+        ),
+        SymbolVariant::MemberVarInjector(s) => has(
+            s.inner.specifiers.contains(AccessModifier::Private.into()),
+            s.inner.specifiers.contains(AccessModifier::Protected.into())
+        ),
+        SymbolVariant::Autobind(s) => has(
+            s.specifiers.contains(AccessModifier::Private.into()),
+            s.specifiers.contains(AccessModifier::Protected.into())
+        ),
+        _ => None
+    }
+}
+
+impl SyntaxNodeVisitor for AccessModifiersVisitor<'_> {
+    fn traversal_policy_default(&self) -> bool {
+        true
+    }
+
+
+    fn visit_identifier_expr(&mut self, n: &IdentifierNode, ctx: &TraversalContextStack) {
+        self.check_identifier(n, ctx);
+    }
+
+    fn visit_member_access_expr(&mut self, n: &MemberAccessExpressionNode, ctx: &TraversalContextStack) -> MemberFieldExpressionTraversalPolicy {
+        self.check_member_access(n, ctx);
+
+        TraversalPolicy::default_to(true)
+    }
+}
+
+impl SyntaxNodeVisitorChainLink for AccessModifiersVisitor<'_> {}
@@ -0,0 +1,157 @@
+use lsp_types as lsp;
+use witcherscript::ast::*;
+use witcherscript_diagnostics::{Diagnostic, DiagnosticKind};
+use crate::symbol_analysis::symbols::*;
+use crate::utils::{resolve_callee, CalleeParam};
+use super::types::ResolvedType;
+use super::AnalysisContext;
+
+
+/// Checks whether arguments of function calls match parameters of the called function.
+/// This includes calls to struct constructors and built-in functions of arrays.
+pub(super) struct CallCheckerVisitor<'a> {
+    ctx: AnalysisContext<'a>,
+    pub diagnostics: Vec<Diagnostic>
+}
+
+impl<'a> CallCheckerVisitor<'a> {
+    pub fn new(ctx: AnalysisContext<'a>) -> Self {
+        Self {
+            ctx,
+            diagnostics: Vec::new()
+        }
+    }
+
+    /// Returns parameters of the called function ordered by their ordinal.
+    /// Returns None if the callee could not be resolved or is not something that can be called.
+    fn callee_params(&self, n: &FunctionCallExpressionNode) -> Option<Vec<CalleeParam<'a>>> {
+        let callee_path = self.ctx.evaluate(n.func(), TraversalContext::FunctionCallExpressionFunc);
+        let callee = resolve_callee(&callee_path, &self.ctx.symtab_marcher)?;
+
+        match callee.symvar {
+            SymbolVariant::Constructor(_)
+            | SymbolVariant::GlobalFunc(_)
+            | SymbolVariant::MemberFunc(_)
+            | SymbolVariant::Event(_)
+            | SymbolVariant::ArrayFunc(_)
+            | SymbolVariant::MemberFuncInjector(_)
+            | SymbolVariant::MemberFuncReplacer(_)
+            | SymbolVariant::GlobalFuncReplacer(_)
+            | SymbolVariant::MemberFuncWrapper(_) => Some(callee.params),
+            _ => None
+        }
+    }
+
+    fn check_args(&mut self, n: &FunctionCallExpressionNode, params: &[CalleeParam]) {
+        let args = n.args().map(|args| args.iter().collect::<Vec<_>>()).unwrap_or_default();
+
+        if let (Some(first_surplus), Some(last)) = (args.get(params.len()), args.last()) {
+            self.diagnostics.push(Diagnostic {
+                range: lsp::Range::new(first_surplus.range().start, last.range().end),
+                kind: DiagnosticKind::TooManyArguments {
+                    max_args: params.len(),
+                    found_args: args.len()
+                }
+            });
+        }
+
+        let func = n.func();
+        let callee_name_range = match func.clone().value() {
+            Expression::MemberAccess(access) => access.member().range(),
+            _ => func.range()
+        };
+
+        for (i, param) in params.iter().enumerate() {
+            match args.get(i) {
+                Some(FunctionCallArgument::Some(expr)) => {
+                    if param.is_out() && !is_assignable(expr.clone()) {
+                        self.diagnostics.push(Diagnostic {
+                            range: expr.range(),
+                            kind: DiagnosticKind::InvalidOutArgument
+                        });
+                    } else {
+                        self.check_arg_type(expr.clone(), param);
+                    }
+                },
+                Some(FunctionCallArgument::Omitted(range)) if !param.is_optional() => {
+                    self.diagnostics.push(Diagnostic {
+                        range: *range,
+                        kind: DiagnosticKind::MissingArgument {
+                            param_name: param.name().to_string()
+                        }
+                    });
+                },
+                None if !param.is_optional() => {
+                    self.diagnostics.push(Diagnostic {
+                        range: callee_name_range,
+                        kind: DiagnosticKind::MissingArgument {
+                            param_name: param.name().to_string()
+                        }
+                    });
+                },
+                _ => {}
+            }
+        }
+    }
+
+    fn check_arg_type(&mut self, arg: ExpressionNode, param: &CalleeParam) {
+        let range = arg.range();
+        let param_type = match ResolvedType::resolve(param.type_path().clone().into(), &self.ctx.symtab_marcher) {
+            Some(typ) => typ,
+            None => return
+        };
+        let arg_type = match self.ctx.expression_type(arg, TraversalContext::FunctionCallArg) {
+            Some(typ) => typ,
+            None => return
+        };
+
+        // value of an out parameter gets written back into the argument
+        let is_valid = if param.is_out() {
+            param_type.is_convertible_to(&arg_type, &self.ctx.symtab_marcher)
+        } else {
+            arg_type.is_convertible_to(&param_type, &self.ctx.symtab_marcher)
+        };
+
+        if !is_valid {
+            self.diagnostics.push(Diagnostic {
+                range,
+                kind: DiagnosticKind::TypeMismatch {
+                    expected_type: param_type.name(),
+                    found_type: arg_type.name()
+                }
+            });
+        }
+    }
+}
+
+/// Whether the expression refers to something that a value can be written to
+fn is_assignable(expr: ExpressionNode) -> bool {
+    match expr.value() {
+        Expression::Identifier(_)
+        | Expression::MemberAccess(_)
+        | Expression::Array(_) => true,
+        Expression::Nested(n) => is_assignable(n.inner()),
+        _ => false
+    }
+}
+
+impl SyntaxNodeVisitor for CallCheckerVisitor<'_> {
+    fn traversal_policy_default(&self) -> bool {
+        true
+    }
+
+
+    fn visit_func_call_expr(&mut self, n: &FunctionCallExpressionNode, _: &TraversalContextStack) -> FunctionCallExpressionTraversalPolicy {
+        // positions of omitted arguments can't be trusted if the call is malformed
+        let has_errors = n.args().map(|args| args.has_errors()).unwrap_or(false);
+        if !has_errors {
+            if let Some(params) = self.callee_params(n) {
+                self.check_args(n, &params);
+            }
+        }
+
+        TraversalPolicy::default_to(true)
+    }
+}
+
+impl SyntaxNodeVisitorChainLink for CallCheckerVisitor<'_> {}
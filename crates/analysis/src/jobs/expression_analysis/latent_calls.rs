@@ -0,0 +1,159 @@
+use std::str::FromStr;
+use lsp_types as lsp;
+use witcherscript::attribs::*;
+use witcherscript::script_document::ScriptDocument;
+use witcherscript::ast::*;
+use witcherscript_diagnostics::{Diagnostic, DiagnosticKind};
+use crate::symbol_analysis::symbols::*;
+use super::AnalysisContext;
+
+
+/// Checks whether latent functions are only called from contexts that allow it,
+/// i.e. from other latent functions or from entry functions of states.
+pub(super) struct LatentCallsVisitor<'a> {
+    ctx: AnalysisContext<'a>,
+    /// Where `latent` could be inserted in the declaration of the currently visited function
+    latent_insert_position: Option<lsp::Position>,
+    pub diagnostics: Vec<Diagnostic>
+}
+
+impl<'a> LatentCallsVisitor<'a> {
+    pub fn new(ctx: AnalysisContext<'a>) -> Self {
+        Self {
+            ctx,
+            latent_insert_position: None,
+            diagnostics: Vec::new()
+        }
+    }
+
+    /// Returns None if the code currently being visited is not inside of a function
+    fn is_inside_latent_context(&self) -> Option<bool> {
+        let caller = self.ctx.symtab_marcher.get_symbol(&self.ctx.current_sympath())?;
+
+        if caller.is_event() {
+            Some(false)
+        } else if let Some(f) = as_member_func(caller) {
+            Some(f.specifiers.contains(MemberFunctionSpecifier::Latent) || f.flavour == Some(MemberFunctionFlavour::Entry))
+        } else {
+            as_global_func(caller).map(|f| f.specifiers.contains(GlobalFunctionSpecifier::Latent))
+        }
+    }
+
+    fn check_call(&mut self, n: &FunctionCallExpressionNode) {
+        let marcher = &self.ctx.symtab_marcher;
+        let callee_path = self.ctx.evaluate(n.func(), TraversalContext::FunctionCallExpressionFunc);
+
+        let mut callee = match marcher.get_symbol(&callee_path) {
+            Some(symvar) => symvar,
+            None => return
+        };
+        // wrappedMethod is latent if the wrapper is
+        if let Some(wrapped_method) = callee.try_as_wrapped_method_ref() {
+            match marcher.get_symbol(wrapped_method.wrapped_path()) {
+                Some(symvar) => callee = symvar,
+                None => return
+            }
+        }
+
+        if !is_latent(callee) || self.is_inside_latent_context() != Some(false) {
+            return;
+        }
+
+        let func = n.func();
+        let range = match func.clone().value() {
+            Expression::MemberAccess(access) => access.member().range(),
+            _ => func.range()
+        };
+
+        self.diagnostics.push(Diagnostic {
+            range,
+            kind: DiagnosticKind::InvalidLatentCall {
+                func_name: callee.name().to_string(),
+                latent_insert_position: self.latent_insert_position
+            }
+        });
+    }
+}
+
+fn as_member_func(symvar: &SymbolVariant) -> Option<&MemberFunctionSymbol> {
+    match symvar {
+        SymbolVariant::MemberFunc(s) => Some(s),
+        SymbolVariant::MemberFuncInjector(s) => Some(&s.inner),
+        SymbolVariant::MemberFuncReplacer(s) => Some(&s.inner),
+        SymbolVariant::MemberFuncWrapper(s) => Some(&s.inner),
+        _ => None
+    }
+}
+
+fn as_global_func(symvar: &SymbolVariant) -> Option<&GlobalFunctionSymbol> {
+    match symvar {
+        SymbolVariant::GlobalFunc(s) => Some(s),
+        SymbolVariant::GlobalFuncReplacer(s) => Some(&s.inner),
+        _ => None
+    }
+}
+
+fn is_latent(symvar: &SymbolVariant) -> bool {
+    if let Some(f) = as_member_func(symvar) {
+        f.specifiers.contains(MemberFunctionSpecifier::Latent)
+    } else if let Some(f) = as_global_func(symvar) {
+        f.specifiers.contains(GlobalFunctionSpecifier::Latent)
+    } else {
+        false
+    }
+}
+
+/// Returns the position of the function's flavour or `function` keyword, right before which `latent` can be written.
+/// Functions that replace or wrap others can't be made latent, as they need to keep the signature of the original.
+fn latent_insert_position(n: &FunctionDeclarationNode, doc: &ScriptDocument) -> Option<lsp::Position> {
+    let is_redefinition = n.annotation()
+        .and_then(|annot| AnnotationKind::from_str(&annot.name().value(doc)).ok())
+        .map(|kind| matches!(kind, AnnotationKind::ReplaceMethod | AnnotationKind::WrapMethod))
+        .unwrap_or(false);
+
+    if is_redefinition {
+        None
+    } else if let Some(flavour) = n.flavour() {
+        Some(flavour.range().start)
+    } else {
+        n.children()
+            .find(|ch| ch.text(doc) == "function")
+            .map(|ch| ch.range().start)
+    }
+}
+
+impl SyntaxNodeVisitor for LatentCallsVisitor<'_> {
+    fn traversal_policy_default(&self) -> bool {
+        true
+    }
+
+
+    fn visit_global_func_decl(&mut self, n: &FunctionDeclarationNode) -> FunctionDeclarationTraversalPolicy {
+        self.latent_insert_position = latent_insert_position(n, self.ctx.doc);
+
+        TraversalPolicy::default_to(true)
+    }
+
+    fn exit_global_func_decl(&mut self, _: &FunctionDeclarationNode) {
+        self.latent_insert_position = None;
+    }
+
+    fn visit_member_func_decl(&mut self, n: &FunctionDeclarationNode, _: &TraversalContextStack) -> FunctionDeclarationTraversalPolicy {
+        self.latent_insert_position = latent_insert_position(n, self.ctx.doc);
+
+        TraversalPolicy::default_to(true)
+    }
+
+    fn exit_member_func_decl(&mut self, _: &FunctionDeclarationNode, _: &TraversalContextStack) {
+        self.latent_insert_position = None;
+    }
+
+
+    fn visit_func_call_expr(&mut self, n: &FunctionCallExpressionNode, _: &TraversalContextStack) -> FunctionCallExpressionTraversalPolicy {
+        self.check_call(n);
+
+        TraversalPolicy::default_to(true)
+    }
+}
+
+impl SyntaxNodeVisitorChainLink for LatentCallsVisitor<'_> {}
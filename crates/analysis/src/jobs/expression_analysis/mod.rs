@@ -0,0 +1,152 @@
+mod types;
+mod suggestions;
+mod type_checker;
+mod call_checker;
+mod latent_calls;
+mod unresolved_symbols;
+mod access_modifiers;
+mod type_args;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use witcherscript::script_document::ScriptDocument;
+use witcherscript::Script;
+use witcherscript::ast::*;
+use witcherscript_diagnostics::Diagnostic;
+use crate::symbol_analysis::symbol_path::{SymbolPath, SymbolPathBuf};
+use crate::symbol_analysis::symbol_table::marcher::SymbolTableMarcher;
+use crate::symbol_analysis::symbols::*;
+use crate::symbol_analysis::unqualified_name_lookup::{UnqualifiedNameLookup, UnqualifiedNameLookupBuilder};
+use crate::utils::{evaluate_expression, produce_type, SymbolPathBuilder, SymbolPathBuilderPayload};
+use types::ResolvedType;
+use type_checker::TypeCheckerVisitor;
+use call_checker::CallCheckerVisitor;
+use latent_calls::LatentCallsVisitor;
+use unresolved_symbols::UnresolvedSymbolsVisitor;
+use access_modifiers::AccessModifiersVisitor;
+use type_args::MissingTypeArgsVisitor;
+
+
+/// Semantic analysis of expressions in function bodies, which requires symbols to be resolved.
+/// The marcher should start with the symbol table of the content that the script belongs to.
+pub fn expression_analysis(script: &Script, doc: &ScriptDocument, symtab_marcher: SymbolTableMarcher, diagnostics: &mut Vec<Diagnostic>) {
+    let (sympath_builder, sympath_builder_payload) = SymbolPathBuilder::new(doc);
+    let (unl_builder, unl_payload) = UnqualifiedNameLookupBuilder::new(doc, sympath_builder_payload.clone(), symtab_marcher.clone());
+    let ctx = AnalysisContext {
+        doc,
+        symtab_marcher,
+        sympath_builder_payload,
+        unl_payload
+    };
+
+    let type_checker = Rc::new(RefCell::new(TypeCheckerVisitor::new(ctx.clone())));
+    let call_checker = Rc::new(RefCell::new(CallCheckerVisitor::new(ctx.clone())));
+    let latent_calls = Rc::new(RefCell::new(LatentCallsVisitor::new(ctx.clone())));
+    let unresolved_symbols = Rc::new(RefCell::new(UnresolvedSymbolsVisitor::new(ctx.clone())));
+    let access_modifiers = Rc::new(RefCell::new(AccessModifiersVisitor::new(ctx.clone())));
+    let missing_type_args = Rc::new(RefCell::new(MissingTypeArgsVisitor::new(ctx)));
+
+    let mut chain = SyntaxNodeVisitorChain::new()
+        .link(sympath_builder)
+        .link(unl_builder)
+        .link_rc(type_checker.clone())
+        .link_rc(call_checker.clone())
+        .link_rc(latent_calls.clone())
+        .link_rc(unresolved_symbols.clone())
+        .link_rc(access_modifiers.clone())
+        .link_rc(missing_type_args.clone());
+
+    script.visit_nodes(&mut chain);
+    drop(chain);
+
+    diagnostics.append(&mut type_checker.borrow_mut().diagnostics);
+    diagnostics.append(&mut call_checker.borrow_mut().diagnostics);
+    diagnostics.append(&mut latent_calls.borrow_mut().diagnostics);
+    diagnostics.append(&mut unresolved_symbols.borrow_mut().diagnostics);
+    diagnostics.append(&mut access_modifiers.borrow_mut().diagnostics);
+    diagnostics.append(&mut missing_type_args.borrow_mut().diagnostics());
+}
+
+
+/// State of the traversal shared by all visitors that take part in expression analysis
+#[derive(Clone)]
+struct AnalysisContext<'a> {
+    doc: &'a ScriptDocument,
+    symtab_marcher: SymbolTableMarcher<'a>,
+    sympath_builder_payload: Rc<RefCell<SymbolPathBuilderPayload>>,
+    unl_payload: Rc<RefCell<UnqualifiedNameLookup>>
+}
+
+impl AnalysisContext<'_> {
+    /// Path of the symbol that the expression evaluates to
+    fn evaluate(&self, expr: ExpressionNode, ctx: TraversalContext) -> SymbolPathBuf {
+        evaluate_expression(
+            expr, ctx,
+            self.doc,
+            self.symtab_marcher.clone(),
+            self.sympath_builder_payload.clone(),
+            self.unl_payload.clone()
+        )
+    }
+
+    fn expression_type(&self, expr: ExpressionNode, ctx: TraversalContext) -> Option<ResolvedType> {
+        self.symbol_type(&self.evaluate(expr, ctx))
+    }
+
+    /// Type of a variable or return type of a function at the given path
+    fn symbol_type(&self, path: &SymbolPath) -> Option<ResolvedType> {
+        ResolvedType::resolve(produce_type(path, &self.symtab_marcher), &self.symtab_marcher)
+    }
+
+    fn current_sympath(&self) -> SymbolPathBuf {
+        self.sympath_builder_payload.borrow().current_sympath.clone()
+    }
+
+    /// Types which members can be accessed through a value of the given type, starting from that type.
+    /// Returns None if not all of them can be known, e.g. when a base class is missing.
+    fn member_scope(&self, type_path: &SymbolPath) -> Option<Vec<SymbolPathBuf>> {
+        let marcher = &self.symtab_marcher;
+        let mut scope = Vec::new();
+
+        let class_path: SymbolPathBuf = match marcher.get_symbol(type_path)? {
+            SymbolVariant::Class(_) => type_path.to_owned(),
+            SymbolVariant::State(_) => {
+                let mut last_state = None;
+                for state in marcher.state_hierarchy(type_path) {
+                    scope.push(state.path().to_owned());
+                    last_state = Some(state);
+                }
+
+                if last_state?.base_state_name.is_some() {
+                    return None;
+                }
+
+                BasicTypeSymbolPath::new(StateSymbol::DEFAULT_STATE_BASE_NAME).into()
+            },
+            SymbolVariant::Struct(_) | SymbolVariant::Array(_) => return Some(vec![type_path.to_owned()]),
+            _ => return None
+        };
+
+        let mut last_class = None;
+        for class in marcher.class_hierarchy(&class_path) {
+            scope.push(class.path().to_owned());
+            last_class = Some(class);
+        }
+
+        if last_class?.base_path.is_some() {
+            return None;
+        }
+
+        Some(scope)
+    }
+
+    /// Path of the type which contains the code currently being visited, if there is one.
+    /// For functions annotated with @addMethod this is the type they're added to.
+    fn scope_type_path(&self) -> Option<SymbolPathBuf> {
+        let current_sympath = self.current_sympath();
+        current_sympath.components()
+            .next()
+            .filter(|c| c.category == SymbolCategory::Type)
+            .map(|c| c.as_sympath().to_owned())
+    }
+}
@@ -0,0 +1,74 @@
+/// Finds the candidate most similar to the given name, which could've been what the user meant to write.
+/// Returns None if no candidate is similar enough.
+pub(super) fn suggest_name<'c>(name: &str, candidates: impl IntoIterator<Item = &'c str>) -> Option<String> {
+    let name_lower = name.to_ascii_lowercase();
+    // allow roughly one typo for every three characters
+    let max_distance = (name.chars().count() / 3).max(1);
+
+    candidates.into_iter()
+        .filter(|c| *c != name)
+        .map(|c| (c, edit_distance(&name_lower, &c.to_ascii_lowercase())))
+        .filter(|(_, dist)| *dist <= max_distance)
+        .min_by_key(|(_, dist)| *dist)
+        .map(|(c, _)| c.to_string())
+}
+
+/// Levenshtein distance between two strings
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev_row: Vec<usize> = (0..=b.len()).collect();
+    let mut curr_row = vec![0; b.len() + 1];
+
+    for (i, ca) in a.chars().enumerate() {
+        curr_row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution_cost = if ca == *cb { 0 } else { 1 };
+            curr_row[j + 1] = (prev_row[j] + substitution_cost)
+                .min(prev_row[j + 1] + 1)
+                .min(curr_row[j] + 1);
+        }
+
+        std::mem::swap(&mut prev_row, &mut curr_row);
+    }
+
+    prev_row[b.len()]
+}
+
+
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("", ""), 0);
+        assert_eq!(edit_distance("abc", "abc"), 0);
+        assert_eq!(edit_distance("abc", ""), 3);
+        assert_eq!(edit_distance("", "ab"), 2);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("flaw", "lawn"), 2);
+        assert_eq!(edit_distance("żółw", "zolw"), 3);
+        assert_eq!(edit_distance("żółw", "żółwie"), 2);
+    }
+
+    #[test]
+    fn test_suggest_name() {
+        assert_eq!(suggest_name("GetPlayr", ["SetPlayer", "GetPlayer"]), Some("GetPlayer".to_string()));
+        assert_eq!(suggest_name("getplayer", ["GetPlayer"]), Some("GetPlayer".to_string()));
+        assert_eq!(suggest_name("ab", ["ac"]), Some("ac".to_string()));
+
+        assert_eq!(suggest_name("Foo", ["Foo"]), None);
+        assert_eq!(suggest_name("abc", ["xyz"]), None);
+        assert_eq!(suggest_name("abc", []), None);
+    }
+
+    #[test]
+    fn test_suggest_name_non_ascii() {
+        // 6 characters, but 9 bytes
+        assert_eq!(suggest_name("żółwik", ["żolwik"]), Some("żolwik".to_string()));
+        assert_eq!(suggest_name("żółwik", ["zolwik"]), None);
+    }
+}
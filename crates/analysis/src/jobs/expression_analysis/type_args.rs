@@ -0,0 +1,190 @@
+use lsp_types as lsp;
+use witcherscript::tokens::*;
+use witcherscript::ast::*;
+use witcherscript_diagnostics::{Diagnostic, DiagnosticKind};
+use crate::symbol_analysis::symbol_path::{SymbolPath, SymbolPathBuf};
+use crate::symbol_analysis::symbols::*;
+use crate::utils::produce_type;
+use super::AnalysisContext;
+
+
+/// Finds array type annotations without a type argument.
+/// The missing argument is deduced from values assigned to or from the annotated variables,
+/// or returned from the annotated function, so that a quick fix can fill it in.
+pub(super) struct MissingTypeArgsVisitor<'a> {
+    ctx: AnalysisContext<'a>,
+    missing: Vec<MissingTypeArg>
+}
+
+struct MissingTypeArg {
+    range: lsp::Range,
+    /// Paths of variables declared with the annotation
+    var_paths: Vec<SymbolPathBuf>,
+    /// Path of the function which return type is the annotation
+    func_path: Option<SymbolPathBuf>,
+    type_arg: Option<String>
+}
+
+impl<'a> MissingTypeArgsVisitor<'a> {
+    pub fn new(ctx: AnalysisContext<'a>) -> Self {
+        Self {
+            ctx,
+            missing: Vec::new()
+        }
+    }
+
+    /// Usages that come after the annotation can still be in the rest of the script, so diagnostics are only made at the end
+    pub fn diagnostics(&mut self) -> Vec<Diagnostic> {
+        self.missing.drain(..)
+            .map(|m| Diagnostic {
+                range: m.range,
+                kind: DiagnosticKind::MissingTypeArg {
+                    type_arg: m.type_arg
+                }
+            })
+            .collect()
+    }
+
+
+    fn check_annotation(&mut self, n: &TypeAnnotationNode, var_paths: Vec<SymbolPathBuf>, func_path: Option<SymbolPathBuf>) {
+        let type_name = n.type_name();
+        if type_name.value(self.ctx.doc) != ArrayTypeSymbol::TYPE_NAME {
+            return;
+        }
+
+        match n.type_arg() {
+            // usage of the outer array can't tell much about the nested one
+            Some(type_arg) => self.check_annotation(&type_arg, Vec::new(), None),
+            None => self.missing.push(MissingTypeArg {
+                range: type_name.range(),
+                var_paths,
+                func_path,
+                type_arg: None
+            })
+        }
+    }
+
+    fn check_var_annotation<'n>(&mut self, n: &TypeAnnotationNode, parent_path: &SymbolPath, names: impl Iterator<Item = IdentifierNode<'n>>) {
+        let var_paths = names
+            .map(|name| MemberDataSymbolPath::new(parent_path, &name.value(self.ctx.doc)).into())
+            .collect();
+
+        self.check_annotation(n, var_paths, None);
+    }
+
+    fn check_func_return_type(&mut self, n: &FunctionDeclarationNode) {
+        if let Some(rt) = n.return_type() {
+            self.check_annotation(&rt, Vec::new(), Some(self.ctx.current_sympath()));
+        }
+    }
+
+    /// Takes the type argument from the type of the expression for annotations that match the predicate,
+    /// if the expression is an array. The expression is only evaluated if there's anything to deduce.
+    fn deduce_from(&mut self, pred: impl Fn(&MissingTypeArg) -> bool, n: ExpressionNode, ctx: TraversalContext) {
+        if !self.missing.iter().any(|m| m.type_arg.is_none() && pred(m)) {
+            return;
+        }
+
+        let marcher = &self.ctx.symtab_marcher;
+        let type_path = produce_type(&self.ctx.evaluate(n, ctx), marcher);
+        let type_arg = match marcher.get_symbol(&type_path) {
+            Some(SymbolVariant::Array(arr)) => arr.data_type_path().to_string(),
+            _ => return
+        };
+
+        for m in self.missing.iter_mut().filter(|m| m.type_arg.is_none() && pred(m)) {
+            m.type_arg = Some(type_arg.clone());
+        }
+    }
+}
+
+impl SyntaxNodeVisitor for MissingTypeArgsVisitor<'_> {
+    fn traversal_policy_default(&self) -> bool {
+        true
+    }
+
+
+    fn visit_class_decl(&mut self, n: &ClassDeclarationNode) -> ClassDeclarationTraversalPolicy {
+        if let Some(base) = n.base() {
+            if base.value(self.ctx.doc) == ArrayTypeSymbol::TYPE_NAME {
+                self.missing.push(MissingTypeArg {
+                    range: base.range(),
+                    var_paths: Vec::new(),
+                    func_path: None,
+                    type_arg: None
+                });
+            }
+        }
+
+        TraversalPolicy::default_to(true)
+    }
+
+    fn visit_global_var_decl(&mut self, n: &MemberVarDeclarationNode) {
+        let parent_path: SymbolPathBuf = n.annotation()
+            .and_then(|annot| annot.arg())
+            .map(|class_name| BasicTypeSymbolPath::new(&class_name.value(self.ctx.doc)).into())
+            .unwrap_or_default();
+
+        self.check_var_annotation(&n.var_type(), &parent_path, n.names());
+    }
+
+    fn visit_member_var_decl(&mut self, n: &MemberVarDeclarationNode, _: &TraversalContextStack) {
+        self.check_var_annotation(&n.var_type(), &self.ctx.current_sympath(), n.names());
+    }
+
+    fn visit_autobind_decl(&mut self, n: &AutobindDeclarationNode, _: &TraversalContextStack) {
+        let var_path = MemberDataSymbolPath::new(&self.ctx.current_sympath(), &n.name().value(self.ctx.doc)).into();
+        self.check_annotation(&n.autobind_type(), vec![var_path], None);
+    }
+
+    fn visit_global_func_decl(&mut self, n: &FunctionDeclarationNode) -> FunctionDeclarationTraversalPolicy {
+        self.check_func_return_type(n);
+
+        TraversalPolicy::default_to(true)
+    }
+
+    fn visit_member_func_decl(&mut self, n: &FunctionDeclarationNode, _: &TraversalContextStack) -> FunctionDeclarationTraversalPolicy {
+        self.check_func_return_type(n);
+
+        TraversalPolicy::default_to(true)
+    }
+
+    fn visit_func_param_group(&mut self, n: &FunctionParameterGroupNode, _: &TraversalContextStack) {
+        self.check_var_annotation(&n.param_type(), &self.ctx.current_sympath(), n.names());
+    }
+
+    fn visit_local_var_decl_stmt(&mut self, n: &LocalVarDeclarationNode, _: &TraversalContextStack) -> VarDeclarationTraversalPolicy {
+        let var_type = n.var_type();
+        self.check_var_annotation(&var_type, &self.ctx.current_sympath(), n.names());
+
+        if let Some(init_value) = n.init_value() {
+            let annot_range = var_type.type_name().range();
+            self.deduce_from(|m| m.range == annot_range, init_value, TraversalContext::LocalVarDeclarationInitValue);
+        }
+
+        TraversalPolicy::default_to(true)
+    }
+
+    fn visit_return_stmt(&mut self, n: &ReturnStatementNode, _: &TraversalContextStack) -> ReturnStatementTraversalPolicy {
+        if let Some(value) = n.value() {
+            let func_path = self.ctx.current_sympath();
+            self.deduce_from(|m| m.func_path.as_ref() == Some(&func_path), value, TraversalContext::ReturnStatement);
+        }
+
+        TraversalPolicy::default_to(true)
+    }
+
+    fn visit_assign_op_expr(&mut self, n: &AssignmentOperationExpressionNode, _: &TraversalContextStack) -> AssignmentOperationExpressionTraversalPolicy {
+        if n.op().value() == AssignmentOperator::Direct && self.missing.iter().any(|m| m.type_arg.is_none() && !m.var_paths.is_empty()) {
+            let left_path = self.ctx.evaluate(n.left(), TraversalContext::AssignmentOperationExpressionLeft);
+            let right_path = self.ctx.evaluate(n.right(), TraversalContext::AssignmentOperationExpressionRight);
+
+            self.deduce_from(|m| m.var_paths.contains(&left_path), n.right(), TraversalContext::AssignmentOperationExpressionRight);
+            self.deduce_from(|m| m.var_paths.contains(&right_path), n.left(), TraversalContext::AssignmentOperationExpressionLeft);
+        }
+
+        TraversalPolicy::default_to(true)
+    }
+}
+
+impl SyntaxNodeVisitorChainLink for MissingTypeArgsVisitor<'_> {}
@@ -0,0 +1,249 @@
+use lsp_types as lsp;
+use witcherscript::tokens::*;
+use witcherscript::ast::*;
+use witcherscript_diagnostics::{Diagnostic, DiagnosticKind};
+use crate::symbol_analysis::symbols::*;
+use super::types::{ResolvedType, TypeKind};
+use super::AnalysisContext;
+
+
+/// Checks whether types of expressions fit the context they're used in.
+/// Expressions of which type can't be resolved are skipped, as there is no telling what is wrong about them.
+pub(super) struct TypeCheckerVisitor<'a> {
+    ctx: AnalysisContext<'a>,
+    pub diagnostics: Vec<Diagnostic>
+}
+
+impl<'a> TypeCheckerVisitor<'a> {
+    pub fn new(ctx: AnalysisContext<'a>) -> Self {
+        Self {
+            ctx,
+            diagnostics: Vec::new()
+        }
+    }
+
+    fn expression_type(&self, expr: ExpressionNode, ctx: TraversalContext) -> Option<ResolvedType> {
+        self.ctx.expression_type(expr, ctx)
+    }
+
+    fn check_conversion(&mut self, expr: ExpressionNode, ctx: TraversalContext, target: &ResolvedType) {
+        let range = expr.range();
+        if let Some(typ) = self.expression_type(expr, ctx) {
+            if !typ.is_convertible_to(target, &self.ctx.symtab_marcher) {
+                self.diagnostics.push(Diagnostic {
+                    range,
+                    kind: DiagnosticKind::TypeMismatch {
+                        expected_type: target.name(),
+                        found_type: typ.name()
+                    }
+                });
+            }
+        }
+    }
+
+    fn check_condition(&mut self, cond: ExpressionNode, ctx: TraversalContext) {
+        let range = cond.range();
+        if let Some(typ) = self.expression_type(cond, ctx) {
+            if !typ.kind.is_condition() {
+                self.diagnostics.push(Diagnostic {
+                    range,
+                    kind: DiagnosticKind::TypeMismatch {
+                        expected_type: "bool".into(),
+                        found_type: typ.name()
+                    }
+                });
+            }
+        }
+    }
+
+    fn check_binary_operands(&mut self, op: BinaryOperator, op_text: String, range: lsp::Range, left: Option<ResolvedType>, right: Option<ResolvedType>) {
+        let (left, right) = match (left, right) {
+            (Some(l), Some(r)) => (l, r),
+            _ => return
+        };
+
+        let valid = match op {
+            BinaryOperator::Sum => {
+                if left.kind == TypeKind::String || right.kind == TypeKind::String {
+                    // string concatenation
+                    left.kind.is_stringifiable() && right.kind.is_stringifiable()
+                } else {
+                    left.kind.is_numeric() && right.kind.is_numeric()
+                }
+            },
+            BinaryOperator::Diff
+            | BinaryOperator::Mult
+            | BinaryOperator::Div
+            | BinaryOperator::Mod => left.kind.is_numeric() && right.kind.is_numeric(),
+            BinaryOperator::BitAnd
+            | BinaryOperator::BitOr
+            | BinaryOperator::BitXor => left.kind.is_integral() && right.kind.is_integral(),
+            BinaryOperator::And
+            | BinaryOperator::Or => left.kind.is_condition() && right.kind.is_condition(),
+            BinaryOperator::Equal
+            | BinaryOperator::NotEqual => {
+                // references to objects of unrelated classes can still be compared
+                (left.kind == TypeKind::Object && right.kind == TypeKind::Object)
+                || left.is_convertible_to(&right, &self.ctx.symtab_marcher)
+                || right.is_convertible_to(&left, &self.ctx.symtab_marcher)
+            },
+            BinaryOperator::Lesser
+            | BinaryOperator::LesserOrEqual
+            | BinaryOperator::Greater
+            | BinaryOperator::GreaterOrEqual => {
+                (left.kind.is_numeric() && right.kind.is_numeric())
+                || (left.kind == TypeKind::String && right.kind == TypeKind::String)
+            }
+        };
+
+        if !valid {
+            self.diagnostics.push(Diagnostic {
+                range,
+                kind: DiagnosticKind::InvalidBinaryOperands {
+                    op: op_text,
+                    left_type: left.name(),
+                    right_type: right.name()
+                }
+            });
+        }
+    }
+}
+
+impl SyntaxNodeVisitor for TypeCheckerVisitor<'_> {
+    fn traversal_policy_default(&self) -> bool {
+        true
+    }
+
+
+    fn visit_local_var_decl_stmt(&mut self, n: &LocalVarDeclarationNode, _: &TraversalContextStack) -> VarDeclarationTraversalPolicy {
+        if let (Some(init_value), Some(name)) = (n.init_value(), n.names().next()) {
+            let var_path = MemberDataSymbolPath::new(&self.ctx.current_sympath(), &name.value(self.ctx.doc));
+            if let Some(var_type) = self.ctx.symbol_type(&var_path) {
+                self.check_conversion(init_value, TraversalContext::LocalVarDeclarationInitValue, &var_type);
+            }
+        }
+
+        TraversalPolicy::default_to(true)
+    }
+
+    fn visit_return_stmt(&mut self, n: &ReturnStatementNode, _: &TraversalContextStack) -> ReturnStatementTraversalPolicy {
+        let func_path = self.ctx.current_sympath();
+        // return values of events are not well understood, so they're not checked
+        let is_event = self.ctx.symtab_marcher.get_symbol(&func_path).map(|v| v.is_event()).unwrap_or(true);
+
+        if let (Some(value), false) = (n.value(), is_event) {
+            if let Some(return_type) = self.ctx.symbol_type(&func_path) {
+                self.check_conversion(value, TraversalContext::ReturnStatement, &return_type);
+            }
+        }
+
+        TraversalPolicy::default_to(true)
+    }
+
+    fn visit_if_stmt(&mut self, n: &IfConditionalNode, _: &TraversalContextStack) -> IfConditionalTraversalPolicy {
+        self.check_condition(n.cond(), TraversalContext::IfConditionalCond);
+
+        TraversalPolicy::default_to(true)
+    }
+
+    fn visit_while_stmt(&mut self, n: &WhileLoopNode, _: &TraversalContextStack) -> WhileLoopTraversalPolicy {
+        self.check_condition(n.cond(), TraversalContext::WhileLoopCond);
+
+        TraversalPolicy::default_to(true)
+    }
+
+    fn visit_do_while_stmt(&mut self, n: &DoWhileLoopNode, _: &TraversalContextStack) -> DoWhileLoopTraversalPolicy {
+        self.check_condition(n.cond(), TraversalContext::DoWhileLoopCond);
+
+        TraversalPolicy::default_to(true)
+    }
+
+    fn visit_for_stmt(&mut self, n: &ForLoopNode, _: &TraversalContextStack) -> ForLoopTraversalPolicy {
+        if let Some(cond) = n.cond() {
+            self.check_condition(cond, TraversalContext::ForLoopCond);
+        }
+
+        TraversalPolicy::default_to(true)
+    }
+
+
+    fn visit_unary_op_expr(&mut self, n: &UnaryOperationExpressionNode, _: &TraversalContextStack) -> UnaryOperationExpressionTraversalPolicy {
+        let op = n.op();
+        if let Some(operand) = self.expression_type(n.right(), TraversalContext::UnaryOperationExpressionRight) {
+            let valid = match op.value() {
+                UnaryOperator::Not => operand.kind.is_condition(),
+                UnaryOperator::BitNot => operand.kind.is_integral(),
+                UnaryOperator::Negation
+                | UnaryOperator::Plus => operand.kind.is_numeric()
+            };
+
+            if !valid {
+                self.diagnostics.push(Diagnostic {
+                    range: n.range(),
+                    kind: DiagnosticKind::InvalidUnaryOperand {
+                        op: op.text(self.ctx.doc).to_string(),
+                        operand_type: operand.name()
+                    }
+                });
+            }
+        }
+
+        TraversalPolicy::default_to(true)
+    }
+
+    fn visit_binary_op_expr(&mut self, n: &BinaryOperationExpressionNode, _: &TraversalContextStack) -> BinaryOperationExpressionTraversalPolicy {
+        let op = n.op();
+        let left = self.expression_type(n.left(), TraversalContext::BinaryOperationExpressionLeft);
+        let right = self.expression_type(n.right(), TraversalContext::BinaryOperationExpressionRight);
+        self.check_binary_operands(op.value(), op.text(self.ctx.doc).to_string(), n.range(), left, right);
+
+        TraversalPolicy::default_to(true)
+    }
+
+    fn visit_assign_op_expr(&mut self, n: &AssignmentOperationExpressionNode, _: &TraversalContextStack) -> AssignmentOperationExpressionTraversalPolicy {
+        let op = n.op();
+        let left = self.expression_type(n.left(), TraversalContext::AssignmentOperationExpressionLeft);
+
+        // compound assignments behave like their binary operator counterparts
+        let binary_op = match op.value() {
+            AssignmentOperator::Direct => None,
+            AssignmentOperator::Mult => Some(BinaryOperator::Mult),
+            AssignmentOperator::Div => Some(BinaryOperator::Div),
+            AssignmentOperator::Sum => Some(BinaryOperator::Sum),
+            AssignmentOperator::Diff => Some(BinaryOperator::Diff),
+            AssignmentOperator::BitAnd => Some(BinaryOperator::BitAnd),
+            AssignmentOperator::BitOr => Some(BinaryOperator::BitOr)
+        };
+
+        if let Some(binary_op) = binary_op {
+            let right = self.expression_type(n.right(), TraversalContext::AssignmentOperationExpressionRight);
+            self.check_binary_operands(binary_op, op.text(self.ctx.doc).to_string(), n.range(), left, right);
+        } else if let Some(left) = left {
+            self.check_conversion(n.right(), TraversalContext::AssignmentOperationExpressionRight, &left);
+        }
+
+        TraversalPolicy::default_to(true)
+    }
+
+    fn visit_ternary_cond_expr(&mut self, n: &TernaryConditionalExpressionNode, _: &TraversalContextStack) -> TernaryConditionalExpressionTraversalPolicy {
+        self.check_condition(n.cond(), TraversalContext::TernaryConditionalExpressionCond);
+
+        let conseq = self.expression_type(n.conseq(), TraversalContext::TernaryConditionalExpressionConseq);
+        let alt = self.expression_type(n.alt(), TraversalContext::TernaryConditionalExpressionAlt);
+        if let (Some(conseq), Some(alt)) = (conseq, alt) {
+            if !alt.is_convertible_to(&conseq, &self.ctx.symtab_marcher) && !conseq.is_convertible_to(&alt, &self.ctx.symtab_marcher) {
+                self.diagnostics.push(Diagnostic {
+                    range: n.alt().range(),
+                    kind: DiagnosticKind::TypeMismatch {
+                        expected_type: conseq.name(),
+                        found_type: alt.name()
+                    }
+                });
+            }
+        }
+
+        TraversalPolicy::default_to(true)
+    }
+}
+
+impl SyntaxNodeVisitorChainLink for TypeCheckerVisitor<'_> {}
@@ -0,0 +1,321 @@
+use crate::symbol_analysis::symbol_path::{SymbolPath, SymbolPathBuf};
+use crate::symbol_analysis::symbol_table::marcher::SymbolTableMarcher;
+use crate::symbol_analysis::symbols::*;
+
+
+/// Broad classification of a type, which decides what implicit conversions and operators are available for it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum TypeKind {
+    Void,
+    Null,
+    Int,
+    Float,
+    Bool,
+    String,
+    Name,
+    Enum,
+    Object,
+    Struct,
+    Array
+}
+
+impl TypeKind {
+    fn is_primitive(&self) -> bool {
+        matches!(self, TypeKind::Void | TypeKind::Null | TypeKind::Int | TypeKind::Float | TypeKind::Bool | TypeKind::String | TypeKind::Name)
+    }
+
+    pub fn is_numeric(&self) -> bool {
+        matches!(self, TypeKind::Int | TypeKind::Float | TypeKind::Enum)
+    }
+
+    pub fn is_integral(&self) -> bool {
+        matches!(self, TypeKind::Int | TypeKind::Enum | TypeKind::Bool)
+    }
+
+    /// Whether a value of this type can be implicitly converted to a string
+    pub fn is_stringifiable(&self) -> bool {
+        matches!(self, TypeKind::Int | TypeKind::Float | TypeKind::Bool | TypeKind::String | TypeKind::Name | TypeKind::Enum)
+    }
+
+    /// Whether a value of this type can be used where a condition is expected, e.g. in an `if` statement
+    pub fn is_condition(&self) -> bool {
+        !matches!(self, TypeKind::Void | TypeKind::Struct | TypeKind::Array)
+    }
+}
+
+
+/// Type of an expression, which could be resolved to an existing type symbol
+#[derive(Debug, Clone)]
+pub(super) struct ResolvedType {
+    pub path: SymbolPathBuf,
+    pub kind: TypeKind
+}
+
+impl ResolvedType {
+    /// Returns None if the type is not known, in which case no assumptions should be made about it
+    pub fn resolve(path: SymbolPathBuf, symtab_marcher: &SymbolTableMarcher) -> Option<Self> {
+        if path.is_empty() || path.has_missing() || path.has_unknown() {
+            return None;
+        }
+
+        let kind = match symtab_marcher.get_symbol(&path)? {
+            SymbolVariant::Primitive(s) => {
+                match s.real_name().unwrap_or(s.name()) {
+                    "void" => TypeKind::Void,
+                    "NULL" => TypeKind::Null,
+                    "Byte" | "Int8" | "Int32" | "Uint64" => TypeKind::Int,
+                    "Float" => TypeKind::Float,
+                    "Bool" => TypeKind::Bool,
+                    "String" => TypeKind::String,
+                    "CName" => TypeKind::Name,
+                    _ => return None
+                }
+            },
+            SymbolVariant::Enum(_) => TypeKind::Enum,
+            SymbolVariant::Class(_) | SymbolVariant::State(_) => TypeKind::Object,
+            SymbolVariant::Struct(_) => TypeKind::Struct,
+            SymbolVariant::Array(_) => TypeKind::Array,
+            _ => return None
+        };
+
+        Some(Self { path, kind })
+    }
+
+    pub fn name(&self) -> String {
+        self.path.to_string()
+    }
+
+    /// Whether a value of this type can be used where the `target` type is expected without an explicit cast
+    pub fn is_convertible_to(&self, target: &ResolvedType, symtab_marcher: &SymbolTableMarcher) -> bool {
+        use TypeKind::*;
+
+        if self.path == target.path {
+            return true;
+        }
+
+        match (self.kind, target.kind) {
+            // primitives can have aliases, e.g. `int` and `Int32`
+            (from, to) if from == to && from.is_primitive() => true,
+            (Int | Float, Int | Float) => true,
+            (Enum, Int | Float) | (Int, Enum) => true,
+            (from, String) if from.is_stringifiable() => true,
+            (String, Name) => true,
+            (Null, Object) => true,
+            (Object, Object) => is_derived_from(&self.path, &target.path, symtab_marcher),
+            (Array, Array) => self.is_equivalent_to(target, symtab_marcher),
+            _ => false
+        }
+    }
+
+    /// Whether both types are the same type, taking into account aliases of primitive types.
+    /// Arrays are equivalent if their element types are, as values can be both read from and written into them.
+    fn is_equivalent_to(&self, other: &ResolvedType, symtab_marcher: &SymbolTableMarcher) -> bool {
+        if self.path == other.path {
+            return true;
+        }
+
+        match (self.kind, other.kind) {
+            (TypeKind::Array, TypeKind::Array) => {
+                match (self.element_type(symtab_marcher), other.element_type(symtab_marcher)) {
+                    (Some(a), Some(b)) => a.is_equivalent_to(&b, symtab_marcher),
+                    // nothing should be assumed about unknown types
+                    _ => true
+                }
+            },
+            (a, b) if a == b && a.is_primitive() => primitive_real_name(&self.path, symtab_marcher) == primitive_real_name(&other.path, symtab_marcher),
+            _ => false
+        }
+    }
+
+    /// Type of values stored in an array type
+    fn element_type(&self, symtab_marcher: &SymbolTableMarcher) -> Option<ResolvedType> {
+        match symtab_marcher.get_symbol(&self.path)? {
+            SymbolVariant::Array(s) => ResolvedType::resolve(s.data_type_path().clone().into(), symtab_marcher),
+            _ => None
+        }
+    }
+}
+
+/// Name of the primitive type that the path points to, with aliases resolved to their actual types
+fn primitive_real_name<'a>(path: &SymbolPath, symtab_marcher: &SymbolTableMarcher<'a>) -> Option<&'a str> {
+    match symtab_marcher.get_symbol(path)? {
+        SymbolVariant::Primitive(s) => Some(s.real_name().unwrap_or(s.name())),
+        _ => None
+    }
+}
+
+
+/// Checks whether a class or state type derives from another class or state type.
+/// If the hierarchy can't be fully resolved the type is assumed to be derived.
+pub(super) fn is_derived_from(derived_path: &SymbolPath, base_path: &SymbolPath, symtab_marcher: &SymbolTableMarcher) -> bool {
+    let mut class_path = derived_path.to_owned();
+
+    if symtab_marcher.get_symbol(derived_path).map(|v| v.is_state()).unwrap_or(false) {
+        let mut last_state = None;
+        for state in symtab_marcher.state_hierarchy(derived_path) {
+            if state.path() == base_path {
+                return true;
+            }
+
+            last_state = Some(state);
+        }
+
+        if last_state.map(|s| s.base_state_name.is_some()).unwrap_or(true) {
+            return true;
+        }
+
+        class_path = BasicTypeSymbolPath::new(StateSymbol::DEFAULT_STATE_BASE_NAME).into();
+    }
+
+    let mut last_class = None;
+    for class in symtab_marcher.class_hierarchy(&class_path) {
+        if class.path() == base_path {
+            return true;
+        }
+
+        last_class = Some(class);
+    }
+
+    last_class.map(|c| c.base_path.is_some()).unwrap_or(true)
+}
+
+
+
+
+#[cfg(test)]
+mod test {
+    use std::path::Path;
+    use std::sync::Arc;
+    use abs_path::AbsPath;
+    use lsp_types as lsp;
+    use witcherscript_project::SourceMask;
+    use crate::jobs::inject_primitives;
+    use crate::symbol_analysis::symbol_table::SymbolTable;
+    use super::*;
+
+
+    fn basic(name: &str) -> TypeSymbolPath {
+        TypeSymbolPath::BasicOrState(BasicTypeSymbolPath::new(name))
+    }
+
+    fn array(type_arg: TypeSymbolPath) -> TypeSymbolPath {
+        TypeSymbolPath::Array(ArrayTypeSymbolPath::new(type_arg))
+    }
+
+    fn test_symtab() -> SymbolTable {
+        let scripts_root = Arc::new(AbsPath::resolve(env!("CARGO_MANIFEST_DIR"), None).unwrap());
+        let local_source_path: Arc<Path> = Arc::from(Path::new("test.ws"));
+        let location = SymbolLocation {
+            scripts_root: scripts_root.clone(),
+            local_source_path: local_source_path.clone(),
+            range: lsp::Range::default(),
+            label_range: lsp::Range::default()
+        };
+
+        let mut symtab = SymbolTable::new(scripts_root);
+        inject_primitives(&mut symtab);
+
+        for (name, base_name) in [("CObject", None), ("CBase", Some("CObject")), ("CDerived", Some("CBase")), ("COrphan", Some("CMissing"))] {
+            let mut class = ClassSymbol::new(BasicTypeSymbolPath::new(name), location.clone());
+            class.base_path = base_name.map(BasicTypeSymbolPath::new);
+            symtab.insert_primary_symbol(class);
+        }
+
+        symtab.insert_primary_symbol(EnumSymbol::new(BasicTypeSymbolPath::new("EFoo"), location.clone()));
+        symtab.insert_primary_symbol(StructSymbol::new(BasicTypeSymbolPath::new("SFoo"), location.clone()));
+        symtab.insert_primary_symbol(StructSymbol::new(BasicTypeSymbolPath::new("SBar"), location.clone()));
+
+        for type_arg in [basic("int"), basic("Int32"), basic("float"), basic("Float"), basic("CBase"), basic("CDerived"), array(basic("int")), array(basic("Int32"))] {
+            symtab.insert_array_type_symbol(ArrayTypeSymbol::new(ArrayTypeSymbolPath::new(type_arg)), &local_source_path);
+        }
+
+        symtab
+    }
+
+    fn is_convertible(from: TypeSymbolPath, to: TypeSymbolPath, symtab: &SymbolTable) -> bool {
+        let mut marcher = SymbolTableMarcher::new();
+        marcher.add_step(symtab, SourceMask::default());
+
+        let from = ResolvedType::resolve(from.into(), &marcher).unwrap();
+        let to = ResolvedType::resolve(to.into(), &marcher).unwrap();
+        from.is_convertible_to(&to, &marcher)
+    }
+
+
+    #[test]
+    fn test_primitive_conversions() {
+        let symtab = test_symtab();
+
+        assert!(is_convertible(basic("int"), basic("int"), &symtab));
+        assert!(is_convertible(basic("int"), basic("Int32"), &symtab));
+        assert!(is_convertible(basic("Float"), basic("float"), &symtab));
+        assert!(is_convertible(basic("int"), basic("float"), &symtab));
+        assert!(is_convertible(basic("float"), basic("int"), &symtab));
+        assert!(is_convertible(basic("int"), basic("string"), &symtab));
+        assert!(is_convertible(basic("bool"), basic("string"), &symtab));
+        assert!(is_convertible(basic("name"), basic("string"), &symtab));
+        assert!(is_convertible(basic("string"), basic("name"), &symtab));
+
+        assert!(!is_convertible(basic("string"), basic("int"), &symtab));
+        assert!(!is_convertible(basic("bool"), basic("int"), &symtab));
+        assert!(!is_convertible(basic("int"), basic("bool"), &symtab));
+        assert!(!is_convertible(basic("name"), basic("int"), &symtab));
+        assert!(!is_convertible(basic("void"), basic("int"), &symtab));
+    }
+
+    #[test]
+    fn test_enum_conversions() {
+        let symtab = test_symtab();
+
+        assert!(is_convertible(basic("EFoo"), basic("int"), &symtab));
+        assert!(is_convertible(basic("EFoo"), basic("float"), &symtab));
+        assert!(is_convertible(basic("int"), basic("EFoo"), &symtab));
+        assert!(is_convertible(basic("EFoo"), basic("string"), &symtab));
+
+        assert!(!is_convertible(basic("float"), basic("EFoo"), &symtab));
+        assert!(!is_convertible(basic("string"), basic("EFoo"), &symtab));
+    }
+
+    #[test]
+    fn test_object_conversions() {
+        let symtab = test_symtab();
+
+        assert!(is_convertible(basic("CDerived"), basic("CDerived"), &symtab));
+        assert!(is_convertible(basic("CDerived"), basic("CBase"), &symtab));
+        assert!(is_convertible(basic("CDerived"), basic("CObject"), &symtab));
+        assert!(is_convertible(basic("NULL"), basic("CBase"), &symtab));
+        // hierarchy of the class is incomplete, so it could be derived from anything
+        assert!(is_convertible(basic("COrphan"), basic("CBase"), &symtab));
+
+        assert!(!is_convertible(basic("CBase"), basic("CDerived"), &symtab));
+        assert!(!is_convertible(basic("CBase"), basic("int"), &symtab));
+        assert!(!is_convertible(basic("NULL"), basic("int"), &symtab));
+        assert!(!is_convertible(basic("CBase"), basic("SFoo"), &symtab));
+    }
+
+    #[test]
+    fn test_struct_conversions() {
+        let symtab = test_symtab();
+
+        assert!(is_convertible(basic("SFoo"), basic("SFoo"), &symtab));
+
+        assert!(!is_convertible(basic("SFoo"), basic("SBar"), &symtab));
+        assert!(!is_convertible(basic("NULL"), basic("SFoo"), &symtab));
+        assert!(!is_convertible(basic("SFoo"), basic("string"), &symtab));
+    }
+
+    #[test]
+    fn test_array_conversions() {
+        let symtab = test_symtab();
+
+        assert!(is_convertible(array(basic("int")), array(basic("int")), &symtab));
+        assert!(is_convertible(array(basic("int")), array(basic("Int32")), &symtab));
+        assert!(is_convertible(array(basic("Float")), array(basic("float")), &symtab));
+        assert!(is_convertible(array(array(basic("int"))), array(array(basic("Int32"))), &symtab));
+
+        assert!(!is_convertible(array(basic("int")), array(basic("float")), &symtab));
+        assert!(!is_convertible(array(basic("CDerived")), array(basic("CBase")), &symtab));
+        assert!(!is_convertible(array(basic("int")), basic("int"), &symtab));
+        assert!(!is_convertible(basic("int"), array(basic("int")), &symtab));
+    }
+}
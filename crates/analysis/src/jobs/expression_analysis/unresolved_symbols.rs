@@ -0,0 +1,289 @@
+use std::cell::OnceCell;
+use witcherscript::tokens::*;
+use witcherscript::ast::*;
+use witcherscript_diagnostics::{Diagnostic, DiagnosticKind};
+use crate::symbol_analysis::symbol_path::SymbolPathBuf;
+use crate::symbol_analysis::symbols::*;
+use super::suggestions::suggest_name;
+use super::AnalysisContext;
+
+
+/// Finds names of types, variables and functions that don't refer to any known symbol.
+/// A name is only reported if it can be said for certain that it doesn't exist,
+/// e.g. members of a class are not checked if its whole hierarchy could not be found.
+pub(super) struct UnresolvedSymbolsVisitor<'a> {
+    ctx: AnalysisContext<'a>,
+    /// Symbols from the global scope, gathered only once something goes unresolved as it is costly
+    root_symbols: OnceCell<Vec<&'a SymbolVariant>>,
+    pub diagnostics: Vec<Diagnostic>
+}
+
+impl<'a> UnresolvedSymbolsVisitor<'a> {
+    pub fn new(ctx: AnalysisContext<'a>) -> Self {
+        Self {
+            ctx,
+            root_symbols: OnceCell::new(),
+            diagnostics: Vec::new()
+        }
+    }
+
+    fn root_symbols(&self) -> &[&'a SymbolVariant] {
+        self.root_symbols.get_or_init(|| self.ctx.symtab_marcher.get_root_symbols().collect())
+    }
+
+    fn member_names(&self, scope: &[SymbolPathBuf], category: SymbolCategory) -> Vec<&'a str> {
+        scope.iter()
+            .flat_map(|type_path| self.ctx.symtab_marcher.get_symbol_children(type_path))
+            .filter(|ch| ch.path().components().next_back().map(|c| c.category == category).unwrap_or(false))
+            .map(|ch| ch.name())
+            .collect()
+    }
+
+
+    fn check_type_ident(&mut self, n: &IdentifierNode) {
+        if n.is_missing() {
+            return;
+        }
+
+        let type_name = n.value(self.ctx.doc);
+        if type_name == ArrayTypeSymbol::TYPE_NAME || self.ctx.symtab_marcher.contains_symbol(&BasicTypeSymbolPath::new(&type_name)) {
+            return;
+        }
+
+        let suggestion = suggest_name(&type_name, self.root_symbols().iter()
+            .filter(|v| v.path().components().next().map(|c| c.category == SymbolCategory::Type).unwrap_or(false))
+            // arrays can't be written without a type argument and states can't be referred to by name
+            .filter(|v| !v.is_array() && !v.is_state())
+            .map(|v| v.name()));
+
+        self.diagnostics.push(Diagnostic {
+            range: n.range(),
+            kind: DiagnosticKind::UnknownType {
+                type_name: type_name.to_string(),
+                suggestion
+            }
+        });
+    }
+
+    fn check_type_annotation(&mut self, n: &TypeAnnotationNode) {
+        self.check_type_ident(&n.type_name());
+
+        if let Some(type_arg) = n.type_arg() {
+            self.check_type_annotation(&type_arg);
+        }
+    }
+
+    fn check_annotation(&mut self, n: &AnnotationNode) {
+        if let Some(arg) = n.arg() {
+            self.check_type_ident(&arg);
+        }
+    }
+
+    fn check_identifier(&mut self, n: &IdentifierNode, ctx: &TraversalContextStack) {
+        if n.is_missing() {
+            return;
+        }
+
+        let name = n.value(self.ctx.doc);
+        let category = if ctx.top() == TraversalContext::FunctionCallExpressionFunc {
+            SymbolCategory::Callable
+        } else {
+            SymbolCategory::Data
+        };
+
+        if self.ctx.unl_payload.borrow().contains(&name, category)
+        || self.ctx.symtab_marcher.contains_symbol(&SymbolPathBuf::new(&name, category)) {
+            return;
+        }
+
+        // names visible in the scope of a type may not have all been put into the lookup,
+        // e.g. methods added to a class with @addMethod from a different content
+        let mut scope = Vec::new();
+        if let Some(scope_type_path) = self.ctx.scope_type_path() {
+            match self.ctx.member_scope(&scope_type_path) {
+                Some(s) => scope = s,
+                None => return
+            }
+
+            if scope.iter().any(|type_path| self.ctx.symtab_marcher.contains_symbol(&type_path.join_component(&name, category))) {
+                return;
+            }
+        }
+
+        let unl = self.ctx.unl_payload.borrow();
+        let visible_names = unl.iter()
+            .filter_map(|p| p.components().next_back())
+            .filter(|c| c.category == category)
+            .map(|c| c.name);
+        let member_names = self.member_names(&scope, category);
+        let global_names = self.root_symbols().iter()
+            .filter(|v| v.path().components().next().map(|c| c.category == category).unwrap_or(false))
+            .map(|v| v.name());
+
+        let suggestion = suggest_name(&name, visible_names.chain(member_names).chain(global_names));
+        drop(unl);
+
+        self.diagnostics.push(Diagnostic {
+            range: n.range(),
+            kind: DiagnosticKind::UnknownIdentifier {
+                name: name.to_string(),
+                suggestion
+            }
+        });
+    }
+
+    fn check_member_access(&mut self, n: &MemberAccessExpressionNode, ctx: &TraversalContextStack) {
+        let member = n.member();
+        if member.is_missing() {
+            return;
+        }
+
+        let accessor_type = match self.ctx.expression_type(n.accessor(), TraversalContext::MemberAccessExpressionAccessor) {
+            Some(typ) => typ,
+            None => return
+        };
+
+        let scope = match self.ctx.member_scope(&accessor_type.path) {
+            Some(scope) => scope,
+            None => return
+        };
+
+        let member_name = member.value(self.ctx.doc);
+        let category = if ctx.top() == TraversalContext::FunctionCallExpressionFunc {
+            SymbolCategory::Callable
+        } else {
+            SymbolCategory::Data
+        };
+
+        if scope.iter().any(|type_path| self.ctx.symtab_marcher.contains_symbol(&type_path.join_component(&member_name, category))) {
+            return;
+        }
+
+        let suggestion = suggest_name(&member_name, self.member_names(&scope, category));
+
+        self.diagnostics.push(Diagnostic {
+            range: member.range(),
+            kind: DiagnosticKind::UnknownMember {
+                member_name: member_name.to_string(),
+                type_name: accessor_type.name(),
+                suggestion
+            }
+        });
+    }
+}
+
+impl SyntaxNodeVisitor for UnresolvedSymbolsVisitor<'_> {
+    fn traversal_policy_default(&self) -> bool {
+        true
+    }
+
+
+    fn visit_class_decl(&mut self, n: &ClassDeclarationNode) -> ClassDeclarationTraversalPolicy {
+        if let Some(base) = n.base() {
+            self.check_type_ident(&base);
+        }
+
+        TraversalPolicy::default_to(true)
+    }
+
+    fn visit_state_decl(&mut self, n: &StateDeclarationNode) -> StateDeclarationTraversalPolicy {
+        self.check_type_ident(&n.parent());
+
+        TraversalPolicy::default_to(true)
+    }
+
+    fn visit_global_var_decl(&mut self, n: &MemberVarDeclarationNode) {
+        if let Some(annot) = n.annotation() {
+            self.check_annotation(&annot);
+        }
+
+        self.check_type_annotation(&n.var_type());
+    }
+
+    fn visit_member_var_decl(&mut self, n: &MemberVarDeclarationNode, _: &TraversalContextStack) {
+        self.check_type_annotation(&n.var_type());
+    }
+
+    fn visit_autobind_decl(&mut self, n: &AutobindDeclarationNode, _: &TraversalContextStack) {
+        self.check_type_annotation(&n.autobind_type());
+    }
+
+    fn visit_member_default_val(&mut self, n: &MemberDefaultValueNode, ctx: &TraversalContextStack) -> MemberDefaultValueTraversalPolicy {
+        self.check_identifier(&n.member(), ctx);
+
+        TraversalPolicy::default_to(true)
+    }
+
+    fn visit_member_defaults_block_assignment(&mut self, n: &MemberDefaultsBlockAssignmentNode, ctx: &TraversalContextStack) -> MemberDefaultValueTraversalPolicy {
+        self.check_identifier(&n.member(), ctx);
+
+        TraversalPolicy::default_to(true)
+    }
+
+    fn visit_member_hint(&mut self, n: &MemberHintNode, ctx: &TraversalContextStack) {
+        self.check_identifier(&n.member(), ctx);
+    }
+
+    fn visit_global_func_decl(&mut self, n: &FunctionDeclarationNode) -> FunctionDeclarationTraversalPolicy {
+        if let Some(annot) = n.annotation() {
+            self.check_annotation(&annot);
+        }
+
+        if let Some(rt) = n.return_type() {
+            self.check_type_annotation(&rt);
+        }
+
+        TraversalPolicy::default_to(true)
+    }
+
+    fn visit_member_func_decl(&mut self, n: &FunctionDeclarationNode, _: &TraversalContextStack) -> FunctionDeclarationTraversalPolicy {
+        if let Some(rt) = n.return_type() {
+            self.check_type_annotation(&rt);
+        }
+
+        TraversalPolicy::default_to(true)
+    }
+
+    fn visit_event_decl(&mut self, n: &EventDeclarationNode, _: &TraversalContextStack) -> EventDeclarationTraversalPolicy {
+        if let Some(rt) = n.return_type() {
+            self.check_type_annotation(&rt);
+        }
+
+        TraversalPolicy::default_to(true)
+    }
+
+    fn visit_func_param_group(&mut self, n: &FunctionParameterGroupNode, _: &TraversalContextStack) {
+        self.check_type_annotation(&n.param_type());
+    }
+
+    fn visit_local_var_decl_stmt(&mut self, n: &LocalVarDeclarationNode, _: &TraversalContextStack) -> VarDeclarationTraversalPolicy {
+        self.check_type_annotation(&n.var_type());
+
+        TraversalPolicy::default_to(true)
+    }
+
+
+    fn visit_identifier_expr(&mut self, n: &IdentifierNode, ctx: &TraversalContextStack) {
+        self.check_identifier(n, ctx);
+    }
+
+    fn visit_member_access_expr(&mut self, n: &MemberAccessExpressionNode, ctx: &TraversalContextStack) -> MemberFieldExpressionTraversalPolicy {
+        self.check_member_access(n, ctx);
+
+        TraversalPolicy::default_to(true)
+    }
+
+    fn visit_new_expr(&mut self, n: &NewExpressionNode, _: &TraversalContextStack) -> NewExpressionTraversalPolicy {
+        self.check_type_ident(&n.class());
+
+        TraversalPolicy::default_to(true)
+    }
+
+    fn visit_type_cast_expr(&mut self, n: &TypeCastExpressionNode, _: &TraversalContextStack) -> TypeCastExpressionTraversalPolicy {
+        self.check_type_ident(&n.target_type());
+
+        TraversalPolicy::default_to(true)
+    }
+}
+
+impl SyntaxNodeVisitorChainLink for UnresolvedSymbolsVisitor<'_> {}
@@ -4,6 +4,9 @@ pub use inject_native_symbols::{inject_primitives, inject_globals};
 mod scan_symbols;
 pub use scan_symbols::scan_symbols;
 
+mod scan_references;
+pub use scan_references::{scan_references, ReferenceScannerVisitor, ReferenceSink};
+
 mod syntax_analysis;
 pub use syntax_analysis::{syntax_analysis, contextual_syntax_analysis};
 
@@ -11,4 +14,7 @@ mod merge_symtabs;
 pub use merge_symtabs::merge_symbol_tables;
 
 mod workspace_symbol_analysis;
-pub use workspace_symbol_analysis::workspace_symbol_analysis;
\ No newline at end of file
+pub use workspace_symbol_analysis::workspace_symbol_analysis;
+
+mod expression_analysis;
+pub use expression_analysis::expression_analysis;
@@ -0,0 +1,291 @@
+use std::cell::RefCell;
+use std::path::Path;
+use std::rc::Rc;
+use std::sync::Arc;
+use lsp_types as lsp;
+use witcherscript::script_document::ScriptDocument;
+use witcherscript::Script;
+use witcherscript::tokens::*;
+use witcherscript::ast::*;
+use crate::symbol_analysis::references::ReferenceIndex;
+use crate::symbol_analysis::symbol_path::SymbolPath;
+use crate::symbol_analysis::symbol_table::marcher::SymbolTableMarcher;
+use crate::symbol_analysis::symbols::*;
+use crate::symbol_analysis::unqualified_name_lookup::{UnqualifiedNameLookup, UnqualifiedNameLookupBuilder};
+use crate::utils::{evaluate_expression, SymbolPathBuilder, SymbolPathBuilderPayload};
+
+
+/// Finds all places in the script where symbols are referenced by name and records them in the index.
+/// The marcher should start with the symbol table of the content that the script belongs to.
+pub fn scan_references(
+    script: &Script,
+    doc: &ScriptDocument,
+    local_source_path: &Path,
+    symtab_marcher: SymbolTableMarcher,
+    index: &mut ReferenceIndex
+) {
+    let (sympath_builder, sympath_builder_payload) = SymbolPathBuilder::new(doc);
+    let (unl_builder, unl_payload) = UnqualifiedNameLookupBuilder::new(doc, sympath_builder_payload.clone(), symtab_marcher.clone());
+    let mut sink = ReferenceIndexSink {
+        index,
+        local_source_path: local_source_path.into()
+    };
+    let scanner = ReferenceScannerVisitor::new(&mut sink, doc, symtab_marcher, sympath_builder_payload, unl_payload);
+
+    let mut chain = SyntaxNodeVisitorChain::new()
+        .link(sympath_builder)
+        .link(unl_builder)
+        .link(scanner);
+
+    script.visit_nodes(&mut chain);
+}
+
+
+/// Receives references found by [`ReferenceScannerVisitor`].
+/// Only references to symbols that could be fully resolved are passed on.
+pub trait ReferenceSink {
+    fn insert_reference(&mut self, sympath: &SymbolPath, range: lsp::Range);
+
+    /// Called for a call made from inside of a function, on top of the reference to the callee
+    fn insert_call(&mut self, _callee_path: &SymbolPath, _caller_path: &SymbolPath, _range: lsp::Range) {}
+}
+
+struct ReferenceIndexSink<'a> {
+    index: &'a mut ReferenceIndex,
+    local_source_path: Arc<Path>
+}
+
+impl ReferenceSink for ReferenceIndexSink<'_> {
+    fn insert_reference(&mut self, sympath: &SymbolPath, range: lsp::Range) {
+        self.index.insert_reference(sympath, self.local_source_path.clone(), range);
+    }
+
+    fn insert_call(&mut self, callee_path: &SymbolPath, caller_path: &SymbolPath, range: lsp::Range) {
+        self.index.insert_call(callee_path, caller_path, self.local_source_path.clone(), range);
+    }
+}
+
+
+/// A node visitor that finds all places in the script where symbols are referenced by name and passes them to the sink.
+/// Expects to work after SymbolPathBuilder and UnqualifiedNameLookupBuilder in visitor chain.
+pub struct ReferenceScannerVisitor<'a, S: ReferenceSink> {
+    sink: &'a mut S,
+    doc: &'a ScriptDocument,
+    symtab_marcher: SymbolTableMarcher<'a>,
+    sympath_builder_payload: Rc<RefCell<SymbolPathBuilderPayload>>,
+    unl_payload: Rc<RefCell<UnqualifiedNameLookup>>
+}
+
+impl<'a, S: ReferenceSink> ReferenceScannerVisitor<'a, S> {
+    pub fn new(
+        sink: &'a mut S,
+        doc: &'a ScriptDocument,
+        symtab_marcher: SymbolTableMarcher<'a>,
+        sympath_builder_payload: Rc<RefCell<SymbolPathBuilderPayload>>,
+        unl_payload: Rc<RefCell<UnqualifiedNameLookup>>
+    ) -> Self {
+        Self {
+            sink,
+            doc,
+            symtab_marcher,
+            sympath_builder_payload,
+            unl_payload
+        }
+    }
+
+    fn insert(&mut self, sympath: &SymbolPath, range: lsp::Range) {
+        // unresolved symbols are not worth keeping
+        if !sympath.is_empty() && !sympath.has_missing() && !sympath.has_unknown() {
+            self.sink.insert_reference(sympath, range);
+        }
+    }
+
+    fn insert_type_ident(&mut self, n: &IdentifierNode) {
+        let type_name = n.value(self.doc);
+        if type_name != ArrayTypeSymbol::TYPE_NAME {
+            self.insert(&BasicTypeSymbolPath::new(&type_name), n.range());
+        }
+    }
+
+    fn insert_type_annotation(&mut self, n: &TypeAnnotationNode) {
+        self.insert_type_ident(&n.type_name());
+
+        if let Some(type_arg) = n.type_arg() {
+            self.insert_type_annotation(&type_arg);
+        }
+    }
+
+    fn insert_annotation(&mut self, n: &AnnotationNode) {
+        if let Some(arg) = n.arg() {
+            self.insert_type_ident(&arg);
+        }
+    }
+
+    fn insert_expression(&mut self, ident_range: lsp::Range, expr: ExpressionNode, ctx: &TraversalContextStack) {
+        let sympath = evaluate_expression(
+            expr, ctx.top(),
+            self.doc,
+            self.symtab_marcher.clone(),
+            self.sympath_builder_payload.clone(),
+            self.unl_payload.clone()
+        );
+
+        self.insert(&sympath, ident_range);
+    }
+
+    fn insert_call(&mut self, n: &FunctionCallExpressionNode) {
+        let func = n.func();
+        let ident_range = match func.clone().value() {
+            Expression::MemberAccess(access) => access.member().range(),
+            _ => func.range()
+        };
+
+        let mut callee_path = evaluate_expression(
+            func, TraversalContext::FunctionCallExpressionFunc,
+            self.doc,
+            self.symtab_marcher.clone(),
+            self.sympath_builder_payload.clone(),
+            self.unl_payload.clone()
+        );
+
+        // a call to wrappedMethod is a call to the method that is being wrapped
+        if let Some(wrapped_method) = self.symtab_marcher.get_symbol(&callee_path).and_then(|v| v.try_as_wrapped_method_ref()) {
+            callee_path = wrapped_method.wrapped_path().to_owned().into();
+        }
+
+        let caller_path = self.sympath_builder_payload.borrow().current_sympath.clone();
+        let is_inside_callable = caller_path.components()
+            .next_back()
+            .map(|c| c.category == SymbolCategory::Callable)
+            .unwrap_or(false);
+
+        if is_inside_callable && !callee_path.is_empty() && !callee_path.has_missing() && !callee_path.has_unknown() {
+            self.sink.insert_call(&callee_path, &caller_path, ident_range);
+        }
+    }
+}
+
+impl<S: ReferenceSink> SyntaxNodeVisitor for ReferenceScannerVisitor<'_, S> {
+    fn traversal_policy_default(&self) -> bool {
+        true
+    }
+
+
+    fn visit_class_decl(&mut self, n: &ClassDeclarationNode) -> ClassDeclarationTraversalPolicy {
+        if let Some(base) = n.base() {
+            self.insert_type_ident(&base);
+        }
+
+        TraversalPolicy::default_to(true)
+    }
+
+    fn visit_state_decl(&mut self, n: &StateDeclarationNode) -> StateDeclarationTraversalPolicy {
+        self.insert_type_ident(&n.parent());
+
+        TraversalPolicy::default_to(true)
+    }
+
+    fn visit_global_var_decl(&mut self, n: &MemberVarDeclarationNode) {
+        if let Some(annot) = n.annotation() {
+            self.insert_annotation(&annot);
+        }
+
+        self.insert_type_annotation(&n.var_type());
+    }
+
+    fn visit_member_var_decl(&mut self, n: &MemberVarDeclarationNode, _: &TraversalContextStack) {
+        self.insert_type_annotation(&n.var_type());
+    }
+
+    fn visit_autobind_decl(&mut self, n: &AutobindDeclarationNode, _: &TraversalContextStack) {
+        self.insert_type_annotation(&n.autobind_type());
+    }
+
+    fn visit_member_default_val(&mut self, n: &MemberDefaultValueNode, ctx: &TraversalContextStack) -> MemberDefaultValueTraversalPolicy {
+        let member = n.member();
+        self.insert_expression(member.range(), member.into(), ctx);
+
+        TraversalPolicy::default_to(true)
+    }
+
+    fn visit_member_defaults_block_assignment(&mut self, n: &MemberDefaultsBlockAssignmentNode, ctx: &TraversalContextStack) -> MemberDefaultValueTraversalPolicy {
+        let member = n.member();
+        self.insert_expression(member.range(), member.into(), ctx);
+
+        TraversalPolicy::default_to(true)
+    }
+
+    fn visit_member_hint(&mut self, n: &MemberHintNode, ctx: &TraversalContextStack) {
+        let member = n.member();
+        self.insert_expression(member.range(), member.into(), ctx);
+    }
+
+    fn visit_global_func_decl(&mut self, n: &FunctionDeclarationNode) -> FunctionDeclarationTraversalPolicy {
+        if let Some(annot) = n.annotation() {
+            self.insert_annotation(&annot);
+        }
+
+        if let Some(rt) = n.return_type() {
+            self.insert_type_annotation(&rt);
+        }
+
+        TraversalPolicy::default_to(true)
+    }
+
+    fn visit_member_func_decl(&mut self, n: &FunctionDeclarationNode, _: &TraversalContextStack) -> FunctionDeclarationTraversalPolicy {
+        if let Some(rt) = n.return_type() {
+            self.insert_type_annotation(&rt);
+        }
+
+        TraversalPolicy::default_to(true)
+    }
+
+    fn visit_event_decl(&mut self, n: &EventDeclarationNode, _: &TraversalContextStack) -> EventDeclarationTraversalPolicy {
+        if let Some(rt) = n.return_type() {
+            self.insert_type_annotation(&rt);
+        }
+
+        TraversalPolicy::default_to(true)
+    }
+
+    fn visit_func_param_group(&mut self, n: &FunctionParameterGroupNode, _: &TraversalContextStack) {
+        self.insert_type_annotation(&n.param_type());
+    }
+
+    fn visit_local_var_decl_stmt(&mut self, n: &LocalVarDeclarationNode, _: &TraversalContextStack) -> VarDeclarationTraversalPolicy {
+        self.insert_type_annotation(&n.var_type());
+
+        TraversalPolicy::default_to(true)
+    }
+
+
+    fn visit_identifier_expr(&mut self, n: &IdentifierNode, ctx: &TraversalContextStack) {
+        self.insert_expression(n.range(), n.clone().into(), ctx);
+    }
+
+    fn visit_member_access_expr(&mut self, n: &MemberAccessExpressionNode, ctx: &TraversalContextStack) -> MemberFieldExpressionTraversalPolicy {
+        self.insert_expression(n.member().range(), n.clone().into(), ctx);
+
+        TraversalPolicy::default_to(true)
+    }
+
+    fn visit_func_call_expr(&mut self, n: &FunctionCallExpressionNode, _: &TraversalContextStack) -> FunctionCallExpressionTraversalPolicy {
+        self.insert_call(n);
+
+        TraversalPolicy::default_to(true)
+    }
+
+    fn visit_new_expr(&mut self, n: &NewExpressionNode, _: &TraversalContextStack) -> NewExpressionTraversalPolicy {
+        self.insert_type_ident(&n.class());
+
+        TraversalPolicy::default_to(true)
+    }
+
+    fn visit_type_cast_expr(&mut self, n: &TypeCastExpressionNode, _: &TraversalContextStack) -> TypeCastExpressionTraversalPolicy {
+        self.insert_type_ident(&n.target_type());
+
+        TraversalPolicy::default_to(true)
+    }
+}
+
+impl<S: ReferenceSink> SyntaxNodeVisitorChainLink for ReferenceScannerVisitor<'_, S> {}
@@ -4,7 +4,7 @@ use std::sync::Arc;
 use lsp_types::Range;
 use witcherscript::attribs::*;
 use witcherscript::script_document::ScriptDocument;
-use witcherscript::Script;
+use witcherscript::{Script, SyntaxNode};
 use witcherscript::tokens::*;
 use witcherscript::ast::*;
 use witcherscript_diagnostics::*;
@@ -114,19 +114,12 @@ impl SymbolScannerVisitor<'_> {
     /// Returns type path and type name, if it's invalid returns empty path
     fn check_type_from_identifier(&mut self, n: IdentifierNode) -> BasicTypeSymbolPath {
         let type_name = n.value(&self.doc);
+        // missing type argument is reported by expression analysis, which can also deduce it
         if type_name == ArrayTypeSymbol::TYPE_NAME {
-            self.diagnostics.push(LocatedDiagnostic { 
-                path: self.symtab.script_root().join(&self.local_source_path).unwrap(), 
-                diagnostic: Diagnostic { 
-                    range: n.range(), 
-                    kind: DiagnosticKind::MissingTypeArg
-                }
-            });
+            BasicTypeSymbolPath::unknown()
         } else {
-            return BasicTypeSymbolPath::new(&type_name);
+            BasicTypeSymbolPath::new(&type_name)
         }
-
-        BasicTypeSymbolPath::unknown()
     }
 
     /// Returns type path and type name, if it's invalid returns empty path
@@ -171,6 +164,29 @@ impl SymbolScannerVisitor<'_> {
     }
 
 
+    /// Joins comments written directly above a declaration into a documentation text
+    fn parse_doc_comment<T>(&self, n: &SyntaxNode<'_, T>) -> Option<String> {
+        let mut lines = Vec::new();
+        for comment_node in n.preceding_comments() {
+            let comment = comment_node.value(self.doc);
+            match comment.kind {
+                CommentKind::Line => {
+                    // `///` style comments are popular as well
+                    lines.push(comment.content().trim_start_matches('/').trim().to_string());
+                },
+                CommentKind::Block => {
+                    lines.extend(comment.content()
+                        .lines()
+                        .map(|line| line.trim().trim_start_matches('*').trim().to_string()));
+                }
+            }
+        }
+
+        let first = lines.iter().position(|line| !line.is_empty())?;
+        let last = lines.iter().rposition(|line| !line.is_empty())?;
+        Some(lines[first..=last].join("\n"))
+    }
+
     fn parse_global_function(&mut self, n: &FunctionDeclarationNode, path: GlobalCallableSymbolPath) -> GlobalFunctionSymbol {
         let mut sym = GlobalFunctionSymbol::new(path, SymbolLocation { 
             scripts_root: self.symtab.script_root_arc(), 
@@ -179,6 +195,8 @@ impl SymbolScannerVisitor<'_> {
             label_range: n.name().range()
         });
 
+        sym.doc_comment = self.parse_doc_comment(n);
+
         sym.specifiers = n.specifiers()
             .map(|sn| sn.value())
             .filter_map(|s| GlobalFunctionSpecifier::try_from(s).ok())
@@ -205,6 +223,8 @@ impl SymbolScannerVisitor<'_> {
             label_range: n.name().range()
         });
 
+        sym.doc_comment = self.parse_doc_comment(n);
+
         sym.specifiers = n.specifiers()
             .map(|sn| sn.value())
             .filter_map(|s| MemberFunctionSpecifier::try_from(s).ok())
@@ -255,6 +275,8 @@ impl SyntaxNodeVisitor for SymbolScannerVisitor<'_> {
                 .filter_map(|s| ClassSpecifier::try_from(s).ok())
                 .collect();
 
+            sym.doc_comment = self.parse_doc_comment(n);
+
             sym.base_path = n.base().map(|base| self.check_type_from_identifier(base));
 
 
@@ -307,6 +329,8 @@ impl SyntaxNodeVisitor for SymbolScannerVisitor<'_> {
                 .filter_map(|s| StateSpecifier::try_from(s).ok())
                 .collect();
 
+            sym.doc_comment = self.parse_doc_comment(n);
+
             sym.base_state_name = n.base().map(|base| base.value(&self.doc).to_string());
 
 
@@ -364,6 +388,8 @@ impl SyntaxNodeVisitor for SymbolScannerVisitor<'_> {
                 .filter_map(|s| StructSpecifier::try_from(s).ok())
                 .collect();
 
+            sym.doc_comment = self.parse_doc_comment(n);
+
             sym.path().clone_into(&mut self.current_path);
             self.symtab.insert_primary_symbol(sym);
 
@@ -402,12 +428,13 @@ impl SyntaxNodeVisitor for SymbolScannerVisitor<'_> {
         let enum_name = name_node.value(&self.doc);
         let path = BasicTypeSymbolPath::new(&enum_name);
         if self.check_contains(&path, name_node.range(), SymbolType::Enum) {
-            let sym = EnumSymbol::new(path, SymbolLocation { 
+            let mut sym = EnumSymbol::new(path, SymbolLocation { 
                 scripts_root: self.symtab.script_root_arc(), 
                 local_source_path: self.local_source_path.clone(), 
                 range: n.range(), 
                 label_range: name_node.range()
             });
+            sym.doc_comment = self.parse_doc_comment(n);
 
             sym.path().clone_into(&mut self.current_path);
             self.symtab.insert_primary_symbol(sym);
@@ -439,6 +466,7 @@ impl SyntaxNodeVisitor for SymbolScannerVisitor<'_> {
                 label_range: name_node.range()
             });
             sym.parent_enum_path = BasicTypeSymbolPath::new(self.current_path.components().next().unwrap().name);
+            sym.doc_comment = self.parse_doc_comment(n);
 
             let value = n.value()
                 .and_then(|v| match v {
@@ -571,6 +599,7 @@ impl SyntaxNodeVisitor for SymbolScannerVisitor<'_> {
                 .collect();
 
             let type_path = self.check_type_from_type_annot(n.var_type());
+            let doc_comment = self.parse_doc_comment(n);
 
             for name_node in n.names() {
                 let var_name = name_node.value(&self.doc);
@@ -584,6 +613,7 @@ impl SyntaxNodeVisitor for SymbolScannerVisitor<'_> {
                     });
                     sym.specifiers = specifiers.clone();
                     sym.type_path = type_path.clone();
+                    sym.doc_comment = doc_comment.clone();
                     sym.ordinal = 0; // no way to know the real order, it's not needed for classes anyways
 
                     let sym = MemberVarInjectorSymbol::new(sym);
@@ -630,12 +660,13 @@ impl SyntaxNodeVisitor for SymbolScannerVisitor<'_> {
         let event_name = name_node.value(&self.doc);
         let path = MemberCallableSymbolPath::new(&self.current_path, &event_name);
         if self.check_contains(&path, name_node.range(), SymbolType::Event) {
-            let sym = EventSymbol::new(path, SymbolLocation { 
+            let mut sym = EventSymbol::new(path, SymbolLocation { 
                 scripts_root: self.symtab.script_root_arc(), 
                 local_source_path: self.local_source_path.clone(), 
                 range: n.range(), 
                 label_range: name_node.range()
             });
+            sym.doc_comment = self.parse_doc_comment(n);
 
             sym.path().clone_into(&mut self.current_path);
             self.symtab.insert_symbol(sym);
@@ -693,6 +724,7 @@ impl SyntaxNodeVisitor for SymbolScannerVisitor<'_> {
             .collect();
 
         let type_path = self.check_type_from_type_annot(n.var_type());
+        let doc_comment = self.parse_doc_comment(n);
 
         
         for name_node in n.names() {
@@ -709,6 +741,7 @@ impl SyntaxNodeVisitor for SymbolScannerVisitor<'_> {
                 sym.specifiers = specifiers.clone();
                 sym.type_path = type_path.clone();
                 sym.ordinal = self.current_var_ordinal;
+                sym.doc_comment = doc_comment.clone();
 
                 self.symtab.insert_symbol(sym);
 
@@ -748,6 +781,7 @@ impl SyntaxNodeVisitor for SymbolScannerVisitor<'_> {
                 .collect();
 
             sym.type_path = self.check_type_from_type_annot(n.autobind_type());
+            sym.doc_comment = self.parse_doc_comment(n);
 
             self.symtab.insert_symbol(sym);
         }
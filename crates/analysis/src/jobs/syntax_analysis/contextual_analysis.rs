@@ -11,24 +11,53 @@ pub fn contextual_syntax_analysis(script: &Script, doc: &ScriptDocument, diagnos
     let mut visitor = ContextualSyntaxAnalysis {
         doc,
         diagnostics,
-        visited_non_var_stmt_before: false
+        visited_non_var_stmt_before: false,
+        valid_local_var_placement: lsp::Position::default(),
+        annotated_classes: Vec::new(),
+        global_var_diag_indices: Vec::new()
     };
 
     script.visit_nodes(&mut visitor);
+    visitor.fill_add_field_targets();
 }
 
 struct ContextualSyntaxAnalysis<'a> {
     doc: &'a ScriptDocument,
     diagnostics: &'a mut Vec<Diagnostic>,
-    visited_non_var_stmt_before: bool
+    visited_non_var_stmt_before: bool,
+    /// Where misplaced local variable declarations could be moved to in the current function
+    valid_local_var_placement: lsp::Position,
+    /// Names of classes that annotated declarations in the script refer to
+    annotated_classes: Vec<String>,
+    /// Indices of GlobalScopeVarDecl diagnostics
+    global_var_diag_indices: Vec<usize>
 }
 
 impl ContextualSyntaxAnalysis<'_> {
+    /// Global variables are most likely meant to be added to the same class as other annotated declarations in the script.
+    /// Those can also come after the variable, so the class can only be known once the whole script has been visited.
+    fn fill_add_field_targets(&mut self) {
+        if let [class_name] = self.annotated_classes.as_slice() {
+            for &i in &self.global_var_diag_indices {
+                if let DiagnosticKind::GlobalScopeVarDecl { target_class } = &mut self.diagnostics[i].kind {
+                    *target_class = Some(class_name.clone());
+                }
+            }
+        }
+    }
+
     fn visit_annotation(&mut self, n: &AnnotationNode, annotated_node_kind: &str, annotated_node_range: lsp::Range) -> Option<AnnotationKind> {
         let name_node = n.name();
         let name = name_node.value(self.doc);
         match AnnotationKind::from_str(&name) {
             Ok(kind) => {
+                if let Some(arg) = n.arg() {
+                    let class_name = arg.value(self.doc).to_string();
+                    if !self.annotated_classes.contains(&class_name) {
+                        self.annotated_classes.push(class_name);
+                    }
+                }
+
                 if kind.requires_arg() && n.arg().is_none() {
                     self.diagnostics.push(Diagnostic {
                         range: name_node.range(),
@@ -78,6 +107,14 @@ impl ContextualSyntaxAnalysis<'_> {
         }
     }
 
+    fn enter_function_definition(&mut self, def: FunctionDefinitionNode) {
+        if let FunctionDefinition::Some(block) = def.value() {
+            // right after the opening brace
+            let block_start = block.range().start;
+            self.valid_local_var_placement = lsp::Position::new(block_start.line, block_start.character + 1);
+        }
+    }
+
     fn check_function_specifiers(&mut self, n: &FunctionDeclarationNode, global: bool) {
         if global {
             let mut specifiers = SmallVec::<[GlobalFunctionSpecifier; 2]>::new();
@@ -258,6 +295,8 @@ impl SyntaxNodeVisitor for ContextualSyntaxAnalysis<'_> {
             self.check_function_specifiers(n, true);
         }
 
+        self.enter_function_definition(n.definition());
+
         TraversalPolicy::default_to(true)
     }
 
@@ -269,9 +308,12 @@ impl SyntaxNodeVisitor for ContextualSyntaxAnalysis<'_> {
         if let Some(annot) = n.annotation() {
             self.visit_annotation(&annot, MemberVarDeclarationNode::NODE_KIND, n.range());
         } else {
+            self.global_var_diag_indices.push(self.diagnostics.len());
             self.diagnostics.push(Diagnostic {
                 range: n.range(),
-                kind: DiagnosticKind::GlobalScopeVarDecl
+                kind: DiagnosticKind::GlobalScopeVarDecl {
+                    target_class: None
+                }
             })
         }
 
@@ -365,6 +407,7 @@ impl SyntaxNodeVisitor for ContextualSyntaxAnalysis<'_> {
         }
 
         self.check_function_specifiers(n, false);
+        self.enter_function_definition(n.definition());
 
         TraversalPolicy::default_to(true)
     }
@@ -373,7 +416,9 @@ impl SyntaxNodeVisitor for ContextualSyntaxAnalysis<'_> {
         self.visited_non_var_stmt_before = false;
     }
 
-    fn visit_event_decl(&mut self, _: &EventDeclarationNode, _: &TraversalContextStack) -> EventDeclarationTraversalPolicy {
+    fn visit_event_decl(&mut self, n: &EventDeclarationNode, _: &TraversalContextStack) -> EventDeclarationTraversalPolicy {
+        self.enter_function_definition(n.definition());
+
         TraversalPolicy::default_to(true)
     }
 
@@ -440,10 +485,22 @@ impl SyntaxNodeVisitor for ContextualSyntaxAnalysis<'_> {
 
     fn visit_local_var_decl_stmt(&mut self, n: &LocalVarDeclarationNode, _: &TraversalContextStack) -> VarDeclarationTraversalPolicy {
         if self.visited_non_var_stmt_before {
+            let names_range = n.names()
+                .map(|name| name.range())
+                .reduce(|acc, r| lsp::Range::new(acc.start, r.end))
+                .unwrap_or_default();
+
             self.diagnostics.push(Diagnostic {
                 range: n.range(),
-                kind: DiagnosticKind::InvalidLocalVarPlacement
+                kind: DiagnosticKind::InvalidLocalVarPlacement {
+                    valid_placement: self.valid_local_var_placement,
+                    names_range,
+                    var_type_range: n.var_type().range(),
+                    init_value_range: n.init_value().map(|v| v.range())
+                }
             });
+        } else {
+            self.valid_local_var_placement = n.range().end;
         }
 
         VarDeclarationTraversalPolicy {
@@ -1,4 +1,5 @@
 pub mod symbols;
 pub mod symbol_table;
 pub mod symbol_path;
-pub mod unqualified_name_lookup;
\ No newline at end of file
+pub mod unqualified_name_lookup;
+pub mod references;
\ No newline at end of file
@@ -0,0 +1,164 @@
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::Arc;
+use abs_path::AbsPath;
+use lsp_types as lsp;
+use crate::symbol_analysis::symbol_path::{SymbolPath, SymbolPathBuf};
+
+
+/// Contains information about where symbols are referenced in the code of a given content.
+/// References are identified by the path of a symbol they point to.
+/// The symbol itself does not need to come from the same content.
+/// 
+/// Function calls are additionally tracked together with the callable they're made from.
+#[derive(Debug, Clone)]
+pub struct ReferenceIndex {
+    script_root: Arc<AbsPath>,
+    references: HashMap<SymbolPathBuf, Vec<ReferenceLocation>>,
+    calls: HashMap<SymbolPathBuf, Vec<CallLocation>>,
+    /// Paths of symbols referenced in given local paths in a source tree
+    source_path_assocs: HashMap<Arc<Path>, HashSet<SymbolPathBuf>>
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReferenceLocation {
+    pub scripts_root: Arc<AbsPath>,
+    pub local_source_path: Arc<Path>,
+    /// Range of the identifier that references a symbol
+    pub range: lsp::Range
+}
+
+impl ReferenceLocation {
+    #[inline]
+    pub fn abs_source_path(&self) -> AbsPath {
+        self.scripts_root.join(self.local_source_path.as_ref()).unwrap()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallLocation {
+    /// Path of the function or event inside of which the call is made
+    pub caller_path: SymbolPathBuf,
+    pub scripts_root: Arc<AbsPath>,
+    pub local_source_path: Arc<Path>,
+    /// Range of the identifier of the called function
+    pub range: lsp::Range
+}
+
+impl CallLocation {
+    #[inline]
+    pub fn abs_source_path(&self) -> AbsPath {
+        self.scripts_root.join(self.local_source_path.as_ref()).unwrap()
+    }
+}
+
+
+impl ReferenceIndex {
+    pub fn new(scripts_root: Arc<AbsPath>) -> Self {
+        Self {
+            script_root: scripts_root,
+            references: HashMap::new(),
+            calls: HashMap::new(),
+            source_path_assocs: HashMap::new()
+        }
+    }
+
+    pub fn script_root(&self) -> &AbsPath {
+        &self.script_root
+    }
+
+    pub fn script_root_arc(&self) -> Arc<AbsPath> {
+        self.script_root.clone()
+    }
+
+
+    pub(crate) fn insert_reference(&mut self, sympath: &SymbolPath, local_source_path: Arc<Path>, range: lsp::Range) {
+        self.source_path_assocs.entry(local_source_path.clone())
+            .or_default()
+            .insert(sympath.to_owned());
+
+        self.references.entry(sympath.to_owned())
+            .or_default()
+            .push(ReferenceLocation {
+                scripts_root: self.script_root.clone(),
+                local_source_path,
+                range
+            });
+    }
+
+    pub(crate) fn insert_call(&mut self, callee_path: &SymbolPath, caller_path: &SymbolPath, local_source_path: Arc<Path>, range: lsp::Range) {
+        self.source_path_assocs.entry(local_source_path.clone())
+            .or_default()
+            .insert(callee_path.to_owned());
+
+        self.calls.entry(callee_path.to_owned())
+            .or_default()
+            .push(CallLocation {
+                caller_path: caller_path.to_owned(),
+                scripts_root: self.script_root.clone(),
+                local_source_path,
+                range
+            });
+    }
+
+    /// Returns locations of all references to a symbol with a given path in this content.
+    pub fn get_references<'a>(&'a self, sympath: &SymbolPath) -> impl Iterator<Item = &'a ReferenceLocation> {
+        self.references.get(sympath)
+            .into_iter()
+            .flat_map(|locs| locs.iter())
+    }
+
+    /// Returns whether there are any references to a symbol with a given path in this content.
+    pub fn contains_references(&self, sympath: &SymbolPath) -> bool {
+        self.references.get(sympath).map(|locs| !locs.is_empty()).unwrap_or(false)
+    }
+
+    /// Returns locations of all calls to a function with a given path made in this content.
+    pub fn get_calls<'a>(&'a self, callee_path: &SymbolPath) -> impl Iterator<Item = &'a CallLocation> {
+        self.calls.get(callee_path)
+            .into_iter()
+            .flat_map(|locs| locs.iter())
+    }
+
+    pub fn remove_references_for_source(&mut self, local_source_path: &Path) {
+        if let Some(sympaths) = self.source_path_assocs.remove(local_source_path) {
+            for sympath in sympaths {
+                if let Some(locs) = self.references.get_mut(&sympath) {
+                    locs.retain(|loc| loc.local_source_path.as_ref() != local_source_path);
+                    if locs.is_empty() {
+                        self.references.remove(&sympath);
+                    }
+                }
+
+                if let Some(locs) = self.calls.get_mut(&sympath) {
+                    locs.retain(|loc| loc.local_source_path.as_ref() != local_source_path);
+                    if locs.is_empty() {
+                        self.calls.remove(&sympath);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Moves all references from another index into this one.
+    /// Both indices are expected to have the same scripts root.
+    pub fn merge(&mut self, other: ReferenceIndex) {
+        for (local_source_path, sympaths) in other.source_path_assocs {
+            self.source_path_assocs.entry(local_source_path)
+                .or_default()
+                .extend(sympaths);
+        }
+
+        for (sympath, locs) in other.references {
+            self.references.entry(sympath)
+                .or_default()
+                .extend(locs);
+        }
+
+        for (sympath, locs) in other.calls {
+            self.calls.entry(sympath)
+                .or_default()
+                .extend(locs);
+        }
+    }
+}
@@ -0,0 +1,78 @@
+use std::collections::HashMap;
+use crate::symbol_analysis::symbols::*;
+use crate::symbol_analysis::symbol_path::{SymbolPath, SymbolPathBuf};
+use super::SymbolTable;
+
+
+/// Lookup structure for finding types that derive from a given type in a symbol table.
+/// This is the reverse of following `extends` clauses of classes and states.
+#[derive(Debug, Clone)]
+pub struct SymbolExtendsIndex {
+    /// Paths of classes keyed by the path of the class they extend
+    derived_classes: HashMap<SymbolPathBuf, Vec<SymbolPathBuf>>,
+    /// Paths of states keyed by the path of the class they belong to
+    class_states: HashMap<SymbolPathBuf, Vec<SymbolPathBuf>>,
+    /// Paths of states keyed by the name of the state they extend.
+    /// Which state that is exactly can only be known by looking at the hierarchy of the class that the state belongs to.
+    derived_states: HashMap<String, Vec<SymbolPathBuf>>
+}
+
+impl SymbolExtendsIndex {
+    pub fn new(symtab: &SymbolTable) -> Self {
+        let mut derived_classes: HashMap<SymbolPathBuf, Vec<SymbolPathBuf>> = HashMap::new();
+        let mut class_states: HashMap<SymbolPathBuf, Vec<SymbolPathBuf>> = HashMap::new();
+        let mut derived_states: HashMap<String, Vec<SymbolPathBuf>> = HashMap::new();
+
+        for (sympath, symvar) in symtab.iter() {
+            match symvar {
+                SymbolVariant::Class(class) => {
+                    if let Some(base_path) = &class.base_path {
+                        derived_classes.entry(base_path.to_owned().into())
+                            .or_default()
+                            .push(sympath.to_owned());
+                    }
+                },
+                SymbolVariant::State(state) => {
+                    class_states.entry(state.parent_class_path().to_owned())
+                        .or_default()
+                        .push(sympath.to_owned());
+
+                    if let Some(base_state_name) = &state.base_state_name {
+                        derived_states.entry(base_state_name.to_owned())
+                            .or_default()
+                            .push(sympath.to_owned());
+                    }
+                },
+                _ => {}
+            }
+        }
+
+        Self {
+            derived_classes,
+            class_states,
+            derived_states
+        }
+    }
+
+    /// Returns paths of classes that directly extend a class with given path
+    pub fn get_derived_classes<'a>(&'a self, base_class_path: &SymbolPath) -> impl Iterator<Item = &'a SymbolPath> {
+        self.derived_classes.get(base_class_path)
+            .into_iter()
+            .flat_map(|paths| paths.iter().map(|p| p.as_sympath()))
+    }
+
+    /// Returns paths of states that belong to a class with given path
+    pub fn get_class_states<'a>(&'a self, class_path: &SymbolPath) -> impl Iterator<Item = &'a SymbolPath> {
+        self.class_states.get(class_path)
+            .into_iter()
+            .flat_map(|paths| paths.iter().map(|p| p.as_sympath()))
+    }
+
+    /// Returns paths of states that extend a state with given name.
+    /// The caller needs to check whether that base state is the one they're interested in.
+    pub fn get_derived_state_candidates<'a>(&'a self, base_state_name: &str) -> impl Iterator<Item = &'a SymbolPath> {
+        self.derived_states.get(base_state_name)
+            .into_iter()
+            .flat_map(|paths| paths.iter().map(|p| p.as_sympath()))
+    }
+}
@@ -1,3 +1,4 @@
+use std::collections::HashSet;
 use witcherscript_project::SourceMask;
 use crate::symbol_analysis::symbol_path::{SymbolPath, SymbolPathBuf};
 use super::{ClassSymbol, PathOccupiedError, StateSymbol, Symbol, SymbolTable, SymbolVariant};
@@ -121,6 +122,43 @@ impl<'a> SymbolTableMarcher<'a> {
         StateHierarchy::new(self.clone(), state_path)
     }
 
+    /// Iterate over direct children of a symbol accross the marcher.
+    /// This way you can also get symbols that were injected into a type from a different content, e.g. with @addMethod.
+    /// Symbols that only redefine already existing ones (i.e. @replaceMethod and @wrapMethod) are skipped.
+    pub fn get_symbol_children(&self, path: &SymbolPath) -> impl Iterator<Item = &'a SymbolVariant> {
+        let mut visited = HashSet::new();
+        let mut children = Vec::new();
+        for i in self.start_idx..self.inner.len() {
+            let masked = &self.inner[i];
+            for ch in masked.symtab.get_symbol_children(path) {
+                let is_redefinition = ch.is_member_func_replacer() || ch.is_member_func_wrapper();
+                if !is_redefinition && mask_symbol(ch, &masked.accum_mask).is_some() && visited.insert(ch.path()) {
+                    children.push(ch);
+                }
+            }
+        }
+
+        children.into_iter()
+    }
+
+    /// Iterate over symbols that are at the very top of the symbol hierarchy accross the marcher, 
+    /// i.e. types, global functions, global variables and enum variants.
+    /// If a symbol with the same path exists in many tables, only the first found one is returned.
+    pub fn get_root_symbols(&self) -> impl Iterator<Item = &'a SymbolVariant> {
+        let mut visited = HashSet::new();
+        let mut roots = Vec::new();
+        for i in self.start_idx..self.inner.len() {
+            let masked = self.inner[i].clone();
+            for (sympath, symvar) in masked.into_iter() {
+                if sympath.components().nth(1).is_none() && visited.insert(sympath) {
+                    roots.push(symvar);
+                }
+            }
+        }
+
+        roots.into_iter()
+    }
+
     /// Iterate over symbols with the same symbol path accross the marcher.
     /// Normally a path conflict (i.e. redefinition) in the dependency tree means an error.
     /// It is not the case with @wrapMethod/@replaceMethod symbols however.
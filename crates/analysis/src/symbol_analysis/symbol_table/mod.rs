@@ -1,6 +1,6 @@
 use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::{Path, PathBuf};
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 use thiserror::Error;
 use abs_path::AbsPath;
 use crate::symbol_analysis::symbols::*;
@@ -12,6 +12,12 @@ use iter::*;
 
 pub mod marcher;
 
+pub mod name_index;
+use name_index::SymbolNameIndex;
+
+pub mod extends_index;
+use extends_index::SymbolExtendsIndex;
+
 
 /// Contains information about all scanned symbols. Symbols are identified by their path.
 /// On a given unique path only one symbol can be present.
@@ -22,7 +28,11 @@ pub struct SymbolTable {
     /// SymbolPath roots of symbols associated with given local paths in a source tree
     source_path_assocs: HashMap<Arc<Path>, Vec<SymbolPathBuf>>,
     /// Keeps track of where array type symbols have been referenced
-    array_type_refs: HashMap<SymbolPathBuf, HashSet<PathBuf>>
+    array_type_refs: HashMap<SymbolPathBuf, HashSet<PathBuf>>,
+    /// Built on demand and discarded whenever the table changes
+    name_index: OnceLock<SymbolNameIndex>,
+    /// Built on demand and discarded whenever the table changes
+    extends_index: OnceLock<SymbolExtendsIndex>
 }
 
 #[derive(Debug, Clone, Error)]
@@ -50,7 +60,9 @@ impl SymbolTable {
             script_root: scripts_root,
             symbols: BTreeMap::new(),
             source_path_assocs: HashMap::new(),
-            array_type_refs: HashMap::new()
+            array_type_refs: HashMap::new(),
+            name_index: OnceLock::new(),
+            extends_index: OnceLock::new()
         }
     }
 
@@ -65,11 +77,15 @@ impl SymbolTable {
 
     pub(crate) fn insert_symbol<S>(&mut self, sym: S)
     where S: Symbol + Into<SymbolVariant> {
+        self.name_index.take();
+        self.extends_index.take();
         self.symbols.insert(sym.path().to_owned(), sym.into());
     }
 
     pub(crate) fn insert_primary_symbol<S>(&mut self, sym: S)
     where S: PrimarySymbol + LocatableSymbol + Into<SymbolVariant> {
+        self.name_index.take();
+        self.extends_index.take();
         self.source_path_assocs.entry(sym.location().local_source_path.clone())
             .or_default()
             .push(sym.path().to_owned());
@@ -119,6 +135,9 @@ impl SymbolTable {
     }
 
     pub fn remove_symbols_for_source(&mut self, local_source_path: &Path) {
+        self.name_index.take();
+        self.extends_index.take();
+
         let for_removal: Vec<_> = 
             self.get_symbols_for_source(local_source_path)
             .map(|sym| sym.path().to_owned())
@@ -158,6 +177,19 @@ impl SymbolTable {
     }
 
 
+    /// Returns the index of names of primary symbols in this table.
+    /// The index gets built on the first call after the table has been modified.
+    pub fn name_index(&self) -> &SymbolNameIndex {
+        self.name_index.get_or_init(|| SymbolNameIndex::new(self))
+    }
+
+    /// Returns the index of types deriving from other types in this table.
+    /// The index gets built on the first call after the table has been modified.
+    pub fn extends_index(&self) -> &SymbolExtendsIndex {
+        self.extends_index.get_or_init(|| SymbolExtendsIndex::new(self))
+    }
+
+
     /// Iterate over direct children of a symbol in a symbol hierarchy.
     /// Symbols are returned ordered by their symbol path.
     #[inline]
@@ -200,6 +232,9 @@ impl SymbolTable {
             return errors;
         }
 
+        self.name_index.take();
+        self.extends_index.take();
+
         let mut sympath_to_skip = SymbolPathBuf::empty();
         for (incoming_sympath, incoming_variant) in other.symbols {
             // if some symbol is a duplicate we can skip its children
@@ -0,0 +1,165 @@
+use crate::symbol_analysis::symbols::*;
+use crate::symbol_analysis::symbol_path::SymbolPathBuf;
+use super::SymbolTable;
+
+
+/// Lookup structure for searching primary symbols of a symbol table by their names.
+/// Includes classes, states, structs, enums, global functions and member functions.
+#[derive(Debug, Clone)]
+pub struct SymbolNameIndex {
+    entries: Vec<SymbolNameIndexEntry>
+}
+
+#[derive(Debug, Clone)]
+pub struct SymbolNameIndexEntry {
+    pub name: String,
+    pub path: SymbolPathBuf,
+    pub typ: SymbolType,
+    /// Name of the type that the symbol belongs to, if the symbol is a member
+    pub container_name: Option<String>,
+    lowercase_name: String
+}
+
+impl SymbolNameIndex {
+    pub fn new(symtab: &SymbolTable) -> Self {
+        let entries = symtab.iter()
+            .filter_map(|(sympath, symvar)| {
+                let (name, container_name) = match symvar {
+                    SymbolVariant::Class(_) |
+                    SymbolVariant::Struct(_) |
+                    SymbolVariant::Enum(_) |
+                    SymbolVariant::GlobalFunc(_) => (symvar.name(), None),
+                    SymbolVariant::State(s) => (s.state_name(), Some(s.parent_class_name().to_string())),
+                    SymbolVariant::MemberFunc(_) => (symvar.name(), sympath.parent().and_then(|p| p.components().next_back()).map(|c| c.name.to_string())),
+                    _ => return None
+                };
+
+                let name = name.to_string();
+                Some(SymbolNameIndexEntry {
+                    lowercase_name: name.to_lowercase(),
+                    name,
+                    path: sympath.to_owned(),
+                    typ: symvar.typ(),
+                    container_name
+                })
+            })
+            .collect();
+
+        Self {
+            entries
+        }
+    }
+
+    /// Returns entries which names contain all characters of the query in the same order, ignoring case.
+    /// Each entry comes paired with a score. The higher the score, the better the name matches the query.
+    pub fn fuzzy_search<'a>(&'a self, query: &str) -> impl Iterator<Item = (i32, &'a SymbolNameIndexEntry)> + 'a {
+        let lowercase_query: Vec<char> = query.to_lowercase().chars().collect();
+
+        self.entries.iter()
+            .filter_map(move |entry| fuzzy_match_score(&lowercase_query, entry).map(|score| (score, entry)))
+    }
+}
+
+
+fn fuzzy_match_score(lowercase_query: &[char], entry: &SymbolNameIndexEntry) -> Option<i32> {
+    if lowercase_query.is_empty() {
+        return Some(0);
+    }
+    if lowercase_query.len() > entry.lowercase_name.len() {
+        return None;
+    }
+
+    let name: Vec<char> = entry.name.chars().collect();
+    let lowercase_name: Vec<char> = entry.lowercase_name.chars().collect();
+    if name.len() != lowercase_name.len() {
+        // some characters changed their length when lowercased, scores would be unreliable
+        return None;
+    }
+
+    let mut score = 0;
+    let mut query_idx = 0;
+    let mut prev_match_idx = None;
+    for (i, c) in lowercase_name.iter().enumerate() {
+        if query_idx == lowercase_query.len() {
+            break;
+        }
+
+        if *c == lowercase_query[query_idx] {
+            score += 1;
+
+            if i == 0 {
+                score += 10;
+            } else if prev_match_idx == Some(i - 1) {
+                score += 5;
+            } else if (name[i].is_uppercase() && !name[i - 1].is_uppercase()) || name[i - 1] == '_' {
+                // start of a word in camel case or snake case
+                score += 3;
+            }
+
+            prev_match_idx = Some(i);
+            query_idx += 1;
+        }
+    }
+
+    if query_idx < lowercase_query.len() {
+        return None;
+    }
+
+    if lowercase_query.len() == lowercase_name.len() {
+        score += 20;
+    }
+
+    // prefer shorter names if everything else is equal
+    score -= (lowercase_name.len() - lowercase_query.len()) as i32 / 4;
+
+    Some(score)
+}
+
+
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+
+    fn score(query: &str, name: &str) -> Option<i32> {
+        let lowercase_query: Vec<char> = query.to_lowercase().chars().collect();
+        let entry = SymbolNameIndexEntry {
+            name: name.to_string(),
+            path: SymbolPathBuf::new(name, SymbolCategory::Type),
+            typ: SymbolType::Class,
+            container_name: None,
+            lowercase_name: name.to_lowercase()
+        };
+
+        fuzzy_match_score(&lowercase_query, &entry)
+    }
+
+    #[test]
+    fn test_fuzzy_match() {
+        assert_eq!(score("", "CR4Player"), Some(0));
+        assert!(score("cr4player", "CR4Player").is_some());
+        assert!(score("CR4PLAYER", "CR4Player").is_some());
+        assert!(score("crply", "CR4Player").is_some());
+
+        assert!(score("yalp", "Player").is_none());
+        assert!(score("playerx", "Player").is_none());
+        assert!(score("playerr", "Player").is_none());
+    }
+
+    #[test]
+    fn test_fuzzy_match_ranking() {
+        // exact match
+        assert!(score("player", "Player") > score("player", "PlayerWitcher"));
+        // prefix
+        assert!(score("play", "PlayerCtl") > score("play", "GetPlayer"));
+        // consecutive characters
+        assert!(score("abc", "Abcxyz") > score("abc", "Axbycz"));
+        // starts of words in camel case and snake case
+        assert!(score("gp", "GetPlayer") > score("gp", "Gasp"));
+        assert!(score("ms", "my_state") > score("ms", "mass"));
+        // shorter names
+        assert!(score("foo", "FooBar") > score("foo", "FooBarBazQux"));
+    }
+}
@@ -8,7 +8,8 @@ pub struct ClassSymbol {
     path: BasicTypeSymbolPath,
     location: SymbolLocation,
     pub specifiers: SymbolSpecifiers<ClassSpecifier>,
-    pub base_path: Option<BasicTypeSymbolPath>
+    pub base_path: Option<BasicTypeSymbolPath>,
+    pub doc_comment: Option<String>
 }
 
 impl Symbol for ClassSymbol {
@@ -37,7 +38,8 @@ impl ClassSymbol {
             path,
             location,
             specifiers: SymbolSpecifiers::new(),
-            base_path: None
+            base_path: None,
+            doc_comment: None
         }
     }
 
@@ -54,6 +56,7 @@ pub struct AutobindSymbol {
     location: SymbolLocation,
     pub specifiers: SymbolSpecifiers<AutobindSpecifier>,
     pub type_path: TypeSymbolPath,
+    pub doc_comment: Option<String>
 }
 
 impl Symbol for AutobindSymbol {
@@ -78,7 +81,8 @@ impl AutobindSymbol {
             path,
             location,
             specifiers: SymbolSpecifiers::new(),
-            type_path: TypeSymbolPath::unknown()
+            type_path: TypeSymbolPath::unknown(),
+            doc_comment: None
         }
     }
 
@@ -5,7 +5,8 @@ use super::*;
 #[derive(Debug, Clone)]
 pub struct EnumSymbol {
     path: BasicTypeSymbolPath,
-    location: SymbolLocation
+    location: SymbolLocation,
+    pub doc_comment: Option<String>
 }
 
 impl Symbol for EnumSymbol {
@@ -32,7 +33,8 @@ impl EnumSymbol {
     pub fn new(path: BasicTypeSymbolPath, location: SymbolLocation) -> Self {
         Self {
             path,
-            location
+            location,
+            doc_comment: None
         }
     }
 }
@@ -47,7 +49,8 @@ pub struct EnumVariantSymbol {
     path: GlobalDataSymbolPath,
     location: SymbolLocation,
     pub parent_enum_path: BasicTypeSymbolPath,
-    pub value: i32
+    pub value: i32,
+    pub doc_comment: Option<String>
 }
 
 impl Symbol for EnumVariantSymbol {
@@ -76,7 +79,8 @@ impl EnumVariantSymbol {
             path,
             location,
             parent_enum_path: BasicTypeSymbolPath::unknown(),
-            value: 0
+            value: 0,
+            doc_comment: None
         }
     }
 }
\ No newline at end of file
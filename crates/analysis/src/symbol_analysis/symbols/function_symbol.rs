@@ -12,7 +12,8 @@ pub struct GlobalFunctionSymbol {
     location: SymbolLocation,
     pub specifiers: SymbolSpecifiers<GlobalFunctionSpecifier>,
     pub flavour: Option<GlobalFunctionFlavour>,
-    pub return_type_path: TypeSymbolPath
+    pub return_type_path: TypeSymbolPath,
+    pub doc_comment: Option<String>
 }
 
 impl Symbol for GlobalFunctionSymbol {
@@ -42,7 +43,8 @@ impl GlobalFunctionSymbol {
             location,
             specifiers: SymbolSpecifiers::new(),
             flavour: None,
-            return_type_path: TypeSymbolPath::unknown()
+            return_type_path: TypeSymbolPath::unknown(),
+            doc_comment: None
         }
     }
 
@@ -59,7 +61,8 @@ pub struct MemberFunctionSymbol {
     location: SymbolLocation,
     pub specifiers: SymbolSpecifiers<MemberFunctionSpecifier>,
     pub flavour: Option<MemberFunctionFlavour>,
-    pub return_type_path: TypeSymbolPath
+    pub return_type_path: TypeSymbolPath,
+    pub doc_comment: Option<String>
 }
 
 impl Symbol for MemberFunctionSymbol {
@@ -85,7 +88,8 @@ impl MemberFunctionSymbol {
             location,
             specifiers: SymbolSpecifiers::new(),
             flavour: None,
-            return_type_path: TypeSymbolPath::unknown()
+            return_type_path: TypeSymbolPath::unknown(),
+            doc_comment: None
         }
     }
 
@@ -99,7 +103,8 @@ impl MemberFunctionSymbol {
 #[derive(Debug, Clone)]
 pub struct EventSymbol {
     path: MemberCallableSymbolPath,
-    location: SymbolLocation
+    location: SymbolLocation,
+    pub doc_comment: Option<String>
 }
 
 impl Symbol for EventSymbol {
@@ -122,7 +127,8 @@ impl EventSymbol {
     pub fn new(path: MemberCallableSymbolPath, location: SymbolLocation) -> Self {
         Self {
             path,
-            location
+            location,
+            doc_comment: None
         }
     }
 }
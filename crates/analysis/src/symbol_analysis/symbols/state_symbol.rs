@@ -9,6 +9,7 @@ pub struct StateSymbol {
     location: SymbolLocation,
     pub specifiers: SymbolSpecifiers<StateSpecifier>,
     pub base_state_name: Option<String>,
+    pub doc_comment: Option<String>
 }
 
 impl Symbol for StateSymbol {
@@ -41,7 +42,8 @@ impl StateSymbol {
             path,
             location,
             specifiers: SymbolSpecifiers::new(),
-            base_state_name: None
+            base_state_name: None,
+            doc_comment: None
         }
     }
 
@@ -7,7 +7,8 @@ use super::*;
 pub struct StructSymbol {
     path: BasicTypeSymbolPath,
     location: SymbolLocation,
-    pub specifiers: SymbolSpecifiers<StructSpecifier>
+    pub specifiers: SymbolSpecifiers<StructSpecifier>,
+    pub doc_comment: Option<String>
 }
 
 impl Symbol for StructSymbol {
@@ -35,7 +36,8 @@ impl StructSymbol {
         Self {
             path,
             location,
-            specifiers: SymbolSpecifiers::new()
+            specifiers: SymbolSpecifiers::new(),
+            doc_comment: None
         }
     }
 }
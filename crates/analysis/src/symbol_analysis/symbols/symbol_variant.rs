@@ -219,6 +219,29 @@ impl SymbolVariant {
             Self::WrappedMethod(_) => None,
         }
     }
+
+    /// Returns the text of comments written directly above the declaration of the symbol
+    pub fn doc_comment(&self) -> Option<&str> {
+        match self {
+            Self::Class(s) => s.doc_comment.as_deref(),
+            Self::State(s) => s.doc_comment.as_deref(),
+            Self::Struct(s) => s.doc_comment.as_deref(),
+            Self::Enum(s) => s.doc_comment.as_deref(),
+            Self::GlobalFunc(s) => s.doc_comment.as_deref(),
+            Self::MemberFunc(s) => s.doc_comment.as_deref(),
+            Self::Event(s) => s.doc_comment.as_deref(),
+            Self::EnumVariant(s) => s.doc_comment.as_deref(),
+            Self::MemberVar(s) => s.doc_comment.as_deref(),
+            Self::Autobind(s) => s.doc_comment.as_deref(),
+
+            Self::MemberFuncInjector(s) => s.doc_comment.as_deref(),
+            Self::MemberFuncReplacer(s) => s.doc_comment.as_deref(),
+            Self::GlobalFuncReplacer(s) => s.doc_comment.as_deref(),
+            Self::MemberFuncWrapper(s) => s.doc_comment.as_deref(),
+            Self::MemberVarInjector(s) => s.doc_comment.as_deref(),
+            _ => None
+        }
+    }
 }
 
 
@@ -9,7 +9,8 @@ pub struct MemberVarSymbol {
     location: SymbolLocation,
     pub specifiers: SymbolSpecifiers<MemberVarSpecifier>,
     pub type_path: TypeSymbolPath,
-    pub ordinal: usize // used in the context of struct constructors
+    pub ordinal: usize, // used in the context of struct constructors
+    pub doc_comment: Option<String>
 }
 
 impl Symbol for MemberVarSymbol {
@@ -35,7 +36,8 @@ impl MemberVarSymbol {
             location,
             specifiers: SymbolSpecifiers::new(),
             type_path: TypeSymbolPath::unknown(),
-            ordinal: 0
+            ordinal: 0,
+            doc_comment: None
         }
     }
 
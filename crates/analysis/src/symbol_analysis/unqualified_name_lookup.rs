@@ -1,4 +1,4 @@
-use std::{cell::RefCell, collections::HashMap, hash::Hash, rc::Rc};
+use std::{cell::RefCell, collections::{HashMap, HashSet}, hash::Hash, rc::Rc};
 use witcherscript::{ast::*, attribs::*, script_document::ScriptDocument};
 use crate::utils::SymbolPathBuilderPayload;
 use super::symbol_path::{SymbolPath, SymbolPathBuf};
@@ -132,6 +132,16 @@ impl UnqualifiedNameLookup {
         None
     }
 
+    /// Iterate over symbol paths of all names accessible in the current context.
+    /// Names overshadowed by the same name in a deeper scope are skipped.
+    pub fn iter(&self) -> impl Iterator<Item = &SymbolPath> {
+        let mut visited = HashSet::new();
+        self.stack.iter().rev()
+            .flat_map(|level| level.iter())
+            .filter(move |(k, _)| visited.insert(AsBorrowedKey::borrow(*k)))
+            .map(|(_, path)| path.as_sympath())
+    }
+
     /// Inserts a symbol name mapping into the stack.
     fn insert(&mut self, path: SymbolPathBuf) {
         let comp = path.components().last().unwrap();
@@ -0,0 +1,95 @@
+use witcherscript::attribs::FunctionParameterSpecifier;
+use crate::symbol_analysis::symbol_path::SymbolPath;
+use crate::symbol_analysis::symbol_table::marcher::SymbolTableMarcher;
+use crate::symbol_analysis::symbols::*;
+
+
+/// Symbol that a function call refers to together with what it can take as arguments
+pub struct Callee<'a> {
+    pub symvar: &'a SymbolVariant,
+    /// Ordered by their ordinal
+    pub params: Vec<CalleeParam<'a>>
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum CalleeParam<'a> {
+    Func(&'a FunctionParameterSymbol),
+    ArrayFunc(&'a ArrayTypeFunctionParameterSymbol),
+    /// Struct constructors take values for fields in the order they were declared in
+    StructField(&'a MemberVarSymbol)
+}
+
+impl<'a> CalleeParam<'a> {
+    pub fn name(&self) -> &'a str {
+        match self {
+            CalleeParam::Func(s) => s.name(),
+            CalleeParam::ArrayFunc(s) => s.name(),
+            CalleeParam::StructField(s) => s.name()
+        }
+    }
+
+    pub fn type_path(&self) -> &'a TypeSymbolPath {
+        match self {
+            CalleeParam::Func(s) => &s.type_path,
+            CalleeParam::ArrayFunc(s) => &s.type_path,
+            CalleeParam::StructField(s) => &s.type_path
+        }
+    }
+
+    pub fn is_optional(&self) -> bool {
+        match self {
+            CalleeParam::Func(s) => s.specifiers.contains(FunctionParameterSpecifier::Optional),
+            CalleeParam::ArrayFunc(_) => false,
+            // any field can be skipped
+            CalleeParam::StructField(_) => true
+        }
+    }
+
+    pub fn is_out(&self) -> bool {
+        match self {
+            CalleeParam::Func(s) => s.specifiers.contains(FunctionParameterSpecifier::Out),
+            _ => false
+        }
+    }
+
+    fn ordinal(&self) -> usize {
+        match self {
+            CalleeParam::Func(s) => s.ordinal,
+            CalleeParam::ArrayFunc(s) => s.ordinal,
+            CalleeParam::StructField(s) => s.ordinal
+        }
+    }
+}
+
+/// Finds the symbol that a call expression with the given callee path refers to and its parameters.
+/// Returns None if the callee doesn't exist.
+pub fn resolve_callee<'a>(callee_path: &SymbolPath, symtab_marcher: &SymbolTableMarcher<'a>) -> Option<Callee<'a>> {
+    let (mut symtab, mut symvar) = symtab_marcher.get_symbol_with_table(callee_path)?;
+    // wrappedMethod shares its parameters with the wrapper
+    if let Some(wrapped_method) = symvar.try_as_wrapped_method_ref() {
+        (symtab, symvar) = symtab_marcher.get_symbol_with_table(wrapped_method.wrapped_path())?;
+    }
+
+    let mut params: Vec<_> = if let SymbolVariant::Constructor(s) = symvar {
+        let (struct_symtab, _) = symtab_marcher.get_symbol_with_table(&s.parent_type_path)?;
+        struct_symtab.get_symbol_children(&s.parent_type_path)
+            .filter_map(|ch| ch.try_as_member_var_ref())
+            .map(CalleeParam::StructField)
+            .collect()
+    } else {
+        symtab.get_symbol_children(symvar.path())
+            .filter_map(|ch| match ch {
+                SymbolVariant::FuncParam(s) => Some(CalleeParam::Func(s)),
+                SymbolVariant::ArrayFuncParam(s) => Some(CalleeParam::ArrayFunc(s)),
+                _ => None
+            })
+            .collect()
+    };
+
+    params.sort_by_key(|p| p.ordinal());
+
+    Some(Callee {
+        symvar,
+        params
+    })
+}
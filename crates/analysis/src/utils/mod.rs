@@ -1,2 +1,5 @@
 mod visitors;
 pub use visitors::*;
+
+mod callee;
+pub use callee::{Callee, CalleeParam, resolve_callee};
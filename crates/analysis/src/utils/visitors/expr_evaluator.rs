@@ -88,62 +88,71 @@ impl<'a> ExpressionEvaluator<'a> {
         self.type_stack.pop()
     }
 
+    #[inline]
     fn produce_type(&self, path: &SymbolPath) -> SymbolPathBuf {
-        if let Some(symvar) = self.symtab_marcher.get_symbol(path) {
-            match symvar {
-                SymbolVariant::Class(s) => s.path().to_owned(),
-                SymbolVariant::State(s) => s.path().to_owned(),
-                SymbolVariant::Struct(s) => s.path().to_owned(),
-                SymbolVariant::Enum(s) => s.path().to_owned(),
-                SymbolVariant::Array(s) => s.path().to_owned(),
-                SymbolVariant::ArrayFunc(s) => s.return_type_path.clone().into(),
-                SymbolVariant::ArrayFuncParam(s) => s.type_path.clone().into(),
-                SymbolVariant::GlobalFunc(s) => s.return_type_path.clone().into(),
-                SymbolVariant::MemberFunc(s) => s.return_type_path.clone().into(),
-                SymbolVariant::Event(_) => BasicTypeSymbolPath::new("void").into(), // I guess??
-                SymbolVariant::Constructor(s) => s.parent_type_path.clone().into(),
-                SymbolVariant::Primitive(s) => s.path().to_owned(),
-                SymbolVariant::EnumVariant(s) => s.parent_enum_path.clone().into(),
-                SymbolVariant::FuncParam(s) => s.type_path.clone().into(),
-                SymbolVariant::GlobalVar(s) => s.type_path().to_owned().into(),
-                SymbolVariant::MemberVar(s) => s.type_path.clone().into(),
-                SymbolVariant::Autobind(s) => s.type_path.clone().into(),
-                SymbolVariant::LocalVar(s) => s.type_path.clone().into(),
-                SymbolVariant::ThisVar(s) => s.type_path().to_owned(),
-                SymbolVariant::SuperVar(s) => s.type_path().to_owned(),
-                SymbolVariant::StateSuperVar(s) => {
-                    if s.base_state_name().is_some() {
-                        let state_path = path.root().unwrap_or_default();
-                        self.symtab_marcher
-                            .state_hierarchy(state_path)
-                            .skip(1).next()
-                            .map(|sym| sym.path().to_owned())
-                            .unwrap_or(SymbolPathBuf::unknown(SymbolCategory::Type))
-                    } else {
-                        BasicTypeSymbolPath::new(StateSymbol::DEFAULT_STATE_BASE_NAME).into()
-                    }
-                },
-                SymbolVariant::ParentVar(s) => s.type_path().to_owned(),
-                SymbolVariant::VirtualParentVar(s) => s.type_path().to_owned(),
-                SymbolVariant::MemberFuncInjector(s) => s.return_type_path.clone().into(),
-                SymbolVariant::MemberFuncReplacer(s) => s.return_type_path.clone().into(),
-                SymbolVariant::GlobalFuncReplacer(s) => s.return_type_path.clone().into(),
-                SymbolVariant::MemberFuncWrapper(s) => s.return_type_path.clone().into(),
-                SymbolVariant::MemberVarInjector(s) => s.type_path.clone().into(),
-                SymbolVariant::WrappedMethod(s) => {
-                    self.symtab_marcher
-                        .get_symbol(s.wrapped_path())
-                        .and_then(|v| v.try_as_member_func_wrapper_ref())
-                        .map(|wrapper| wrapper.return_type_path.clone().into())
+        produce_type(path, &self.symtab_marcher)
+    }
+}
+
+
+/// Compute the type of a symbol at the given path, e.g. for a variable it is the path to its type
+/// and for a function it is the path to its return type. Type symbols evaluate to themselves.
+pub fn produce_type(path: &SymbolPath, symtab_marcher: &SymbolTableMarcher) -> SymbolPathBuf {
+    if let Some(symvar) = symtab_marcher.get_symbol(path) {
+        match symvar {
+            SymbolVariant::Class(s) => s.path().to_owned(),
+            SymbolVariant::State(s) => s.path().to_owned(),
+            SymbolVariant::Struct(s) => s.path().to_owned(),
+            SymbolVariant::Enum(s) => s.path().to_owned(),
+            SymbolVariant::Array(s) => s.path().to_owned(),
+            SymbolVariant::ArrayFunc(s) => s.return_type_path.clone().into(),
+            SymbolVariant::ArrayFuncParam(s) => s.type_path.clone().into(),
+            SymbolVariant::GlobalFunc(s) => s.return_type_path.clone().into(),
+            SymbolVariant::MemberFunc(s) => s.return_type_path.clone().into(),
+            SymbolVariant::Event(_) => BasicTypeSymbolPath::new("void").into(), // I guess??
+            SymbolVariant::Constructor(s) => s.parent_type_path.clone().into(),
+            SymbolVariant::Primitive(s) => s.path().to_owned(),
+            SymbolVariant::EnumVariant(s) => s.parent_enum_path.clone().into(),
+            SymbolVariant::FuncParam(s) => s.type_path.clone().into(),
+            SymbolVariant::GlobalVar(s) => s.type_path().to_owned().into(),
+            SymbolVariant::MemberVar(s) => s.type_path.clone().into(),
+            SymbolVariant::Autobind(s) => s.type_path.clone().into(),
+            SymbolVariant::LocalVar(s) => s.type_path.clone().into(),
+            SymbolVariant::ThisVar(s) => s.type_path().to_owned(),
+            SymbolVariant::SuperVar(s) => s.type_path().to_owned(),
+            SymbolVariant::StateSuperVar(s) => {
+                if s.base_state_name().is_some() {
+                    let state_path = path.root().unwrap_or_default();
+                    symtab_marcher
+                        .state_hierarchy(state_path)
+                        .skip(1).next()
+                        .map(|sym| sym.path().to_owned())
                         .unwrap_or(SymbolPathBuf::unknown(SymbolCategory::Type))
+                } else {
+                    BasicTypeSymbolPath::new(StateSymbol::DEFAULT_STATE_BASE_NAME).into()
                 }
+            },
+            SymbolVariant::ParentVar(s) => s.type_path().to_owned(),
+            SymbolVariant::VirtualParentVar(s) => s.type_path().to_owned(),
+            SymbolVariant::MemberFuncInjector(s) => s.return_type_path.clone().into(),
+            SymbolVariant::MemberFuncReplacer(s) => s.return_type_path.clone().into(),
+            SymbolVariant::GlobalFuncReplacer(s) => s.return_type_path.clone().into(),
+            SymbolVariant::MemberFuncWrapper(s) => s.return_type_path.clone().into(),
+            SymbolVariant::MemberVarInjector(s) => s.type_path.clone().into(),
+            SymbolVariant::WrappedMethod(s) => {
+                symtab_marcher
+                    .get_symbol(s.wrapped_path())
+                    .and_then(|v| v.try_as_member_func_wrapper_ref())
+                    .map(|wrapper| wrapper.return_type_path.clone().into())
+                    .unwrap_or(SymbolPathBuf::unknown(SymbolCategory::Type))
             }
-        } else {
-            SymbolPathBuf::unknown(SymbolCategory::Type)
         }
+    } else {
+        SymbolPathBuf::unknown(SymbolCategory::Type)
     }
 }
 
+
 impl SyntaxNodeVisitor for ExpressionEvaluator<'_> {
     fn exit_nested_expr(&mut self, _: &NestedExpressionNode, ctx: &TraversalContextStack) {
         self.top_mut().map(|e| e.ctx = ctx.top() );
@@ -5,4 +5,4 @@ mod sympath_builder;
 pub use sympath_builder::{SymbolPathBuilder, SymbolPathBuilderPayload};
 
 mod expr_evaluator;
-pub use expr_evaluator::{ExpressionEvaluator, evaluate_expression};
\ No newline at end of file
+pub use expr_evaluator::{ExpressionEvaluator, evaluate_expression, produce_type};
\ No newline at end of file
@@ -0,0 +1,693 @@
+use std::str::FromStr;
+use lsp_types as lsp;
+use tree_sitter as ts;
+use crate::{script_document::{RopeUtils, ScriptDocument}, tokens::Keyword, Script};
+
+
+/// Placement of the opening brace of a block
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BraceStyle {
+    /// Brace is put at the end of the line that precedes the block, e.g. `if (cond) {`
+    SameLine,
+    /// Brace is put in its own line below the line that precedes the block
+    NextLine
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormatterOptions {
+    /// Number of spaces that make up one indentation level. Ignored if `use_tabs` is set.
+    pub tab_size: u32,
+    pub use_tabs: bool,
+    pub brace_style: BraceStyle,
+    /// Put spaces around binary, assignment and ternary operators, e.g. `a + b` instead of `a+b`
+    pub space_around_operators: bool,
+    /// Put a space before the colon of a type annotation, e.g. `var x : int` instead of `var x: int`
+    pub space_before_type_colon: bool,
+    /// Put a space between `if`, `for`, `while` or `switch` and the opening parenthesis
+    pub space_after_control_keywords: bool
+}
+
+impl Default for FormatterOptions {
+    fn default() -> Self {
+        Self {
+            tab_size: 4,
+            use_tabs: false,
+            brace_style: BraceStyle::NextLine,
+            space_around_operators: true,
+            space_before_type_colon: true,
+            space_after_control_keywords: true
+        }
+    }
+}
+
+
+/// Computes edits that format the entire script.
+///
+/// The formatter only ever changes whitespace between tokens, so comments, blank lines
+/// and line breaks inside of expressions are preserved.
+/// Returns `None` if the script contains syntax errors.
+pub fn format_document(script: &Script, doc: &ScriptDocument, options: &FormatterOptions) -> Option<Vec<lsp::TextEdit>> {
+    let root = script.root_node().tree_node;
+    if root.has_error() {
+        return None;
+    }
+
+    let formatter = Formatter::new(root, doc, options);
+    Some(formatter.edits(0..doc.rope.len_bytes()))
+}
+
+/// Computes edits that format only the part of the script inside of a given range.
+/// Returns `None` if there are syntax errors in that range.
+pub fn format_range(script: &Script, doc: &ScriptDocument, range: lsp::Range, options: &FormatterOptions) -> Option<Vec<lsp::TextEdit>> {
+    let formatter = Formatter::new(script.root_node().tree_node, doc, options);
+    let byte_range = doc.rope.position_to_byte(&range.start)..doc.rope.position_to_byte(&range.end);
+    formatter.checked_edits(byte_range)
+}
+
+/// Computes edits that should be made after the user typed `ch` character before a given position.
+/// Typing `}` formats the block it closes and `;` formats the statement or declaration it ends.
+/// Returns `None` if there is nothing to format or there are syntax errors in the formatted code.
+pub fn format_on_type(script: &Script, doc: &ScriptDocument, position: lsp::Position, ch: &str, options: &FormatterOptions) -> Option<Vec<lsp::TextEdit>> {
+    let formatter = Formatter::new(script.root_node().tree_node, doc, options);
+    let position_byte = doc.rope.position_to_byte(&position);
+
+    let token = formatter.tokens.iter()
+        .rev()
+        .find(|t| t.node.kind() == ch && t.node.end_byte() <= position_byte)
+        .filter(|t| t.node.end_position().row == position.line as usize)?;
+
+    let scope = match ch {
+        "}" => token.parent?,
+        ";" => token.item,
+        _ => return None
+    };
+
+    formatter.checked_edits(scope.start_byte()..scope.end_byte())
+}
+
+
+/// Node kinds that are treated as single tokens even if they have children
+const ATOMIC_NODE_KINDS: [&str; 9] = [
+    "ident",
+    "annotation_ident",
+    "literal_int",
+    "literal_hex",
+    "literal_float",
+    "literal_bool",
+    "literal_string",
+    "literal_name",
+    "literal_null"
+];
+
+#[derive(Debug, Clone, Copy)]
+struct Token<'script> {
+    node: ts::Node<'script>,
+    parent: Option<ts::Node<'script>>,
+    /// Top-most node containing the token that still belongs to the nearest block or the script root,
+    /// e.g. a statement or a member declaration
+    item: ts::Node<'script>,
+    in_error: bool,
+    in_annotation: bool
+}
+
+impl Token<'_> {
+    #[inline]
+    fn kind(&self) -> &'static str {
+        self.node.kind()
+    }
+
+    #[inline]
+    fn parent_kind(&self) -> &'static str {
+        self.parent.map(|p| p.kind()).unwrap_or_default()
+    }
+
+    #[inline]
+    fn is_comment(&self) -> bool {
+        self.node.is_extra()
+    }
+
+    fn is_unary_operator(&self) -> bool {
+        is_operator_kind(self.kind(), "unary_op_") || is_operator_kind(self.parent_kind(), "unary_op_")
+    }
+
+    fn is_binary_operator(&self) -> bool {
+        self.kind() == "="
+        || (self.parent_kind() == "ternary_cond_expr" && matches!(self.kind(), "?" | ":"))
+        || is_operator_kind(self.kind(), "binary_op_") || is_operator_kind(self.parent_kind(), "binary_op_")
+        || is_operator_kind(self.kind(), "assign_op_") || is_operator_kind(self.parent_kind(), "assign_op_")
+    }
+
+    fn is_label_colon(&self) -> bool {
+        self.kind() == ":" && matches!(self.parent_kind(), "switch_case_label" | "switch_default_label")
+    }
+
+    fn is_type_colon(&self) -> bool {
+        self.kind() == ":" && !self.is_label_colon() && self.parent_kind() != "ternary_cond_expr"
+    }
+
+    fn is_generic_bracket(&self) -> bool {
+        matches!(self.kind(), "<" | ">") && self.parent_kind() == "type_annot"
+    }
+
+    fn is_label_start(&self) -> bool {
+        matches!(self.kind(), "case" | "default") && matches!(self.parent_kind(), "switch_case_label" | "switch_default_label")
+    }
+
+    fn is_do_while_keyword(&self) -> bool {
+        self.kind() == "while" && self.parent_kind() == "do_while_stmt"
+    }
+}
+
+fn is_operator_kind(kind: &str, prefix: &str) -> bool {
+    kind.starts_with(prefix) && !kind.ends_with("_expr")
+}
+
+fn is_keyword(kind: &str) -> bool {
+    Keyword::from_str(kind).is_ok()
+}
+
+fn is_control_keyword(kind: &str) -> bool {
+    matches!(kind, "if" | "for" | "while" | "switch")
+}
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LineBreak {
+    /// Keep as many line breaks as there were originally, possibly none
+    Preserve,
+    /// There must be at least one line break
+    Required,
+    /// Tokens must stay in the same line
+    Forbidden
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct BlockFrame {
+    /// Whether the block is a switch block and there was a case label in it,
+    /// so the following statements need an additional indentation level
+    in_case: bool
+}
+
+struct Formatter<'a, 'script> {
+    doc: &'a ScriptDocument,
+    options: &'a FormatterOptions,
+    tokens: Vec<Token<'script>>,
+    /// Byte offsets of tokens that were expected by the parser, but are missing in the text
+    missing_offsets: Vec<usize>,
+    eol: &'static str
+}
+
+impl<'a, 'script> Formatter<'a, 'script> {
+    fn new(root: ts::Node<'script>, doc: &'a ScriptDocument, options: &'a FormatterOptions) -> Self {
+        let eol = if doc.rope.lines().next().map(|l| l.to_string().ends_with("\r\n")).unwrap_or(false) {
+            "\r\n"
+        } else {
+            "\n"
+        };
+
+        let mut formatter = Self {
+            doc,
+            options,
+            tokens: Vec::new(),
+            missing_offsets: Vec::new(),
+            eol
+        };
+
+        formatter.collect_tokens(root, None, None, false, false);
+        formatter
+    }
+
+    fn collect_tokens(&mut self, node: ts::Node<'script>, parent: Option<ts::Node<'script>>, item: Option<ts::Node<'script>>, in_error: bool, in_annotation: bool) {
+        if node.is_missing() {
+            self.missing_offsets.push(node.start_byte());
+            return;
+        }
+        if node.start_byte() == node.end_byte() {
+            return;
+        }
+
+        let in_error = in_error || node.is_error();
+        let in_annotation = in_annotation || node.kind() == "annotation";
+        let item = item.unwrap_or(node);
+
+        if node.child_count() == 0 || node.is_extra() || ATOMIC_NODE_KINDS.contains(&node.kind()) {
+            self.tokens.push(Token {
+                node,
+                parent,
+                item,
+                in_error,
+                in_annotation
+            });
+            return;
+        }
+
+        let mut cursor = node.walk();
+        let children: Vec<_> = node.children(&mut cursor).collect();
+
+        // children of the root and of nodes delimited by braces are items on their own
+        let contains_items = parent.is_none() || children.iter().any(|c| c.kind() == "{");
+        for child in children {
+            self.collect_tokens(child, Some(node), if contains_items { None } else { Some(item) }, in_error, in_annotation);
+        }
+    }
+
+
+    /// Like [`Self::edits`], but returns `None` if there are syntax errors in the range
+    fn checked_edits(&self, byte_range: std::ops::Range<usize>) -> Option<Vec<lsp::TextEdit>> {
+        let has_errors = self.tokens.iter().any(|t| t.in_error && t.node.end_byte() >= byte_range.start && t.node.start_byte() <= byte_range.end)
+            || self.missing_offsets.iter().any(|o| byte_range.contains(o));
+
+        if has_errors {
+            None
+        } else {
+            Some(self.edits(byte_range))
+        }
+    }
+
+    /// Computes edits for all gaps between tokens that intersect with the range
+    fn edits(&self, byte_range: std::ops::Range<usize>) -> Vec<lsp::TextEdit> {
+        let mut edits = Vec::new();
+        let mut push_edit = |gap: std::ops::Range<usize>, new_text: String| {
+            if gap.end < byte_range.start || gap.start > byte_range.end {
+                return;
+            }
+
+            let old_text = self.doc.rope.byte_slice(gap.clone());
+            if old_text.chars().any(|c| !c.is_whitespace()) || old_text == new_text.as_str() {
+                return;
+            }
+
+            edits.push(lsp::TextEdit::new(
+                lsp::Range::new(self.doc.rope.byte_to_position(gap.start), self.doc.rope.byte_to_position(gap.end)),
+                new_text
+            ));
+        };
+
+        let mut blocks: Vec<BlockFrame> = Vec::new();
+        let mut prev: Option<&Token> = None;
+        for cur in self.tokens.iter() {
+            if cur.kind() == "}" {
+                blocks.pop();
+            }
+            if cur.is_label_start() {
+                if let Some(block) = blocks.last_mut() {
+                    block.in_case = false;
+                }
+            }
+
+            let gap = prev.map(|p| p.node.end_byte()).unwrap_or(0)..cur.node.start_byte();
+            let is_gap_broken = cur.in_error
+                || prev.map(|p| p.in_error).unwrap_or(false)
+                || self.missing_offsets.iter().any(|o| gap.start <= *o && *o <= gap.end);
+
+            if !is_gap_broken {
+                let old_newlines = self.doc.rope.byte_slice(gap.clone()).chars().filter(|c| *c == '\n').count();
+                let newlines = match prev.map(|p| self.line_break(p, cur, old_newlines)).unwrap_or(LineBreak::Preserve) {
+                    LineBreak::Preserve => old_newlines,
+                    LineBreak::Required => old_newlines.max(1),
+                    LineBreak::Forbidden => 0
+                };
+
+                let new_text = if newlines > 0 {
+                    let level = blocks.len()
+                        + blocks.iter().filter(|b| b.in_case).count()
+                        + if self.is_continuation(prev, cur) { 1 } else { 0 };
+
+                    self.eol.repeat(newlines) + &self.indent(level)
+                } else if let Some(prev) = prev {
+                    self.space(prev, cur).to_string()
+                } else {
+                    String::new()
+                };
+
+                push_edit(gap, new_text);
+            }
+
+            if cur.kind() == "{" {
+                blocks.push(BlockFrame::default());
+            }
+            if cur.is_label_colon() {
+                if let Some(block) = blocks.last_mut() {
+                    block.in_case = true;
+                }
+            }
+
+            prev = Some(cur);
+        }
+
+        // trailing whitespace at the end of the file
+        if let Some(last) = prev {
+            let gap = last.node.end_byte()..self.doc.rope.len_bytes();
+            let has_newline = self.doc.rope.byte_slice(gap.clone()).chars().any(|c| c == '\n');
+            push_edit(gap, if has_newline { self.eol.to_string() } else { String::new() });
+        }
+
+        edits
+    }
+
+    fn line_break(&self, prev: &Token, cur: &Token, old_newlines: usize) -> LineBreak {
+        let brace_style_break = match self.options.brace_style {
+            BraceStyle::SameLine => LineBreak::Forbidden,
+            BraceStyle::NextLine => LineBreak::Required,
+        };
+
+        if prev.is_comment() {
+            let is_line_comment = self.doc.rope.byte_slice(prev.node.start_byte()..prev.node.end_byte()).to_string().starts_with("//");
+            return if is_line_comment { LineBreak::Required } else { LineBreak::Preserve };
+        }
+        if cur.is_comment() {
+            return LineBreak::Preserve;
+        }
+
+        match (prev.kind(), cur.kind()) {
+            // empty block written as `{}`
+            ("{", "}") if old_newlines == 0 => LineBreak::Forbidden,
+            (_, "{") => brace_style_break,
+            ("{", _) | (_, "}") => LineBreak::Required,
+            ("}", "else") => brace_style_break,
+            ("}", _) if cur.is_do_while_keyword() => brace_style_break,
+            ("}", ";") => LineBreak::Preserve,
+            ("}", _) => LineBreak::Required,
+            // semicolons inside of for loop's parentheses
+            (";", _) if prev.parent_kind() == "for_stmt" => LineBreak::Preserve,
+            (";", _) => LineBreak::Required,
+            _ if prev.is_label_colon() => LineBreak::Required,
+            _ => LineBreak::Preserve
+        }
+    }
+
+    /// Whether the token starts a line that continues an expression or a declaration from the previous line
+    fn is_continuation(&self, prev: Option<&Token>, cur: &Token) -> bool {
+        if cur.is_comment()
+        || matches!(cur.kind(), "{" | "}" | ")" | "]" | "else")
+        || cur.is_do_while_keyword() {
+            return false;
+        }
+
+        // annotations are written in their own line above the declaration
+        if prev.map(|p| p.in_annotation).unwrap_or(false) && !cur.in_annotation {
+            return false;
+        }
+
+        cur.item.start_position().row < cur.node.start_position().row
+    }
+
+    /// Whitespace between two tokens in the same line
+    fn space(&self, prev: &Token, cur: &Token) -> &'static str {
+        let operator_space = if self.options.space_around_operators { " " } else { "" };
+
+        if prev.is_comment() || cur.is_comment() {
+            return " ";
+        }
+        if matches!(cur.kind(), "," | ";" | ")" | "]" | ".") || matches!(prev.kind(), "(" | "[" | ".") {
+            return "";
+        }
+        if matches!(prev.kind(), "," | ";") {
+            return " ";
+        }
+        if cur.is_label_colon() || prev.is_unary_operator() {
+            return "";
+        }
+        if prev.is_binary_operator() || cur.is_binary_operator() {
+            return operator_space;
+        }
+        if cur.is_type_colon() {
+            return if self.options.space_before_type_colon { " " } else { "" };
+        }
+        if cur.is_generic_bracket() || (prev.kind() == "<" && prev.is_generic_bracket()) {
+            return "";
+        }
+
+        match cur.kind() {
+            "(" if is_control_keyword(prev.kind()) => if self.options.space_after_control_keywords { " " } else { "" },
+            "(" if is_keyword(prev.kind()) => " ",
+            "(" | "[" => "",
+            // type cast, e.g. `(CPlayer)actor`
+            _ if prev.kind() == ")" && prev.parent_kind() == "cast_expr" => "",
+            _ => " "
+        }
+    }
+
+    fn indent(&self, level: usize) -> String {
+        if self.options.use_tabs {
+            "\t".repeat(level)
+        } else {
+            " ".repeat(level * self.options.tab_size as usize)
+        }
+    }
+}
+
+
+
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+
+    fn apply_edits(doc: &ScriptDocument, mut edits: Vec<lsp::TextEdit>) -> String {
+        let mut doc = doc.clone();
+        // going in reverse so positions of the remaining edits stay valid
+        edits.sort_by_key(|e| e.range.start);
+        for edit in edits.into_iter().rev() {
+            doc.edit(&lsp::TextDocumentContentChangeEvent {
+                range: Some(edit.range),
+                range_length: None,
+                text: edit.new_text
+            });
+        }
+
+        doc.text()
+    }
+
+    fn format(text: &str, options: &FormatterOptions) -> String {
+        let doc = ScriptDocument::from_str(text);
+        let script = Script::new(&doc).unwrap();
+        let edits = format_document(&script, &doc, options).unwrap();
+        apply_edits(&doc, edits)
+    }
+
+
+    #[test]
+    fn test_format_document() {
+        let formatted = format(
+r#"function Foo(a:int):int{
+var b:int;
+b=a+1;
+return b;}
+"#, &FormatterOptions::default());
+
+        assert_eq!(formatted,
+r#"function Foo(a : int) : int
+{
+    var b : int;
+    b = a + 1;
+    return b;
+}
+"#);
+    }
+
+    #[test]
+    fn test_idempotency() {
+        let text =
+r#"class CFoo extends CBar{
+private var a:int;default a=1;
+function Bar(x : float,  y:array<int>) :bool{
+if(x>0){return true;}
+else {while(x<1.0){x+=0.5;}}
+return (CFoo)this;}
+}
+"#;
+
+        for options in [FormatterOptions::default(), FormatterOptions { brace_style: BraceStyle::SameLine, ..Default::default() }] {
+            let once = format(text, &options);
+            let twice = format(&once, &options);
+            assert_eq!(once, twice);
+        }
+    }
+
+    #[test]
+    fn test_comments_and_blank_lines() {
+        let formatted = format(
+r#"// header comment
+
+class CFoo
+{
+    /* block */   var a:int;
+
+
+    // doc
+    function Bar()
+    {
+        a=1;   // trailing
+    }
+}
+"#, &FormatterOptions::default());
+
+        assert_eq!(formatted,
+r#"// header comment
+
+class CFoo
+{
+    /* block */ var a : int;
+
+
+    // doc
+    function Bar()
+    {
+        a = 1; // trailing
+    }
+}
+"#);
+    }
+
+    #[test]
+    fn test_brace_style() {
+        let next_line =
+r#"function Foo()
+{
+    if (true)
+    {
+        Bar();
+    }
+    else
+    {
+        Baz();
+    }
+}
+"#;
+
+        let same_line =
+r#"function Foo() {
+    if (true) {
+        Bar();
+    } else {
+        Baz();
+    }
+}
+"#;
+
+        let next_line_options = FormatterOptions { brace_style: BraceStyle::NextLine, ..Default::default() };
+        let same_line_options = FormatterOptions { brace_style: BraceStyle::SameLine, ..Default::default() };
+
+        assert_eq!(format(next_line, &same_line_options), same_line);
+        assert_eq!(format(same_line, &next_line_options), next_line);
+    }
+
+    #[test]
+    fn test_switch_case_indentation() {
+        let formatted = format(
+r#"function Foo(a : int)
+{
+switch (a)
+{
+case 1:
+Bar();
+break;
+default:
+Baz();
+}
+}
+"#, &FormatterOptions::default());
+
+        assert_eq!(formatted,
+r#"function Foo(a : int)
+{
+    switch (a)
+    {
+        case 1:
+            Bar();
+            break;
+        default:
+            Baz();
+    }
+}
+"#);
+    }
+
+    #[test]
+    fn test_crlf() {
+        let formatted = format("function Foo()\r\n{\r\nBar();\r\n}\r\n", &FormatterOptions::default());
+        assert_eq!(formatted, "function Foo()\r\n{\r\n    Bar();\r\n}\r\n");
+    }
+
+    #[test]
+    fn test_syntax_errors() {
+        let doc = ScriptDocument::from_str("function Foo( {\n}\n");
+        let script = Script::new(&doc).unwrap();
+        assert!(format_document(&script, &doc, &FormatterOptions::default()).is_none());
+    }
+
+    #[test]
+    fn test_format_range() {
+        let doc = ScriptDocument::from_str(
+r#"function A(){
+Foo();
+}
+
+function B(){
+Foo();
+}
+"#);
+        let script = Script::new(&doc).unwrap();
+        let range = lsp::Range::new(lsp::Position::new(4, 0), lsp::Position::new(6, 1));
+        let edits = format_range(&script, &doc, range, &FormatterOptions::default()).unwrap();
+
+        assert_eq!(apply_edits(&doc, edits),
+r#"function A(){
+Foo();
+}
+
+function B()
+{
+    Foo();
+}
+"#);
+    }
+
+    #[test]
+    fn test_format_on_type() {
+        let doc = ScriptDocument::from_str(
+r#"function A()
+{
+    Foo(  1 );
+    Bar(  2 );
+}
+"#);
+        let script = Script::new(&doc).unwrap();
+        let edits = format_on_type(&script, &doc, lsp::Position::new(2, 14), ";", &FormatterOptions::default()).unwrap();
+
+        assert_eq!(apply_edits(&doc, edits),
+r#"function A()
+{
+    Foo(1);
+    Bar(  2 );
+}
+"#);
+
+
+        let doc = ScriptDocument::from_str(
+r#"function A()
+{
+    if (true) {
+Foo();
+}
+}
+"#);
+        let script = Script::new(&doc).unwrap();
+        let edits = format_on_type(&script, &doc, lsp::Position::new(4, 1), "}", &FormatterOptions::default()).unwrap();
+
+        assert_eq!(apply_edits(&doc, edits),
+r#"function A()
+{
+    if (true)
+    {
+        Foo();
+    }
+}
+"#);
+
+        assert!(format_on_type(&script, &doc, lsp::Position::new(4, 1), "x", &FormatterOptions::default()).is_none());
+    }
+}
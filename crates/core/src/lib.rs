@@ -13,4 +13,5 @@ use debug::*;
 pub mod tokens;
 pub mod attribs;
 pub mod ast;
-pub mod script_document;
\ No newline at end of file
+pub mod script_document;
+pub mod formatting;
\ No newline at end of file
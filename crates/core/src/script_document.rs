@@ -47,6 +47,11 @@ impl ScriptDocument {
         })
     }
 
+    /// Returns the entire text of the document
+    pub fn text(&self) -> String {
+        self.rope.to_string()
+    }
+
     pub fn text_at(&self, range: lsp::Range) -> Cow<'_, str> {
         let start_char = self.rope.position_to_char(&range.start);
         let end_char = self.rope.position_to_char(&range.end);
@@ -108,9 +113,10 @@ fn position_to_point(position: lsp::Position) -> ts::Point {
     }
 }
 
-trait RopeUtils {
+pub(crate) trait RopeUtils {
     fn position_to_char(&self, position: &lsp::Position) -> usize;
     fn position_to_byte(&self, position: &lsp::Position) -> usize;
+    fn byte_to_position(&self, byte: usize) -> lsp::Position;
 }
 
 impl RopeUtils for Rope {
@@ -121,6 +127,12 @@ impl RopeUtils for Rope {
     fn position_to_byte(&self, position: &lsp::Position) -> usize {
         self.char_to_byte(self.position_to_char(position))
     }
+
+    fn byte_to_position(&self, byte: usize) -> lsp::Position {
+        let char_idx = self.byte_to_char(byte);
+        let line = self.char_to_line(char_idx);
+        lsp::Position::new(line as u32, (char_idx - self.line_to_char(line)) as u32)
+    }
 }
 
 // returns a relative position of the end of the given string
@@ -1,7 +1,7 @@
 use std::{borrow::Cow, cell::Cell, fmt::Debug, marker::PhantomData};
 use lsp_types as lsp;
 use tree_sitter as ts;
-use crate::{SyntaxError, script_document::ScriptDocument};
+use crate::{SyntaxError, script_document::ScriptDocument, tokens::CommentNode};
 
 
 /// Represents a WitcherScript syntax tree node
@@ -91,6 +91,58 @@ impl<'script, T> SyntaxNode<'script, T> {
     }
 
 
+    /// Returns all comments inside of this node in the order they appear in the text.
+    /// Comments are not part of the syntax tree proper, so they're not returned by [`Self::children`].
+    pub fn comments(&self) -> Vec<CommentNode<'script>> {
+        self.use_cursor(|mut cursor| {
+            let mut comments = Vec::new();
+            'traversal: loop {
+                let node = cursor.node();
+                if node.is_extra() && !node.is_error() {
+                    comments.push(CommentNode::new(node));
+                } else if cursor.goto_first_child() {
+                    continue;
+                }
+
+                while !cursor.goto_next_sibling() {
+                    if !cursor.goto_parent() {
+                        break 'traversal;
+                    }
+                }
+            }
+
+            (cursor, comments)
+        })
+    }
+
+    /// Returns comments that directly precede this node in the order they appear in the text.
+    /// Comments separated from the node by an empty line or trailing some other node on its line are not included.
+    pub fn preceding_comments(&self) -> Vec<CommentNode<'script>> {
+        let mut comments = Vec::new();
+        let mut next_start_row = self.tree_node.start_position().row;
+        let mut prev = self.tree_node.prev_sibling();
+        while let Some(node) = prev {
+            if !node.is_extra() || node.is_error() || node.end_position().row + 1 < next_start_row {
+                break;
+            }
+
+            let prev_node = node.prev_sibling();
+            let is_trailing = prev_node
+                .map(|n| !n.is_extra() && n.end_position().row == node.start_position().row)
+                .unwrap_or(false);
+            if is_trailing {
+                break;
+            }
+
+            comments.push(CommentNode::new(node));
+            next_start_row = node.start_position().row;
+            prev = prev_node;
+        }
+
+        comments.reverse();
+        comments
+    }
+
     /// Whether any nodes descending from this node are errors
     pub fn has_errors(&self) -> bool {
         self.use_cursor(|mut cursor| {
@@ -360,3 +412,39 @@ impl<'script> Iterator for SyntaxNodeFieldChildren<'script> {
 pub trait NamedSyntaxNode {
     const NODE_KIND: &'static str;
 }
+
+
+
+#[cfg(test)]
+mod test {
+    use crate::{ast::RootStatement, script_document::ScriptDocument, Script};
+
+    #[test]
+    fn test_preceding_comments() {
+        let doc = ScriptDocument::from_str(
+r#"
+// not attached
+
+// first line
+/* second line */
+class A {}
+
+var x : int; // trailing
+class B {}
+"#);
+
+        let script = Script::new(&doc).unwrap();
+        let comments: Vec<Vec<String>> = script.root_node().iter()
+            .filter_map(|stmt| match stmt.value() {
+                RootStatement::Class(n) => Some(n.preceding_comments()),
+                _ => None
+            })
+            .map(|comments| comments.iter().map(|c| c.value(&doc).content().trim().to_string()).collect())
+            .collect();
+
+        assert_eq!(comments, vec![
+            vec!["first line".to_string(), "second line".to_string()],
+            vec![]
+        ]);
+    }
+}
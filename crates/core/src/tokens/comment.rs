@@ -0,0 +1,68 @@
+use std::borrow::Cow;
+use std::fmt::Debug;
+use crate::{script_document::ScriptDocument, AnyNode, DebugRange, SyntaxNode};
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommentKind {
+    /// `// comment`
+    Line,
+    /// `/* comment */`
+    Block
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Comment<'d> {
+    pub kind: CommentKind,
+    /// Text of the comment together with `//` or `/*` and `*/`
+    pub text: Cow<'d, str>
+}
+
+impl Comment<'_> {
+    /// Text of the comment without the comment delimiters
+    pub fn content(&self) -> &str {
+        match self.kind {
+            CommentKind::Line => {
+                self.text.strip_prefix("//").unwrap_or(&self.text)
+            },
+            CommentKind::Block => {
+                let s = self.text.strip_prefix("/*").unwrap_or(&self.text);
+                s.strip_suffix("*/").unwrap_or(s)
+            }
+        }
+    }
+}
+
+
+pub type CommentNode<'script> = SyntaxNode<'script, Comment<'script>>;
+
+impl CommentNode<'_> {
+    pub fn value<'d>(&self, doc: &'d ScriptDocument) -> Comment<'d> {
+        let text = self.text(doc);
+        let kind = if text.starts_with("/*") { CommentKind::Block } else { CommentKind::Line };
+
+        Comment {
+            kind,
+            text
+        }
+    }
+}
+
+impl Debug for CommentNode<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Comment {}", self.range().debug())
+    }
+}
+
+impl<'script> TryFrom<AnyNode<'script>> for CommentNode<'script> {
+    type Error = ();
+
+    fn try_from(value: AnyNode<'script>) -> Result<Self, Self::Error> {
+        // comments are the only extra nodes in the grammar
+        if value.tree_node.is_extra() && !value.tree_node.is_error() {
+            Ok(value.into())
+        } else {
+            Err(())
+        }
+    }
+}
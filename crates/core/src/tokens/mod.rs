@@ -3,9 +3,11 @@ mod keywords;
 mod operators;
 mod literals;
 mod unnamed;
+mod comment;
 
 pub use identifier::*;
 pub use keywords::*;
 pub use operators::*;
 pub use literals::*;
-pub use unnamed::*;
\ No newline at end of file
+pub use unnamed::*;
+pub use comment::*;
\ No newline at end of file
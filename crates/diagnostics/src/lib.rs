@@ -1,6 +1,7 @@
 use std::path::PathBuf;
 use lsp_types as lsp;
 use abs_path::AbsPath;
+use serde::{Deserialize, Serialize};
 use strum_macros::IntoStaticStr;
 
 
@@ -31,19 +32,20 @@ impl Into<lsp::Diagnostic> for Diagnostic {
                 }]
             }),
             tags: None,
-            data: None
+            data: self.kind.data().and_then(|data| serde_json::to_value(data).ok())
         }
     }
 }
 
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum DiagnosticDomain {
     ProjectSystem,
     SyntaxAnalysis,
     ContextualSyntaxAnalysis,
     SymbolAnalysis,
     WorkspaceSymbolAnalysis,
+    ExpressionAnalysis,
 }
 
 
@@ -59,7 +61,11 @@ pub enum DiagnosticKind {
     InvalidRedkitProjectManifest(String),
     ProjectDependencyPathNotFound(PathBuf),
     ProjectDependencyNameNotFound(String),
-    ProjectDependencyNameNotFoundAtPath(String),
+    ProjectDependencyNameNotFoundAtPath {
+        content_name: String,
+        /// Name of the content that actually exists at the path
+        name_at_path: String
+    },
     MultipleMatchingProjectDependencies {
         content_name: String,
         matching_paths: Vec<AbsPath>
@@ -90,8 +96,17 @@ pub enum DiagnosticKind {
         annotation_name: String,
         expected_text: String
     },
-    GlobalScopeVarDecl,
-    InvalidLocalVarPlacement,
+    GlobalScopeVarDecl {
+        /// Class that the variable should probably be added to with @addField
+        target_class: Option<String>
+    },
+    InvalidLocalVarPlacement {
+        /// Position right after the last properly placed local variable or after the opening brace of the function
+        valid_placement: lsp::Position,
+        names_range: lsp::Range,
+        var_type_range: lsp::Range,
+        init_value_range: Option<lsp::Range>
+    },
 
     // symbol anaysis
     SymbolNameTaken {
@@ -99,7 +114,6 @@ pub enum DiagnosticKind {
         precursor_file_path: Option<AbsPath>,
         precursor_range: Option<lsp::Range>
     },
-    MissingTypeArg,
     UnnecessaryTypeArg,
     SameContentAnnotation {
         original_file_path: Option<AbsPath>,
@@ -112,6 +126,57 @@ pub enum DiagnosticKind {
         precursor_file_path: Option<AbsPath>,
         precursor_range: Option<lsp::Range>
     },
+
+    // expression analysis
+    TypeMismatch {
+        expected_type: String,
+        found_type: String
+    },
+    InvalidBinaryOperands {
+        op: String,
+        left_type: String,
+        right_type: String
+    },
+    InvalidUnaryOperand {
+        op: String,
+        operand_type: String
+    },
+    UnknownType {
+        type_name: String,
+        suggestion: Option<String>
+    },
+    UnknownIdentifier {
+        name: String,
+        suggestion: Option<String>
+    },
+    UnknownMember {
+        member_name: String,
+        type_name: String,
+        suggestion: Option<String>
+    },
+    TooManyArguments {
+        max_args: usize,
+        found_args: usize
+    },
+    MissingArgument {
+        param_name: String
+    },
+    InvalidOutArgument,
+    InvalidLatentCall {
+        func_name: String,
+        /// Position in the declaration of the calling function at which the `latent` specifier can be inserted.
+        /// None if the function can't be made latent.
+        latent_insert_position: Option<lsp::Position>
+    },
+    InaccessibleMember {
+        member_name: String,
+        owner_name: String,
+        access_modifier: String
+    },
+    MissingTypeArg {
+        /// Type argument deduced from how the annotated variable or function is used
+        type_arg: Option<String>
+    },
 }
 
 #[cfg(debug_assertions)]
@@ -131,7 +196,7 @@ impl DiagnosticKind {
             | InvalidRedkitProjectManifest(_)
             | ProjectDependencyPathNotFound(_)
             | ProjectDependencyNameNotFound(_)
-            | ProjectDependencyNameNotFoundAtPath(_)
+            | ProjectDependencyNameNotFoundAtPath { .. }
             | MultipleMatchingProjectDependencies { .. } 
             | ProjectSelfDependency => DiagnosticDomain::ProjectSystem,
             MissingSyntax(_)
@@ -144,13 +209,24 @@ impl DiagnosticKind {
             | InvalidAnnotationPlacement 
             | MissingAnnotationArgument { .. }
             | IncompatibleAnnotation { .. } 
-            | GlobalScopeVarDecl 
-            | InvalidLocalVarPlacement => DiagnosticDomain::ContextualSyntaxAnalysis,
+            | GlobalScopeVarDecl { .. } 
+            | InvalidLocalVarPlacement { .. } => DiagnosticDomain::ContextualSyntaxAnalysis,
             SymbolNameTaken { .. }
-            | MissingTypeArg
             | UnnecessaryTypeArg 
             | SameContentAnnotation { .. } => DiagnosticDomain::SymbolAnalysis,
-            SymbolNameTakenInDependency { .. } => DiagnosticDomain::WorkspaceSymbolAnalysis
+            SymbolNameTakenInDependency { .. } => DiagnosticDomain::WorkspaceSymbolAnalysis,
+            TypeMismatch { .. }
+            | InvalidBinaryOperands { .. }
+            | InvalidUnaryOperand { .. }
+            | UnknownType { .. }
+            | UnknownIdentifier { .. }
+            | UnknownMember { .. }
+            | TooManyArguments { .. }
+            | MissingArgument { .. }
+            | InvalidOutArgument
+            | InvalidLatentCall { .. }
+            | InaccessibleMember { .. }
+            | MissingTypeArg { .. } => DiagnosticDomain::ExpressionAnalysis
         }
     }
 
@@ -164,7 +240,7 @@ impl DiagnosticKind {
             InvalidRedkitProjectManifest(_) => lsp::DiagnosticSeverity::ERROR,
             ProjectDependencyPathNotFound(_) => lsp::DiagnosticSeverity::ERROR,
             ProjectDependencyNameNotFound(_) => lsp::DiagnosticSeverity::ERROR,
-            ProjectDependencyNameNotFoundAtPath(_) => lsp::DiagnosticSeverity::ERROR,
+            ProjectDependencyNameNotFoundAtPath { .. } => lsp::DiagnosticSeverity::ERROR,
             MultipleMatchingProjectDependencies { .. } => lsp::DiagnosticSeverity::ERROR,
             ProjectSelfDependency => lsp::DiagnosticSeverity::ERROR,
 
@@ -179,15 +255,27 @@ impl DiagnosticKind {
             InvalidAnnotationPlacement => lsp::DiagnosticSeverity::ERROR,
             MissingAnnotationArgument { .. } => lsp::DiagnosticSeverity::ERROR,
             IncompatibleAnnotation { .. } => lsp::DiagnosticSeverity::ERROR,
-            GlobalScopeVarDecl => lsp::DiagnosticSeverity::ERROR,
-            InvalidLocalVarPlacement => lsp::DiagnosticSeverity::ERROR,
+            GlobalScopeVarDecl { .. } => lsp::DiagnosticSeverity::ERROR,
+            InvalidLocalVarPlacement { .. } => lsp::DiagnosticSeverity::ERROR,
 
             SymbolNameTaken { .. } => lsp::DiagnosticSeverity::ERROR,
-            MissingTypeArg => lsp::DiagnosticSeverity::ERROR,
             UnnecessaryTypeArg => lsp::DiagnosticSeverity::ERROR,
             SameContentAnnotation { .. } => lsp::DiagnosticSeverity::WARNING,
 
-            SymbolNameTakenInDependency { .. } => lsp::DiagnosticSeverity::ERROR
+            SymbolNameTakenInDependency { .. } => lsp::DiagnosticSeverity::ERROR,
+
+            TypeMismatch { .. } => lsp::DiagnosticSeverity::ERROR,
+            InvalidBinaryOperands { .. } => lsp::DiagnosticSeverity::ERROR,
+            InvalidUnaryOperand { .. } => lsp::DiagnosticSeverity::ERROR,
+            UnknownType { .. } => lsp::DiagnosticSeverity::ERROR,
+            UnknownIdentifier { .. } => lsp::DiagnosticSeverity::ERROR,
+            UnknownMember { .. } => lsp::DiagnosticSeverity::ERROR,
+            TooManyArguments { .. } => lsp::DiagnosticSeverity::ERROR,
+            MissingArgument { .. } => lsp::DiagnosticSeverity::ERROR,
+            InvalidOutArgument => lsp::DiagnosticSeverity::ERROR,
+            InvalidLatentCall { .. } => lsp::DiagnosticSeverity::ERROR,
+            InaccessibleMember { .. } => lsp::DiagnosticSeverity::ERROR,
+            MissingTypeArg { .. } => lsp::DiagnosticSeverity::ERROR
         }
     }
 
@@ -200,7 +288,7 @@ impl DiagnosticKind {
             InvalidRedkitProjectManifest(err) => err.clone(),
             ProjectDependencyPathNotFound(p) => format!("Dependency could not be found at path \"{}\"", p.display()),
             ProjectDependencyNameNotFound(n) => format!("Dependency could not be found with name \"{n}\""),
-            ProjectDependencyNameNotFoundAtPath(n) => format!("Dependency with name \"{n}\" could not be found at specified path"),
+            ProjectDependencyNameNotFoundAtPath { content_name, .. } => format!("Dependency with name \"{content_name}\" could not be found at specified path"),
             MultipleMatchingProjectDependencies { content_name: project_name, matching_paths } => format!("Multiple matching contents for dependency with name \"{project_name}\": {:?}", matching_paths.into_iter().map(|p| p.to_string()).collect::<Vec<_>>()),
             ProjectSelfDependency => "Content may not specify itself as its own dependency".into(),
 
@@ -215,15 +303,27 @@ impl DiagnosticKind {
             InvalidAnnotationPlacement => "Annotations can only be used at the global scope".into(),
             MissingAnnotationArgument { missing } => format!("This annotation requires {missing} argument"),
             IncompatibleAnnotation { annotation_name, expected_text } => format!("{} may only be used for {}", annotation_name, expected_text),
-            GlobalScopeVarDecl => "Syntax error: variable declarations in the global scope are not allowed unless you intend to use the @addField annotation.".into(),
-            InvalidLocalVarPlacement => "Local variables can only be declared at the start of the function before all other statements".into(),
+            GlobalScopeVarDecl { .. } => "Syntax error: variable declarations in the global scope are not allowed unless you intend to use the @addField annotation.".into(),
+            InvalidLocalVarPlacement { .. } => "Local variables can only be declared at the start of the function before all other statements".into(),
 
             SymbolNameTaken { name, .. } => format!("The name \"{}\" is defined multiple times", name),
-            MissingTypeArg => "Missing type argument".into(),
             UnnecessaryTypeArg => "This type does not take any type arguments".into(),
             SameContentAnnotation { .. } => "WIDE does not support creating annotations for types from the same content. Doing so will result in undefined behaviour.".into(),
 
             SymbolNameTakenInDependency { name, .. } => format!("The name \"{}\" is already defined in another content", name),
+
+            TypeMismatch { expected_type, found_type } => format!("Type mismatch: expected \"{}\", found \"{}\"", expected_type, found_type),
+            InvalidBinaryOperands { op, left_type, right_type } => format!("Operator \"{}\" cannot be applied to \"{}\" and \"{}\"", op, left_type, right_type),
+            InvalidUnaryOperand { op, operand_type } => format!("Operator \"{}\" cannot be applied to \"{}\"", op, operand_type),
+            UnknownType { type_name, suggestion } => format!("Unknown type \"{}\"{}", type_name, did_you_mean(suggestion)),
+            UnknownIdentifier { name, suggestion } => format!("Cannot find \"{}\" in this scope{}", name, did_you_mean(suggestion)),
+            UnknownMember { member_name, type_name, suggestion } => format!("\"{}\" has no member named \"{}\"{}", type_name, member_name, did_you_mean(suggestion)),
+            TooManyArguments { max_args, found_args } => format!("Too many arguments: expected at most {}, found {}", max_args, found_args),
+            MissingArgument { param_name } => format!("Missing argument for parameter \"{}\"", param_name),
+            InvalidOutArgument => "Arguments passed to out parameters must be variables, fields or array elements".into(),
+            InvalidLatentCall { func_name, .. } => format!("Latent function \"{}\" can only be called from other latent functions or from entry functions of states", func_name),
+            InaccessibleMember { member_name, owner_name, access_modifier } => format!("Member \"{}\" of \"{}\" is {} and cannot be accessed here", member_name, owner_name, access_modifier),
+            MissingTypeArg { .. } => "Missing type argument".into(),
         }
     }
 
@@ -250,6 +350,38 @@ impl DiagnosticKind {
         }
     }
 
+    /// Data needed to provide quick fixes for the diagnostic, which the client sends back in code action requests
+    fn data(&self) -> Option<DiagnosticData> {
+        use DiagnosticKind::*;
+
+        match self {
+            ProjectDependencyNameNotFoundAtPath { name_at_path, .. } => Some(DiagnosticData::ProjectDependencyNameNotFoundAtPath {
+                name_at_path: name_at_path.clone()
+            }),
+            GlobalScopeVarDecl { target_class: Some(target_class) } => Some(DiagnosticData::GlobalScopeVarDecl {
+                target_class: target_class.clone()
+            }),
+            InvalidLocalVarPlacement { valid_placement, names_range, var_type_range, init_value_range } => Some(DiagnosticData::InvalidLocalVarPlacement { 
+                valid_placement: *valid_placement, 
+                names_range: *names_range, 
+                var_type_range: *var_type_range, 
+                init_value_range: *init_value_range
+            }),
+            UnknownType { suggestion: Some(suggestion), .. }
+            | UnknownIdentifier { suggestion: Some(suggestion), .. }
+            | UnknownMember { suggestion: Some(suggestion), .. } => Some(DiagnosticData::NameSuggestion {
+                suggestion: suggestion.clone()
+            }),
+            InvalidLatentCall { latent_insert_position: Some(latent_insert_position), .. } => Some(DiagnosticData::InvalidLatentCall {
+                latent_insert_position: *latent_insert_position
+            }),
+            MissingTypeArg { type_arg: Some(type_arg) } => Some(DiagnosticData::MissingTypeArg {
+                type_arg: type_arg.clone()
+            }),
+            _ => None
+        }
+    }
+
     fn code(&self) -> &str {
         // using strum's IntoStaticStr
         let code: &str = self.into();
@@ -262,6 +394,49 @@ impl DiagnosticKind {
     }
 }
 
+/// Structured data put into the `data` field of diagnostics.
+/// Only present for diagnostics which need more information than just the range to provide quick fixes.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub enum DiagnosticData {
+    #[serde(rename_all = "camelCase")]
+    ProjectDependencyNameNotFoundAtPath {
+        name_at_path: String
+    },
+    /// Class that the variable can be added to with @addField
+    #[serde(rename_all = "camelCase")]
+    GlobalScopeVarDecl {
+        target_class: String
+    },
+    #[serde(rename_all = "camelCase")]
+    InvalidLocalVarPlacement {
+        valid_placement: lsp::Position,
+        names_range: lsp::Range,
+        var_type_range: lsp::Range,
+        init_value_range: Option<lsp::Range>
+    },
+    /// Name that the diagnosed name should probably be replaced with
+    #[serde(rename_all = "camelCase")]
+    NameSuggestion {
+        suggestion: String
+    },
+    #[serde(rename_all = "camelCase")]
+    InvalidLatentCall {
+        latent_insert_position: lsp::Position
+    },
+    /// Type argument that can be put into the array type annotation
+    #[serde(rename_all = "camelCase")]
+    MissingTypeArg {
+        type_arg: String
+    }
+}
+
+fn did_you_mean(suggestion: &Option<String>) -> String {
+    suggestion.as_ref()
+        .map(|s| format!(". Did you mean \"{}\"?", s))
+        .unwrap_or_default()
+}
+
 struct DiagnosticRelatedInfo {
     path: AbsPath,
     range: lsp::Range,
@@ -1,13 +1,15 @@
-use std::{collections::HashMap, sync::Arc};
-use dashmap::DashMap;
+use std::{collections::HashMap, sync::{atomic::{AtomicBool, AtomicU64, Ordering}, Arc, Mutex}};
+use dashmap::{DashMap, DashSet};
 use filetime::FileTime;
 use shrinkwraprs::Shrinkwrap;
 use tokio::sync::RwLock;
 use tower_lsp::Client;
+use tower_lsp::lsp_types as lsp;
 use abs_path::AbsPath;
 use witcherscript::{script_document::ScriptDocument, Script};
 use witcherscript_analysis::symbol_analysis::symbol_table::{marcher::SymbolTableMarcher, SymbolTable};
-use witcherscript_project::{ContentGraph, SourceTree, SourceTreePath};
+use witcherscript_analysis::symbol_analysis::references::ReferenceIndex;
+use witcherscript_project::{ContentGraph, Manifest, SourceTree, SourceTreePath};
 use crate::{config::Config, reporting::Reporter};
 
 
@@ -27,7 +29,20 @@ pub struct BackendInner {
     pub source_trees: SourceTreeMap,
     // key is path to the file
     pub scripts: Arc<ScriptStates>,
+    // paths to scripts currently opened in the editor
+    pub open_scripts: DashSet<AbsPath>,
+    // key is path to the manifest file
+    pub manifests: ManifestStates,
     pub symtabs: RwLock<SymbolTables>,
+    pub ref_indices: RwLock<ReferenceIndices>,
+    pub semantic_tokens_cache: SemanticTokensCache,
+    pub workspace_analysis: Mutex<Option<WorkspaceAnalysisRun>>,
+    // whether the client lets the server register file watchers on its own
+    pub file_watchers_support: AtomicBool,
+    // whether file watchers can use patterns relative to a base directory
+    pub relative_pattern_support: AtomicBool,
+    // whether the client accepts type hierarchy being registered after initialization
+    pub type_hierarchy_support: AtomicBool
 }
 
 #[derive(Debug, Shrinkwrap)]
@@ -74,6 +89,28 @@ impl ScriptStates {
     }
 }
 
+/// State of a project manifest opened in the editor
+#[derive(Debug)]
+pub struct ManifestState {
+    pub buffer: ScriptDocument,
+    /// Last successfully parsed form of the manifest.
+    /// It is kept while the document is being edited and temporarily can't be parsed.
+    pub manifest: Option<Manifest>
+}
+
+#[derive(Debug, Shrinkwrap)]
+pub struct ManifestStates {
+    inner: DashMap<AbsPath, ManifestState>
+}
+
+impl ManifestStates {
+    fn new() -> Self {
+        Self {
+            inner: DashMap::new()
+        }
+    }
+}
+
 #[derive(Debug, Shrinkwrap)]
 #[shrinkwrap(mutable)]
 pub struct SymbolTables {
@@ -89,6 +126,52 @@ impl SymbolTables {
     }
 }
 
+#[derive(Debug, Shrinkwrap)]
+#[shrinkwrap(mutable)]
+pub struct ReferenceIndices {
+    // key is path to content directory
+    pub inner: HashMap<AbsPath, ReferenceIndex>
+}
+
+impl ReferenceIndices {
+    fn new() -> Self {
+        Self {
+            inner: HashMap::new()
+        }
+    }
+}
+
+/// Semantic tokens last sent to the client, needed to compute deltas for subsequent requests
+#[derive(Debug, Shrinkwrap)]
+pub struct SemanticTokensCache {
+    // key is path to the file
+    #[shrinkwrap(main_field)]
+    inner: DashMap<AbsPath, lsp::SemanticTokens>,
+    result_id_counter: AtomicU64
+}
+
+impl SemanticTokensCache {
+    fn new() -> Self {
+        Self {
+            inner: DashMap::new(),
+            result_id_counter: AtomicU64::new(0)
+        }
+    }
+
+    pub fn next_result_id(&self) -> String {
+        self.result_id_counter
+            .fetch_add(1, Ordering::Relaxed)
+            .to_string()
+    }
+}
+
+/// Handle to the analysis of workspace scripts running in the background
+#[derive(Debug)]
+pub struct WorkspaceAnalysisRun {
+    pub progress_token: lsp::NumberOrString,
+    pub cancelled: Arc<AtomicBool>
+}
+
 impl Backend {
     pub const LANGUAGE_ID: &'static str = "witcherscript";
     pub const SERVER_NAME: &'static str = "witcherscript-ide";
@@ -104,7 +187,15 @@ impl Backend {
                 content_graph: RwLock::new(ContentGraph::new()),
                 source_trees: SourceTreeMap::new(),
                 scripts: Arc::new(ScriptStates::new()),
-                symtabs: RwLock::new(SymbolTables::new())
+                open_scripts: DashSet::new(),
+                manifests: ManifestStates::new(),
+                symtabs: RwLock::new(SymbolTables::new()),
+                ref_indices: RwLock::new(ReferenceIndices::new()),
+                semantic_tokens_cache: SemanticTokensCache::new(),
+                workspace_analysis: Mutex::new(None),
+                file_watchers_support: AtomicBool::new(false),
+                relative_pattern_support: AtomicBool::new(false),
+                type_hierarchy_support: AtomicBool::new(false)
             })
         }
     }
@@ -13,7 +13,36 @@ use crate::Backend;
 pub struct Config {
     pub game_directory: PathBuf,
     pub content_repositories: Vec<PathBuf>,
-    pub enable_syntax_analysis: bool
+    pub enable_syntax_analysis: bool,
+    pub enable_workspace_analysis: bool,
+    pub formatting: FormattingConfig
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct FormattingConfig {
+    pub brace_style: BraceStyle,
+    pub space_around_operators: bool,
+    pub space_before_type_colon: bool,
+    pub space_after_control_keywords: bool
+}
+
+impl Default for FormattingConfig {
+    fn default() -> Self {
+        Self {
+            brace_style: BraceStyle::NextLine,
+            space_around_operators: true,
+            space_before_type_colon: true,
+            space_after_control_keywords: true
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BraceStyle {
+    SameLine,
+    NextLine
 }
 
 #[derive(Debug, Error)]
@@ -25,10 +54,12 @@ pub enum ConfigError {
 }
 
 impl Config {
-    const CONFIG_ITEM_SECTIONS: [&'static str; 3] = [
+    const CONFIG_ITEM_SECTIONS: [&'static str; 5] = [
         "witcherscript-ide.gameDirectory",
         "witcherscript-ide.contentRepositories",
-        "witcherscript-ide.languageServer.syntaxAnalysis"
+        "witcherscript-ide.languageServer.syntaxAnalysis",
+        "witcherscript-ide.languageServer.workspaceAnalysis",
+        "witcherscript-ide.formatting"
     ];
 
     pub async fn fetch(client: &Client) -> Result<Self, ConfigError> {
@@ -42,7 +73,9 @@ impl Config {
         Ok(Self {
             game_directory: serde_json::from_value(values[0].clone())?,
             content_repositories: serde_json::from_value(values[1].clone())?,
-            enable_syntax_analysis: serde_json::from_value(values[2].clone())?
+            enable_syntax_analysis: serde_json::from_value(values[2].clone())?,
+            enable_workspace_analysis: serde_json::from_value(values[3].clone())?,
+            formatting: serde_json::from_value(values[4].clone())?
         })
     }
 }
@@ -77,7 +110,9 @@ impl Backend {
 pub struct ConfigDifference {
     pub game_directory_changed: bool,
     pub content_repositories_changed: bool,
-    pub enable_syntax_analysis_changed: bool
+    pub enable_syntax_analysis_changed: bool,
+    pub enable_workspace_analysis_changed: bool,
+    pub formatting_changed: bool
 }
 
 impl ConfigDifference {
@@ -85,18 +120,24 @@ impl ConfigDifference {
         let game_directory_changed = old_config.game_directory != new_config.game_directory;
         let content_repositories_changed = old_config.content_repositories != new_config.content_repositories;
         let enable_syntax_analysis_changed = old_config.enable_syntax_analysis != new_config.enable_syntax_analysis;
+        let enable_workspace_analysis_changed = old_config.enable_workspace_analysis != new_config.enable_workspace_analysis;
+        let formatting_changed = old_config.formatting != new_config.formatting;
 
         ConfigDifference {
             game_directory_changed,
             content_repositories_changed,
-            enable_syntax_analysis_changed
+            enable_syntax_analysis_changed,
+            enable_workspace_analysis_changed,
+            formatting_changed
         }
     }
 
     pub fn any_changed(&self) -> bool {
         self.game_directory_changed || 
         self.content_repositories_changed ||
-        self.enable_syntax_analysis_changed
+        self.enable_syntax_analysis_changed ||
+        self.enable_workspace_analysis_changed ||
+        self.formatting_changed
     }
 }
 
@@ -105,7 +146,9 @@ impl Default for ConfigDifference {
         Self { 
             game_directory_changed: false, 
             content_repositories_changed: false,
-            enable_syntax_analysis_changed: false
+            enable_syntax_analysis_changed: false,
+            enable_workspace_analysis_changed: false,
+            formatting_changed: false
         }
     }
 }
\ No newline at end of file
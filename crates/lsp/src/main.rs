@@ -68,6 +68,10 @@ impl LanguageServer for Backend {
         self.did_change_workspace_folders_impl(params).await
     }
 
+    async fn did_change_watched_files(&self, params: lsp::DidChangeWatchedFilesParams) {
+        self.did_change_watched_files_impl(params).await
+    }
+
 
     async fn selection_range(&self, params: lsp::SelectionRangeParams) -> Result<Option<Vec<lsp::SelectionRange>>> {
         self.selection_range_impl(params).await
@@ -90,10 +94,118 @@ impl LanguageServer for Backend {
         self.goto_type_definition_impl(params).await
     }
 
+    async fn goto_implementation(&self, params: lsp::request::GotoImplementationParams) -> Result<Option<lsp::request::GotoImplementationResponse>> {
+        self.goto_implementation_impl(params).await
+    }
+
 
     async fn hover(&self, params: lsp::HoverParams) -> Result<Option<lsp::Hover>> {
         self.hover_impl(params).await
     }
+
+    async fn completion(&self, params: lsp::CompletionParams) -> Result<Option<lsp::CompletionResponse>> {
+        self.completion_impl(params).await
+    }
+
+    async fn signature_help(&self, params: lsp::SignatureHelpParams) -> Result<Option<lsp::SignatureHelp>> {
+        self.signature_help_impl(params).await
+    }
+
+    async fn references(&self, params: lsp::ReferenceParams) -> Result<Option<Vec<lsp::Location>>> {
+        self.references_impl(params).await
+    }
+
+    async fn prepare_rename(&self, params: lsp::TextDocumentPositionParams) -> Result<Option<lsp::PrepareRenameResponse>> {
+        self.prepare_rename_impl(params).await
+    }
+
+    async fn rename(&self, params: lsp::RenameParams) -> Result<Option<lsp::WorkspaceEdit>> {
+        self.rename_impl(params).await
+    }
+
+    async fn symbol(&self, params: lsp::WorkspaceSymbolParams) -> Result<Option<Vec<lsp::SymbolInformation>>> {
+        self.workspace_symbol_impl(params).await
+    }
+
+    async fn semantic_tokens_full(&self, params: lsp::SemanticTokensParams) -> Result<Option<lsp::SemanticTokensResult>> {
+        self.semantic_tokens_full_impl(params).await
+    }
+
+    async fn semantic_tokens_full_delta(&self, params: lsp::SemanticTokensDeltaParams) -> Result<Option<lsp::SemanticTokensFullDeltaResult>> {
+        self.semantic_tokens_full_delta_impl(params).await
+    }
+
+    async fn formatting(&self, params: lsp::DocumentFormattingParams) -> Result<Option<Vec<lsp::TextEdit>>> {
+        self.formatting_impl(params).await
+    }
+
+    async fn range_formatting(&self, params: lsp::DocumentRangeFormattingParams) -> Result<Option<Vec<lsp::TextEdit>>> {
+        self.range_formatting_impl(params).await
+    }
+
+    async fn on_type_formatting(&self, params: lsp::DocumentOnTypeFormattingParams) -> Result<Option<Vec<lsp::TextEdit>>> {
+        self.on_type_formatting_impl(params).await
+    }
+
+    async fn folding_range(&self, params: lsp::FoldingRangeParams) -> Result<Option<Vec<lsp::FoldingRange>>> {
+        self.folding_range_impl(params).await
+    }
+
+    async fn inlay_hint(&self, params: lsp::InlayHintParams) -> Result<Option<Vec<lsp::InlayHint>>> {
+        self.inlay_hint_impl(params).await
+    }
+
+    async fn prepare_call_hierarchy(&self, params: lsp::CallHierarchyPrepareParams) -> Result<Option<Vec<lsp::CallHierarchyItem>>> {
+        self.prepare_call_hierarchy_impl(params).await
+    }
+
+    async fn incoming_calls(&self, params: lsp::CallHierarchyIncomingCallsParams) -> Result<Option<Vec<lsp::CallHierarchyIncomingCall>>> {
+        self.incoming_calls_impl(params).await
+    }
+
+    async fn outgoing_calls(&self, params: lsp::CallHierarchyOutgoingCallsParams) -> Result<Option<Vec<lsp::CallHierarchyOutgoingCall>>> {
+        self.outgoing_calls_impl(params).await
+    }
+
+    async fn prepare_type_hierarchy(&self, params: lsp::TypeHierarchyPrepareParams) -> Result<Option<Vec<lsp::TypeHierarchyItem>>> {
+        self.prepare_type_hierarchy_impl(params).await
+    }
+
+    async fn supertypes(&self, params: lsp::TypeHierarchySupertypesParams) -> Result<Option<Vec<lsp::TypeHierarchyItem>>> {
+        self.supertypes_impl(params).await
+    }
+
+    async fn subtypes(&self, params: lsp::TypeHierarchySubtypesParams) -> Result<Option<Vec<lsp::TypeHierarchyItem>>> {
+        self.subtypes_impl(params).await
+    }
+
+    async fn code_action(&self, params: lsp::CodeActionParams) -> Result<Option<lsp::CodeActionResponse>> {
+        self.code_action_impl(params).await
+    }
+
+    async fn code_lens(&self, params: lsp::CodeLensParams) -> Result<Option<Vec<lsp::CodeLens>>> {
+        self.code_lens_impl(params).await
+    }
+
+    async fn code_lens_resolve(&self, params: lsp::CodeLens) -> Result<lsp::CodeLens> {
+        self.code_lens_resolve_impl(params).await
+    }
+
+    async fn document_highlight(&self, params: lsp::DocumentHighlightParams) -> Result<Option<Vec<lsp::DocumentHighlight>>> {
+        self.document_highlight_impl(params).await
+    }
+
+    async fn document_link(&self, params: lsp::DocumentLinkParams) -> Result<Option<Vec<lsp::DocumentLink>>> {
+        self.document_link_impl(params).await
+    }
+
+    async fn diagnostic(&self, params: lsp::DocumentDiagnosticParams) -> Result<lsp::DocumentDiagnosticReportResult> {
+        self.diagnostic_impl(params).await
+    }
+
+    async fn workspace_diagnostic(&self, params: lsp::WorkspaceDiagnosticParams) -> Result<lsp::WorkspaceDiagnosticReportResult> {
+        self.workspace_diagnostic_impl(params).await
+    }
 }
 
 
@@ -115,6 +227,8 @@ async fn main() {
         .custom_method(requests::debug::content_graph_dot::METHOD, Backend::content_graph_dot)
         .custom_method(requests::debug::script_symbols::METHOD, Backend::script_symbols)
         .custom_method(notifications::projects::did_import_scripts::METHOD, Backend::did_import_scripts)
+        // not supported by tower-lsp's LanguageServer trait yet
+        .custom_method(<lsp::notification::WorkDoneProgressCancel as lsp::notification::Notification>::METHOD, Backend::work_done_progress_cancel)
         .finish();
 
     Server::new(stdin, stdout, socket).serve(service).await;
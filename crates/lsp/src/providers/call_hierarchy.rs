@@ -0,0 +1,310 @@
+use std::collections::HashMap;
+use std::{cell::RefCell, rc::Rc};
+use abs_path::AbsPath;
+use tower_lsp::lsp_types as lsp;
+use tower_lsp::jsonrpc::Result;
+use witcherscript::ast::*;
+use witcherscript::script_document::ScriptDocument;
+use witcherscript_analysis::symbol_analysis::symbol_path::{SymbolPath, SymbolPathBuf};
+use witcherscript_analysis::symbol_analysis::symbol_table::marcher::SymbolTableMarcher;
+use witcherscript_analysis::symbol_analysis::symbols::*;
+use witcherscript_analysis::symbol_analysis::unqualified_name_lookup::*;
+use witcherscript_analysis::utils::*;
+use crate::Backend;
+use super::common::resolve_text_document_position;
+
+
+/// Ranges of calls grouped by the path of the caller and the file in which it is declared
+type CallsByCaller = HashMap<(SymbolPathBuf, AbsPath), Vec<lsp::Range>>;
+
+impl Backend {
+    pub async fn prepare_call_hierarchy_impl(&self, params: lsp::CallHierarchyPrepareParams) -> Result<Option<Vec<lsp::CallHierarchyItem>>> {
+        let doc_path = AbsPath::try_from(params.text_document_position_params.text_document.uri.clone()).unwrap();
+
+        if doc_path.extension().unwrap_or_default() != "ws" {
+            return Ok(None);
+        }
+
+        let content_path;
+        if let Some(path) = self.scripts.get(&doc_path).and_then(|ss| ss.content_info.as_ref().map(|ci| ci.content_path.to_owned())) {
+            content_path = path;
+        }
+        else {
+            return Ok(None);
+        }
+
+        let symtabs = self.symtabs.read().await;
+        let symtabs_marcher = self.march_symbol_tables(&symtabs, &content_path).await;
+
+        let script_state;
+        if let Some(ss) = self.scripts.get(&doc_path) {
+            script_state = ss;
+        } else {
+            return Ok(None);
+        }
+
+        let position_target = resolve_text_document_position(params.text_document_position_params.position, &script_state, symtabs_marcher.clone());
+        drop(script_state);
+
+        let sympath;
+        if let Some(path) = position_target.and_then(|t| t.target_symbol_path(&symtabs_marcher)) {
+            sympath = path;
+        } else {
+            return Ok(None);
+        }
+
+        let item = if let Some(wrapped_method) = symtabs_marcher.get_symbol(&sympath).and_then(|v| v.try_as_wrapped_method_ref()) {
+            wrapped_callable_item(&symtabs_marcher, wrapped_method.wrapped_path(), &doc_path)
+        } else {
+            // prefer the redefinition declared in this document if there is one
+            callable_item(&symtabs_marcher, &sympath, Some(&doc_path))
+                .or_else(|| callable_item(&symtabs_marcher, &sympath, None))
+        };
+
+        Ok(item.map(|item| vec![item]))
+    }
+
+    pub async fn incoming_calls_impl(&self, params: lsp::CallHierarchyIncomingCallsParams) -> Result<Option<Vec<lsp::CallHierarchyIncomingCall>>> {
+        let symtabs = self.symtabs.read().await;
+
+        let (content_path, sympath);
+        if let Some((p, s)) = self.resolve_hierarchy_item_target(&symtabs, &params.item.uri, params.item.selection_range.start).await {
+            content_path = p;
+            sympath = s;
+        } else {
+            return Ok(None);
+        }
+
+        // all redefinitions of a function share the same path, so calls are searched for starting from its original declaration
+        let origin_content_path = self.origin_content_path(&symtabs, &content_path, &sympath).await;
+
+        let searched_contents: Vec<AbsPath> =
+            [origin_content_path.clone()].into_iter()
+            .chain(self.content_graph
+                .read().await
+                .walk_dependants(&origin_content_path)
+                .map(|n| n.content.path().to_owned()))
+            .collect();
+
+        // calls grouped by content in which they're made and then by the caller
+        let mut calls_per_content: Vec<(AbsPath, CallsByCaller)> = Vec::new();
+        let ref_indices = self.ref_indices.read().await;
+        for content_path in searched_contents {
+            if let Some(ref_index) = ref_indices.get(&content_path) {
+                let mut callers: HashMap<_, Vec<_>> = HashMap::new();
+                for call in ref_index.get_calls(&sympath) {
+                    callers.entry((call.caller_path.clone(), call.abs_source_path()))
+                        .or_default()
+                        .push(call.range);
+                }
+
+                if !callers.is_empty() {
+                    calls_per_content.push((content_path, callers));
+                }
+            }
+        }
+        drop(ref_indices);
+
+        let mut incoming = Vec::new();
+        for (content_path, callers) in calls_per_content {
+            let symtabs_marcher = self.march_symbol_tables(&symtabs, &content_path).await;
+            for ((caller_path, caller_source_path), from_ranges) in callers {
+                if let Some(from) = callable_item(&symtabs_marcher, &caller_path, Some(&caller_source_path)) {
+                    incoming.push(lsp::CallHierarchyIncomingCall {
+                        from,
+                        from_ranges
+                    });
+                }
+            }
+        }
+
+        Ok(Some(incoming))
+    }
+
+    pub async fn outgoing_calls_impl(&self, params: lsp::CallHierarchyOutgoingCallsParams) -> Result<Option<Vec<lsp::CallHierarchyOutgoingCall>>> {
+        let doc_path = AbsPath::try_from(params.item.uri.clone()).unwrap();
+        let symtabs = self.symtabs.read().await;
+
+        let (content_path, sympath);
+        if let Some((p, s)) = self.resolve_hierarchy_item_target(&symtabs, &params.item.uri, params.item.selection_range.start).await {
+            content_path = p;
+            sympath = s;
+        } else {
+            return Ok(None);
+        }
+
+        let symtabs_marcher = self.march_symbol_tables(&symtabs, &content_path).await;
+
+        let script_state;
+        if let Some(ss) = self.scripts.get(&doc_path) {
+            script_state = ss;
+        } else {
+            return Ok(None);
+        }
+
+        let (sympath_builder, sympath_builder_payload) = SymbolPathBuilder::new(&script_state.buffer);
+        let (unl_builder, unl_payload) = UnqualifiedNameLookupBuilder::new(&script_state.buffer, sympath_builder_payload.clone(), symtabs_marcher.clone());
+        let collector = OutgoingCallCollector::new_rc(
+            sympath.clone(),
+            &script_state.buffer,
+            symtabs_marcher.clone(),
+            sympath_builder_payload.clone(),
+            unl_payload.clone()
+        );
+
+        let mut chain = SyntaxNodeVisitorChain::new()
+            .link(sympath_builder)
+            .link(unl_builder)
+            .link_rc(collector.clone());
+
+        script_state.script.visit_nodes(&mut chain);
+
+        let calls = std::mem::take(&mut collector.borrow_mut().calls);
+        drop(chain);
+        drop(script_state);
+
+        // group calls by the function they call while keeping the order in which they appear
+        let mut callees: Vec<(SymbolPathBuf, Vec<lsp::Range>)> = Vec::new();
+        for (callee_path, range) in calls {
+            if let Some((_, ranges)) = callees.iter_mut().find(|(p, _)| p == &callee_path) {
+                ranges.push(range);
+            } else {
+                callees.push((callee_path, vec![range]));
+            }
+        }
+
+        let mut outgoing = Vec::new();
+        for (callee_path, from_ranges) in callees {
+            let to = if let Some(wrapped_method) = symtabs_marcher.get_symbol(&callee_path).and_then(|v| v.try_as_wrapped_method_ref()) {
+                wrapped_callable_item(&symtabs_marcher, wrapped_method.wrapped_path(), &doc_path)
+            } else {
+                callable_item(&symtabs_marcher, &callee_path, None)
+            };
+
+            if let Some(to) = to {
+                outgoing.push(lsp::CallHierarchyOutgoingCall {
+                    to,
+                    from_ranges
+                });
+            }
+        }
+
+        Ok(Some(outgoing))
+    }
+}
+
+
+/// Creates a call hierarchy item for a function or an event with given path.
+/// If `source_path` is specified, the item is created only for a redefinition of the function declared in that file.
+fn callable_item(symtab_marcher: &SymbolTableMarcher, sympath: &SymbolPath, source_path: Option<&AbsPath>) -> Option<lsp::CallHierarchyItem> {
+    symtab_marcher
+        .redefinition_chain(sympath)
+        .filter(|symvar| source_path.map(|sp| symvar.location().map(|loc| &loc.abs_source_path() == sp).unwrap_or(false)).unwrap_or(true))
+        .find_map(make_call_hierarchy_item)
+}
+
+/// Creates a call hierarchy item for a method wrapped by the wrapper declared in given file
+fn wrapped_callable_item(symtab_marcher: &SymbolTableMarcher, wrapped_path: &SymbolPath, wrapper_source_path: &AbsPath) -> Option<lsp::CallHierarchyItem> {
+    symtab_marcher
+        .redefinition_chain(wrapped_path)
+        .skip_while(|symvar| symvar.location().map(|loc| &loc.abs_source_path() != wrapper_source_path).unwrap_or(true))
+        .nth(1)
+        .and_then(make_call_hierarchy_item)
+}
+
+fn make_call_hierarchy_item(symvar: &SymbolVariant) -> Option<lsp::CallHierarchyItem> {
+    let class_name = symvar.path()
+        .parent()
+        .and_then(|p| p.components().next_back())
+        .map(|c| c.name)
+        .unwrap_or_default();
+
+    let (kind, detail) = match symvar {
+        SymbolVariant::GlobalFunc(_) => (lsp::SymbolKind::FUNCTION, None),
+        SymbolVariant::GlobalFuncReplacer(_) => (lsp::SymbolKind::FUNCTION, Some(AnnotationKind::ReplaceMethod.to_string())),
+        SymbolVariant::MemberFunc(_) => (lsp::SymbolKind::METHOD, Some(class_name.to_string())),
+        SymbolVariant::Event(_) => (lsp::SymbolKind::EVENT, Some(class_name.to_string())),
+        SymbolVariant::MemberFuncInjector(_) => (lsp::SymbolKind::METHOD, Some(format!("{}({})", AnnotationKind::AddMethod, class_name))),
+        SymbolVariant::MemberFuncReplacer(_) => (lsp::SymbolKind::METHOD, Some(format!("{}({})", AnnotationKind::ReplaceMethod, class_name))),
+        SymbolVariant::MemberFuncWrapper(_) => (lsp::SymbolKind::METHOD, Some(format!("{}({})", AnnotationKind::WrapMethod, class_name))),
+        _ => return None
+    };
+
+    let loc = symvar.location()?;
+    Some(lsp::CallHierarchyItem {
+        name: symvar.name().to_string(),
+        kind,
+        tags: None,
+        detail,
+        uri: loc.abs_source_path().to_uri(),
+        range: loc.range,
+        selection_range: loc.label_range,
+        data: None
+    })
+}
+
+
+/// A node visitor that gathers function calls made inside the body of a specific function.
+/// Expects to work after SymbolPathBuilder and UnqualifiedNameLookupBuilder in visitor chain.
+struct OutgoingCallCollector<'a> {
+    caller_path: SymbolPathBuf,
+    doc: &'a ScriptDocument,
+    symtab_marcher: SymbolTableMarcher<'a>,
+    sympath_builder_payload: Rc<RefCell<SymbolPathBuilderPayload>>,
+    unl_builder_payload: Rc<RefCell<UnqualifiedNameLookup>>,
+    /// Paths of called functions with ranges of their identifiers
+    calls: Vec<(SymbolPathBuf, lsp::Range)>
+}
+
+impl<'a> OutgoingCallCollector<'a> {
+    fn new_rc(
+        caller_path: SymbolPathBuf,
+        doc: &'a ScriptDocument,
+        symtab_marcher: SymbolTableMarcher<'a>,
+        sympath_builder_payload: Rc<RefCell<SymbolPathBuilderPayload>>,
+        unl_builder_payload: Rc<RefCell<UnqualifiedNameLookup>>
+    ) -> Rc<RefCell<Self>> {
+        Rc::new(RefCell::new(Self {
+            caller_path,
+            doc,
+            symtab_marcher,
+            sympath_builder_payload,
+            unl_builder_payload,
+            calls: Vec::new()
+        }))
+    }
+}
+
+impl SyntaxNodeVisitor for OutgoingCallCollector<'_> {
+    fn traversal_policy_default(&self) -> bool {
+        true
+    }
+
+    fn visit_func_call_expr(&mut self, n: &FunctionCallExpressionNode, _: &TraversalContextStack) -> FunctionCallExpressionTraversalPolicy {
+        if self.sympath_builder_payload.borrow().current_sympath != self.caller_path {
+            return TraversalPolicy::default_to(true);
+        }
+
+        let func = n.func();
+        let ident_range = match func.clone().value() {
+            Expression::MemberAccess(access) => access.member().range(),
+            _ => func.range()
+        };
+
+        let callee_path = evaluate_expression(
+            func, TraversalContext::FunctionCallExpressionFunc,
+            self.doc,
+            self.symtab_marcher.clone(),
+            self.sympath_builder_payload.clone(),
+            self.unl_builder_payload.clone()
+        );
+
+        if !callee_path.is_empty() && !callee_path.has_missing() && !callee_path.has_unknown() {
+            self.calls.push((callee_path, ident_range));
+        }
+
+        TraversalPolicy::default_to(true)
+    }
+}
+
+impl SyntaxNodeVisitorChainLink for OutgoingCallCollector<'_> {}
@@ -0,0 +1,200 @@
+use std::collections::HashMap;
+use abs_path::AbsPath;
+use tower_lsp::lsp_types as lsp;
+use tower_lsp::jsonrpc::Result;
+use witcherscript::script_document::ScriptDocument;
+use witcherscript_diagnostics::DiagnosticData;
+use crate::Backend;
+
+
+impl Backend {
+    pub async fn code_action_impl(&self, params: lsp::CodeActionParams) -> Result<Option<lsp::CodeActionResponse>> {
+        let doc_path = AbsPath::try_from(params.text_document.uri.clone()).unwrap();
+
+        let mut actions = Vec::new();
+        for diag in params.context.diagnostics.iter() {
+            let code = match &diag.code {
+                Some(lsp::NumberOrString::String(code)) if diag.source.as_deref() == Some("witcherscript-ide") => code.as_str(),
+                _ => continue
+            };
+
+            let data = diag.data.clone().and_then(|data| serde_json::from_value::<DiagnosticData>(data).ok());
+
+            let fix = if code == "project-dependency-name-not-found-at-path" {
+                dependency_name_fix(diag, data)
+            } else if matches!(code, "unknown-type" | "unknown-identifier" | "unknown-member") {
+                name_suggestion_fix(diag, data)
+            } else if code == "invalid-latent-call" {
+                latent_fix(data)
+            } else if code == "missing-type-arg" {
+                type_arg_fix(diag, data)
+            } else if let Some(script_state) = self.scripts.get(&doc_path) {
+                let doc = &script_state.buffer;
+                match code {
+                    "repeated-specifier" => specifier_removal_fix(diag, doc, "Remove repeated specifier"),
+                    "multiple-access-modifiers" => specifier_removal_fix(diag, doc, "Remove additional access modifier"),
+                    "invalid-local-var-placement" => local_var_placement_fix(diag, data, doc),
+                    "global-scope-var-decl" => add_field_fix(diag, data, doc),
+                    _ => None
+                }
+            } else {
+                None
+            };
+
+            if let Some((title, edits)) = fix {
+                actions.push(lsp::CodeActionOrCommand::CodeAction(lsp::CodeAction {
+                    title,
+                    kind: Some(lsp::CodeActionKind::QUICKFIX),
+                    diagnostics: Some(vec![diag.clone()]),
+                    edit: Some(lsp::WorkspaceEdit {
+                        changes: Some(HashMap::from([(params.text_document.uri.clone(), edits)])),
+                        ..Default::default()
+                    }),
+                    // these are deduced from the rest of the code, so they're not as certain as other fixes
+                    is_preferred: Some(!matches!(code, "missing-type-arg" | "global-scope-var-decl")),
+                    ..Default::default()
+                }));
+            }
+        }
+
+        Ok(Some(actions))
+    }
+}
+
+
+type QuickFix = (String, Vec<lsp::TextEdit>);
+
+/// Removes the specifier together with a single space that follows it
+fn specifier_removal_fix(diag: &lsp::Diagnostic, doc: &ScriptDocument, title: &str) -> Option<QuickFix> {
+    let mut range = diag.range;
+    // specifiers are always followed by some other token, so peeking at the next character is safe
+    let next_char_range = lsp::Range::new(range.end, lsp::Position::new(range.end.line, range.end.character + 1));
+    if doc.text_at(next_char_range) == " " {
+        range.end = next_char_range.end;
+    }
+
+    Some((title.to_string(), vec![lsp::TextEdit::new(range, String::new())]))
+}
+
+/// Declares the variable together with other local variables at the start of the function.
+/// If the variable was initialized, the initialization is left in place as an assignment.
+fn local_var_placement_fix(diag: &lsp::Diagnostic, data: Option<DiagnosticData>, doc: &ScriptDocument) -> Option<QuickFix> {
+    let (valid_placement, names_range, var_type_range, init_value_range) = match data? {
+        DiagnosticData::InvalidLocalVarPlacement { valid_placement, names_range, var_type_range, init_value_range } => (valid_placement, names_range, var_type_range, init_value_range),
+        _ => return None
+    };
+
+    let names = doc.text_at(names_range);
+    if init_value_range.is_some() && names.contains(',') {
+        // the initializer would need to be evaluated multiple times
+        return None;
+    }
+
+    let decl_indent = line_indentation(doc, diag.range.start);
+    let follows_brace = valid_placement.character > 0 
+        && doc.text_at(lsp::Range::new(lsp::Position::new(valid_placement.line, valid_placement.character - 1), valid_placement)) == "{";
+    let placement_indent = if follows_brace {
+        decl_indent.clone()
+    } else {
+        line_indentation(doc, valid_placement)
+    };
+
+    let mut edits = vec![lsp::TextEdit::new(
+        lsp::Range::new(valid_placement, valid_placement),
+        format!("\n{}var {} : {};", placement_indent, names, doc.text_at(var_type_range))
+    )];
+
+    if let Some(init_value_range) = init_value_range {
+        edits.push(lsp::TextEdit::new(diag.range, format!("{} = {};", names, doc.text_at(init_value_range))));
+    } else {
+        // there's always at least the closing brace of the function after the declaration, so peeking at the next character is safe
+        let next_char_range = lsp::Range::new(diag.range.end, lsp::Position::new(diag.range.end.line, diag.range.end.character + 1));
+        let is_alone_in_line = decl_indent.chars().count() == diag.range.start.character as usize && matches!(doc.text_at(next_char_range).as_ref(), "\n" | "\r");
+        let range = if is_alone_in_line {
+            lsp::Range::new(lsp::Position::new(diag.range.start.line, 0), lsp::Position::new(diag.range.end.line + 1, 0))
+        } else {
+            diag.range
+        };
+
+        edits.push(lsp::TextEdit::new(range, String::new()));
+    }
+
+    Some(("Move the declaration to the start of the function".into(), edits))
+}
+
+fn add_field_fix(diag: &lsp::Diagnostic, data: Option<DiagnosticData>, doc: &ScriptDocument) -> Option<QuickFix> {
+    let target_class = match data? {
+        DiagnosticData::GlobalScopeVarDecl { target_class } => target_class,
+        _ => return None
+    };
+
+    let indent = line_indentation(doc, diag.range.start);
+    let edit = lsp::TextEdit::new(
+        lsp::Range::new(diag.range.start, diag.range.start),
+        format!("@addField({target_class})\n{indent}")
+    );
+
+    Some((format!("Add the variable to {} with @addField", target_class), vec![edit]))
+}
+
+fn type_arg_fix(diag: &lsp::Diagnostic, data: Option<DiagnosticData>) -> Option<QuickFix> {
+    let type_arg = match data? {
+        DiagnosticData::MissingTypeArg { type_arg } => type_arg,
+        _ => return None
+    };
+
+    let edit = lsp::TextEdit::new(
+        lsp::Range::new(diag.range.end, diag.range.end),
+        format!("<{}>", type_arg)
+    );
+
+    Some((format!("Add type argument <{}>", type_arg), vec![edit]))
+}
+
+fn dependency_name_fix(diag: &lsp::Diagnostic, data: Option<DiagnosticData>) -> Option<QuickFix> {
+    let name_at_path = match data? {
+        DiagnosticData::ProjectDependencyNameNotFoundAtPath { name_at_path } => name_at_path,
+        _ => return None
+    };
+
+    let is_bare_key = name_at_path.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    let new_key = if is_bare_key {
+        name_at_path.clone()
+    } else {
+        format!("\"{}\"", name_at_path)
+    };
+
+    Some((format!("Change dependency name to \"{}\"", name_at_path), vec![lsp::TextEdit::new(diag.range, new_key)]))
+}
+
+fn name_suggestion_fix(diag: &lsp::Diagnostic, data: Option<DiagnosticData>) -> Option<QuickFix> {
+    let suggestion = match data? {
+        DiagnosticData::NameSuggestion { suggestion } => suggestion,
+        _ => return None
+    };
+
+    Some((format!("Change to \"{}\"", suggestion), vec![lsp::TextEdit::new(diag.range, suggestion)]))
+}
+
+fn latent_fix(data: Option<DiagnosticData>) -> Option<QuickFix> {
+    let latent_insert_position = match data? {
+        DiagnosticData::InvalidLatentCall { latent_insert_position } => latent_insert_position,
+        _ => return None
+    };
+
+    let edit = lsp::TextEdit::new(
+        lsp::Range::new(latent_insert_position, latent_insert_position),
+        "latent ".into()
+    );
+
+    Some(("Make the enclosing function latent".into(), vec![edit]))
+}
+
+/// Returns whitespace at the start of the line that given position is in
+fn line_indentation(doc: &ScriptDocument, pos: lsp::Position) -> String {
+    doc.text_at(lsp::Range::new(lsp::Position::new(pos.line, 0), pos))
+        .chars()
+        .take_while(|c| c.is_whitespace())
+        .collect()
+}
+
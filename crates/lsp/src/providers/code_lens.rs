@@ -0,0 +1,235 @@
+use serde::{Deserialize, Serialize};
+use abs_path::AbsPath;
+use tower_lsp::lsp_types as lsp;
+use tower_lsp::jsonrpc::Result;
+use witcherscript::ast::AnnotationKind;
+use witcherscript_analysis::symbol_analysis::symbols::*;
+use crate::Backend;
+
+
+/// Client-side command that opens a peek view with given locations
+const SHOW_REFERENCES_COMMAND: &str = "witcherscript-ide.misc.showReferences";
+
+/// Lenses only carry information about what they should display.
+/// The actual title and locations are computed when the client asks to resolve the lens.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+enum CodeLensData {
+    References {
+        uri: lsp::Url,
+        position: lsp::Position
+    },
+    Overrides {
+        uri: lsp::Url,
+        position: lsp::Position
+    },
+    #[serde(rename_all = "camelCase")]
+    Redefinition {
+        uri: lsp::Url,
+        position: lsp::Position,
+        redefining_content_uri: lsp::Url
+    }
+}
+
+impl Backend {
+    pub async fn code_lens_impl(&self, params: lsp::CodeLensParams) -> Result<Option<Vec<lsp::CodeLens>>> {
+        let doc_path = AbsPath::try_from(params.text_document.uri.clone()).unwrap();
+
+        if doc_path.extension().unwrap_or_default() != "ws" {
+            return Ok(None);
+        }
+
+        let content_info;
+        if let Some(ci) = self.scripts.get(&doc_path).and_then(|ss| ss.content_info.clone()) {
+            content_info = ci;
+        }
+        else {
+            return Ok(None);
+        }
+
+        let symtabs = self.symtabs.read().await;
+        let symtab;
+        if let Some(st) = symtabs.get(&content_info.content_path) {
+            symtab = st;
+        } else {
+            return Ok(None);
+        }
+
+        // functions can only be redefined with annotations in contents that depend on this one
+        let dependant_paths: Vec<AbsPath> =
+            self.content_graph
+            .read().await
+            .walk_dependants(&content_info.content_path)
+            .map(|n| n.content.path().to_owned())
+            .collect();
+
+        let uri = params.text_document.uri;
+        let mut lenses = Vec::new();
+        for symvar in symtab.get_symbols_for_source(content_info.source_tree_path.local()) {
+            let loc;
+            if let Some(l) = symvar.location() {
+                loc = l;
+            } else {
+                continue;
+            }
+
+            let position = loc.label_range.start;
+            let make_lens = |data: CodeLensData| lsp::CodeLens {
+                range: loc.label_range,
+                command: None,
+                data: serde_json::to_value(data).ok()
+            };
+
+            match symvar {
+                SymbolVariant::Class(_)
+                | SymbolVariant::State(_)
+                | SymbolVariant::GlobalFunc(_) => {
+                    lenses.push(make_lens(CodeLensData::References { uri: uri.clone(), position }));
+                },
+                SymbolVariant::MemberFunc(_)
+                | SymbolVariant::MemberFuncInjector(_)
+                | SymbolVariant::Event(_) => {
+                    lenses.push(make_lens(CodeLensData::References { uri: uri.clone(), position }));
+                    lenses.push(make_lens(CodeLensData::Overrides { uri: uri.clone(), position }));
+                },
+                _ => {}
+            }
+
+            if symvar.is_global_func() || symvar.is_global_func_replacer() || symvar.is_member_func()
+            || symvar.is_member_func_injector() || symvar.is_member_func_wrapper() || symvar.is_member_func_replacer() {
+                // checking whether a redefinition exists is cheap, so only lenses that will have something to show are created
+                for dependant_path in dependant_paths.iter() {
+                    let is_redefined = symtabs
+                        .get(dependant_path)
+                        .and_then(|st| st.get_symbol(symvar.path()))
+                        .map(|v| v.is_member_func_wrapper() || v.is_member_func_replacer() || v.is_global_func_replacer())
+                        .unwrap_or(false);
+
+                    if is_redefined {
+                        lenses.push(make_lens(CodeLensData::Redefinition {
+                            uri: uri.clone(),
+                            position,
+                            redefining_content_uri: dependant_path.to_uri()
+                        }));
+                    }
+                }
+            }
+        }
+
+        Ok(Some(lenses))
+    }
+
+    pub async fn code_lens_resolve_impl(&self, mut lens: lsp::CodeLens) -> Result<lsp::CodeLens> {
+        let data;
+        if let Some(d) = lens.data.clone().and_then(|d| serde_json::from_value::<CodeLensData>(d).ok()) {
+            data = d;
+        } else {
+            return Ok(lens);
+        }
+
+        let (uri, position) = match &data {
+            CodeLensData::References { uri, position } => (uri, *position),
+            CodeLensData::Overrides { uri, position } => (uri, *position),
+            CodeLensData::Redefinition { uri, position, .. } => (uri, *position)
+        };
+
+        let symtabs = self.symtabs.read().await;
+
+        let (content_path, sympath);
+        if let Some((p, s)) = self.resolve_hierarchy_item_target(&symtabs, uri, position).await {
+            content_path = p;
+            sympath = s;
+        } else {
+            return Ok(lens);
+        }
+
+        let (title, locations) = match &data {
+            CodeLensData::References { .. } => {
+                let origin_content_path = self.origin_content_path(&symtabs, &content_path, &sympath).await;
+                drop(symtabs);
+
+                let locations = self.find_references(&origin_content_path, &[sympath]).await;
+                let title = if locations.len() == 1 {
+                    "1 reference".to_string()
+                } else {
+                    format!("{} references", locations.len())
+                };
+
+                (title, locations)
+            },
+            CodeLensData::Overrides { .. } => {
+                let origin_content_path = self.origin_content_path(&symtabs, &content_path, &sympath).await;
+                let overrides = self.find_member_overrides(&symtabs, &origin_content_path, &sympath).await;
+
+                // overrides can only be declared in the content that declares the member or in contents that depend on it
+                let searched_contents: Vec<AbsPath> =
+                    [origin_content_path.clone()].into_iter()
+                    .chain(self.content_graph
+                        .read().await
+                        .walk_dependants(&origin_content_path)
+                        .map(|n| n.content.path().to_owned()))
+                    .collect();
+
+                let locations: Vec<lsp::Location> = overrides.iter()
+                    .filter_map(|path| searched_contents.iter()
+                        .filter_map(|p| symtabs.get(p))
+                        .find_map(|st| st.get_symbol(path).and_then(|v| v.location())))
+                    .map(|loc| lsp::Location::new(loc.abs_source_path().to_uri(), loc.label_range))
+                    .collect();
+
+                // overrides can be in classes as well as states
+                let title = if locations.len() == 1 {
+                    "1 override".to_string()
+                } else {
+                    format!("{} overrides", locations.len())
+                };
+
+                (title, locations)
+            },
+            CodeLensData::Redefinition { redefining_content_uri, .. } => {
+                let redefining_content_path;
+                if let Ok(p) = AbsPath::try_from(redefining_content_uri.clone()) {
+                    redefining_content_path = p;
+                } else {
+                    return Ok(lens);
+                }
+
+                let redefinition = symtabs
+                    .get(&redefining_content_path)
+                    .and_then(|st| st.get_symbol(&sympath));
+
+                let (action, annotation) = match redefinition {
+                    Some(SymbolVariant::MemberFuncWrapper(_)) => ("wrapped", AnnotationKind::WrapMethod),
+                    Some(SymbolVariant::MemberFuncReplacer(_)) | Some(SymbolVariant::GlobalFuncReplacer(_)) => ("replaced", AnnotationKind::ReplaceMethod),
+                    _ => return Ok(lens)
+                };
+
+                let content_name = self.content_graph
+                    .read().await
+                    .get_node_by_path(&redefining_content_path)
+                    .map(|n| n.content.content_name().to_string())
+                    .unwrap_or_default();
+
+                let locations = redefinition
+                    .and_then(|v| v.location())
+                    .map(|loc| lsp::Location::new(loc.abs_source_path().to_uri(), loc.label_range))
+                    .into_iter()
+                    .collect();
+
+                (format!("{} by {} ({})", action, content_name, annotation), locations)
+            }
+        };
+
+        lens.command = Some(lsp::Command {
+            title,
+            command: SHOW_REFERENCES_COMMAND.into(),
+            arguments: Some(vec![
+                serde_json::to_value(uri).unwrap(),
+                serde_json::to_value(position).unwrap(),
+                serde_json::to_value(locations).unwrap()
+            ])
+        });
+
+        Ok(lens)
+    }
+}
@@ -1,12 +1,13 @@
-use std::{cell::RefCell, rc::Rc};
+use std::{cell::RefCell, collections::HashSet, rc::Rc};
+use abs_path::AbsPath;
 use tower_lsp::lsp_types as lsp;
 use witcherscript::{ast::*, script_document::ScriptDocument, tokens::*};
 use witcherscript_analysis::symbol_analysis::symbol_table::marcher::SymbolTableMarcher;
 use witcherscript_analysis::symbol_analysis::symbols::*;
 use witcherscript_analysis::symbol_analysis::unqualified_name_lookup::*;
-use witcherscript_analysis::symbol_analysis::symbol_path::SymbolPathBuf;
+use witcherscript_analysis::symbol_analysis::symbol_path::{SymbolPath, SymbolPathBuf};
 use witcherscript_analysis::utils::*;
-use crate::ScriptState;
+use crate::{Backend, ScriptState, SymbolTables};
 
 
 #[derive(Debug, Clone)]
@@ -569,4 +570,136 @@ pub(super) fn resolve_text_document_position<'a>(position: lsp::Position, script
 
     let resolver_ref = resolver.borrow();
     resolver_ref.found_target.clone()
-}
\ No newline at end of file
+}
+
+
+
+/// Returns paths of a class or a state and all types it derives from, starting from the type itself.
+/// For states this also includes the classes that all states derive from by default.
+pub(super) fn type_hierarchy_paths(type_path: &SymbolPath, symtab_marcher: &SymbolTableMarcher) -> Vec<SymbolPathBuf> {
+    match symtab_marcher.get_symbol(type_path) {
+        Some(SymbolVariant::Class(_)) => {
+            symtab_marcher
+                .class_hierarchy(type_path)
+                .map(|class| class.path().to_owned())
+                .collect()
+        },
+        Some(SymbolVariant::State(_)) => {
+            let default_base_path = BasicTypeSymbolPath::new(StateSymbol::DEFAULT_STATE_BASE_NAME);
+
+            symtab_marcher
+                .state_hierarchy(type_path)
+                .map(|state| state.path().to_owned())
+                .chain(symtab_marcher
+                    .class_hierarchy(&default_base_path)
+                    .map(|class| class.path().to_owned()))
+                .collect()
+        },
+        _ => Vec::new()
+    }
+}
+
+impl Backend {
+    /// Returns the path of the closest content, starting from given content and then going through its dependencies,
+    /// that declares a symbol with given path. If no such content could be found, the starting content is returned.
+    pub(super) async fn declaring_content_path(&self, symtabs: &SymbolTables, content_path: &AbsPath, sympath: &SymbolPath) -> AbsPath {
+        let dependency_paths: Vec<_> =
+            self.content_graph
+            .read().await
+            .walk_dependencies(content_path)
+            .map(|n| n.content.path().to_owned())
+            .collect();
+
+        [content_path.to_owned()].into_iter()
+            .chain(dependency_paths)
+            .find(|p| symtabs.get(p).map(|symtab| symtab.contains_symbol(sympath)).unwrap_or(false))
+            .unwrap_or(content_path.to_owned())
+    }
+
+    /// Returns the path of the furthest content, starting from given content and then going through its dependencies,
+    /// that declares a symbol with given path. For functions redefined with annotations this is the content with the original declaration.
+    /// If no such content could be found, the starting content is returned.
+    pub(super) async fn origin_content_path(&self, symtabs: &SymbolTables, content_path: &AbsPath, sympath: &SymbolPath) -> AbsPath {
+        let dependency_paths: Vec<_> =
+            self.content_graph
+            .read().await
+            .walk_dependencies(content_path)
+            .map(|n| n.content.path().to_owned())
+            .collect();
+
+        [content_path.to_owned()].into_iter()
+            .chain(dependency_paths)
+            .rfind(|p| symtabs.get(p).map(|symtab| symtab.contains_symbol(sympath)).unwrap_or(false))
+            .unwrap_or(content_path.to_owned())
+    }
+
+    /// Returns the path of the symbol at given position in a script file together with the path of the content that the file belongs to.
+    /// Used to find out what symbol a call or type hierarchy item was created for.
+    pub(super) async fn resolve_hierarchy_item_target(&self, symtabs: &SymbolTables, uri: &lsp::Url, position: lsp::Position) -> Option<(AbsPath, SymbolPathBuf)> {
+        let doc_path = AbsPath::try_from(uri.clone()).ok()?;
+        let content_path = self.scripts.get(&doc_path).and_then(|ss| ss.content_info.as_ref().map(|ci| ci.content_path.to_owned()))?;
+        let symtabs_marcher = self.march_symbol_tables(symtabs, &content_path).await;
+
+        let script_state = self.scripts.get(&doc_path)?;
+        let sympath = resolve_text_document_position(position, &script_state, symtabs_marcher.clone())
+            .and_then(|t| t.target_symbol_path(&symtabs_marcher))?;
+
+        Some((content_path, sympath))
+    }
+
+    /// Finds paths of methods and events that override a given member of a class or a state.
+    /// This includes members of derived types and members of states that belong to derived classes.
+    /// Overrides are searched for in the content that declares the member and in all contents dependant on it.
+    pub(super) async fn find_member_overrides(&self, symtabs: &SymbolTables, decl_content_path: &AbsPath, member_path: &SymbolPath) -> Vec<SymbolPathBuf> {
+        let (parent_path, member_comp) = match (member_path.parent(), member_path.components().next_back()) {
+            (Some(parent_path), Some(member_comp)) => (parent_path, member_comp),
+            _ => return Vec::new()
+        };
+
+        let searched_contents: Vec<_> =
+            [decl_content_path.to_owned()].into_iter()
+            .chain(self.content_graph
+                .read().await
+                .walk_dependants(decl_content_path)
+                .map(|n| n.content.path().to_owned()))
+            .collect();
+
+        let mut overrides = Vec::new();
+        let mut visited = HashSet::new();
+        for content_path in searched_contents {
+            let symtab;
+            if let Some(st) = symtabs.get(&content_path) {
+                symtab = st;
+            } else {
+                continue;
+            }
+
+            let symtabs_marcher = self.march_symbol_tables(symtabs, &content_path).await;
+            for type_symvar in symtabs_marcher.get_root_symbols() {
+                let override_path = type_symvar.path().join_component(member_comp.name, member_comp.category);
+                if override_path.as_sympath() == member_path || !symtab.contains_symbol(&override_path) || visited.contains(&override_path) {
+                    continue;
+                }
+
+                let mut derives = type_hierarchy_paths(type_symvar.path(), &symtabs_marcher)
+                    .iter()
+                    .skip(1)
+                    .any(|p| p.as_sympath() == parent_path);
+
+                // state can override methods of the class it belongs to
+                if let Some(state) = type_symvar.try_as_state_ref() {
+                    derives = derives || type_hierarchy_paths(state.parent_class_path(), &symtabs_marcher)
+                        .iter()
+                        .any(|p| p.as_sympath() == parent_path);
+                }
+
+                if derives {
+                    visited.insert(override_path.clone());
+                    overrides.push(override_path);
+                }
+            }
+        }
+
+        overrides
+    }
+}
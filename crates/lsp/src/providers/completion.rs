@@ -0,0 +1,701 @@
+use std::{cell::RefCell, collections::HashSet, rc::Rc};
+use abs_path::AbsPath;
+use tower_lsp::lsp_types as lsp;
+use tower_lsp::jsonrpc::Result;
+use witcherscript::{ast::*, script_document::ScriptDocument, tokens::IdentifierNode};
+use witcherscript::attribs::*;
+use witcherscript_analysis::symbol_analysis::symbol_path::{SymbolPath, SymbolPathBuf};
+use witcherscript_analysis::symbol_analysis::symbol_table::marcher::SymbolTableMarcher;
+use witcherscript_analysis::symbol_analysis::symbols::*;
+use witcherscript_analysis::symbol_analysis::unqualified_name_lookup::*;
+use witcherscript_analysis::utils::*;
+use witcherscript_project::Manifest;
+use crate::{Backend, ScriptState};
+use super::hover::RenderTooltip;
+
+
+impl Backend {
+    pub async fn completion_impl(&self, params: lsp::CompletionParams) -> Result<Option<lsp::CompletionResponse>> {
+        let doc_path = AbsPath::try_from(params.text_document_position.text_document.uri.clone()).unwrap();
+
+        if doc_path.file_name().unwrap_or_default() == Manifest::FILE_NAME {
+            return self.manifest_completion(&doc_path, params.text_document_position.position).await;
+        }
+
+        if doc_path.extension().unwrap_or_default() != "ws" {
+            return Ok(None);
+        }
+
+        let content_path;
+        if let Some(path) = self.scripts.get(&doc_path).and_then(|ss| ss.content_info.as_ref().map(|ci| ci.content_path.to_owned())) {
+            content_path = path;
+        }
+        else {
+            return Ok(None);
+        }
+
+        let symtabs = self.symtabs.read().await;
+        let symtabs_marcher = self.march_symbol_tables(&symtabs, &content_path).await;
+
+        let script_state;
+        if let Some(ss) = self.scripts.get(&doc_path) {
+            script_state = ss;
+        } else {
+            return Ok(None);
+        }
+
+        let position = params.text_document_position.position;
+        let line_prefix = script_state.buffer.text_at(lsp::Range::new(lsp::Position::new(position.line, 0), position)).to_string();
+        let trigger = CompletionTrigger::deduce(&line_prefix, position);
+
+        let resolved = resolve_completion_context(position, &trigger, &script_state, symtabs_marcher.clone());
+        drop(script_state);
+
+        let items = match trigger {
+            CompletionTrigger::MemberAccess { .. } => {
+                resolved.accessor_type
+                    .map(|accessor_type| member_completion_items(&accessor_type, &resolved.sympath_ctx, &symtabs_marcher))
+                    .unwrap_or_default()
+            },
+            CompletionTrigger::TypeAnnotation => {
+                type_completion_items(&symtabs_marcher)
+            },
+            CompletionTrigger::Other => {
+                if resolved.in_type_annotation {
+                    type_completion_items(&symtabs_marcher)
+                } else if let Some(unl) = resolved.unl {
+                    statement_completion_items(&unl, &symtabs_marcher)
+                } else {
+                    Vec::new()
+                }
+            }
+        };
+
+        Ok(Some(lsp::CompletionResponse::Array(items)))
+    }
+}
+
+
+/// What kind of completion is expected judging by the text that precedes the cursor.
+/// Text is used instead of the syntax tree, because the latter is very likely
+/// to be erroneous while the user is still typing.
+#[derive(Debug, Clone)]
+enum CompletionTrigger {
+    /// Cursor is placed after a dot, `accessor_end` is the position right after the accessed expression
+    MemberAccess {
+        accessor_end: lsp::Position
+    },
+    /// Cursor is placed where a type name is expected, e.g. after a colon in a var declaration
+    TypeAnnotation,
+    Other
+}
+
+impl CompletionTrigger {
+    fn deduce(line_prefix: &str, position: lsp::Position) -> Self {
+        let chars: Vec<char> = line_prefix.chars().collect();
+
+        // skip the identifier that is currently being typed
+        let mut i = chars.len();
+        while i > 0 && is_ident_char(chars[i - 1]) {
+            i -= 1;
+        }
+        while i > 0 && chars[i - 1].is_whitespace() {
+            i -= 1;
+        }
+
+        if i == 0 {
+            return Self::Other;
+        }
+
+        match chars[i - 1] {
+            '.' => {
+                let mut j = i - 1;
+                while j > 0 && chars[j - 1].is_whitespace() {
+                    j -= 1;
+                }
+
+                if j == 0 {
+                    // accessor is on a previous line, which is not supported
+                    Self::Other
+                } else {
+                    Self::MemberAccess {
+                        accessor_end: lsp::Position::new(position.line, j as u32)
+                    }
+                }
+            },
+            ':' => {
+                let before: String = chars[..i - 1].iter().collect();
+                let before = before.trim_start();
+
+                // excluding ternary operator and switch case labels
+                if before.contains('?') || before.starts_with("case") || before.starts_with("default") {
+                    Self::Other
+                } else {
+                    Self::TypeAnnotation
+                }
+            },
+            '<' => {
+                if preceding_word(&chars[..i - 1]) == ArrayTypeSymbol::TYPE_NAME {
+                    Self::TypeAnnotation
+                } else {
+                    Self::Other
+                }
+            },
+            _ => {
+                match preceding_word(&chars[..i]).as_str() {
+                    "new" | "extends" => Self::TypeAnnotation,
+                    _ => Self::Other
+                }
+            }
+        }
+    }
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+fn preceding_word(chars: &[char]) -> String {
+    let end = chars.iter().rposition(|c| !c.is_whitespace()).map(|i| i + 1).unwrap_or(0);
+    let start = chars[..end].iter().rposition(|c| !is_ident_char(*c)).map(|i| i + 1).unwrap_or(0);
+    chars[start..end].iter().collect()
+}
+
+
+
+#[derive(Debug, Clone, Default)]
+struct ResolvedCompletionContext {
+    /// Path to the type of the expression that is being accessed
+    accessor_type: Option<SymbolPathBuf>,
+    in_type_annotation: bool,
+    /// Names accessible at the position, available if the position is inside a function body
+    unl: Option<UnqualifiedNameLookup>,
+    sympath_ctx: SymbolPathBuf
+}
+
+/// A node visitor that gathers information needed to provide code completion at a given position.
+/// Expects to work after PositionFilter and SymbolPathBuilder, but before UnqualifiedNameLookupBuilder in visitor chain.
+struct CompletionResolver<'a> {
+    pos: lsp::Position,
+    accessor_end: Option<lsp::Position>,
+    doc: &'a ScriptDocument,
+    symtab_marcher: SymbolTableMarcher<'a>,
+    sympath_builder_payload: Rc<RefCell<SymbolPathBuilderPayload>>,
+    unl_builder_payload: Rc<RefCell<UnqualifiedNameLookup>>,
+    in_callable_body: bool,
+    resolved: ResolvedCompletionContext
+}
+
+impl<'a> CompletionResolver<'a> {
+    fn new_rc(
+        pos: lsp::Position,
+        accessor_end: Option<lsp::Position>,
+        doc: &'a ScriptDocument,
+        symtab_marcher: SymbolTableMarcher<'a>,
+        sympath_builder_payload: Rc<RefCell<SymbolPathBuilderPayload>>,
+        unl_builder_payload: Rc<RefCell<UnqualifiedNameLookup>>
+    ) -> Rc<RefCell<Self>> {
+        Rc::new(RefCell::new(Self {
+            pos,
+            accessor_end,
+            doc,
+            symtab_marcher,
+            sympath_builder_payload,
+            unl_builder_payload,
+            in_callable_body: false,
+            resolved: ResolvedCompletionContext::default()
+        }))
+    }
+
+
+    fn enter_callable(&mut self, definition: FunctionDefinitionNode) {
+        if definition.spans_position(self.pos) {
+            self.in_callable_body = true;
+            self.resolved.sympath_ctx = self.sympath_builder_payload.borrow().current_sympath.clone();
+        }
+    }
+
+    fn exit_callable(&mut self) {
+        if self.in_callable_body {
+            self.snapshot_unl();
+            self.in_callable_body = false;
+        }
+    }
+
+    /// Saves names accessible at the position.
+    /// Called at the first local var declaration that is past the position or at the end of the function.
+    fn snapshot_unl(&mut self) {
+        if self.in_callable_body && self.resolved.unl.is_none() {
+            self.resolved.unl = Some(self.unl_builder_payload.borrow().clone());
+        }
+    }
+
+    fn check_type_annotation(&mut self, n: &TypeAnnotationNode) {
+        if n.spans_position(self.pos) {
+            self.resolved.in_type_annotation = true;
+        }
+    }
+
+    fn check_accessor(&mut self, n: ExpressionNode, ctx: &TraversalContextStack) {
+        let is_accessor = self.accessor_end
+            .map(|accessor_end| n.range().end == accessor_end)
+            .unwrap_or(false);
+
+        // the outermost expression gets visited first, so it's the one that should be evaluated
+        if is_accessor && self.resolved.accessor_type.is_none() {
+            let expr_path = evaluate_expression(
+                n, ctx.top(),
+                self.doc,
+                self.symtab_marcher.clone(),
+                self.sympath_builder_payload.clone(),
+                self.unl_builder_payload.clone()
+            );
+
+            self.resolved.accessor_type = Some(produce_type(&expr_path, &self.symtab_marcher));
+            self.resolved.sympath_ctx = self.sympath_builder_payload.borrow().current_sympath.clone();
+        }
+    }
+}
+
+impl SyntaxNodeVisitor for CompletionResolver<'_> {
+    fn traversal_policy_default(&self) -> bool {
+        true
+    }
+
+
+    fn visit_global_var_decl(&mut self, n: &MemberVarDeclarationNode) {
+        self.check_type_annotation(&n.var_type());
+    }
+
+    fn visit_member_var_decl(&mut self, n: &MemberVarDeclarationNode, _: &TraversalContextStack) {
+        self.check_type_annotation(&n.var_type());
+    }
+
+    fn visit_autobind_decl(&mut self, n: &AutobindDeclarationNode, _: &TraversalContextStack) {
+        self.check_type_annotation(&n.autobind_type());
+    }
+
+    fn visit_func_param_group(&mut self, n: &FunctionParameterGroupNode, _: &TraversalContextStack) {
+        self.check_type_annotation(&n.param_type());
+    }
+
+
+    fn visit_global_func_decl(&mut self, n: &FunctionDeclarationNode) -> FunctionDeclarationTraversalPolicy {
+        if let Some(rt) = n.return_type() {
+            self.check_type_annotation(&rt);
+        }
+        self.enter_callable(n.definition());
+
+        TraversalPolicy::default_to(true)
+    }
+
+    fn exit_global_func_decl(&mut self, _: &FunctionDeclarationNode) {
+        self.exit_callable();
+    }
+
+    fn visit_member_func_decl(&mut self, n: &FunctionDeclarationNode, _: &TraversalContextStack) -> FunctionDeclarationTraversalPolicy {
+        if let Some(rt) = n.return_type() {
+            self.check_type_annotation(&rt);
+        }
+        self.enter_callable(n.definition());
+
+        TraversalPolicy::default_to(true)
+    }
+
+    fn exit_member_func_decl(&mut self, _: &FunctionDeclarationNode, _: &TraversalContextStack) {
+        self.exit_callable();
+    }
+
+    fn visit_event_decl(&mut self, n: &EventDeclarationNode, _: &TraversalContextStack) -> EventDeclarationTraversalPolicy {
+        if let Some(rt) = n.return_type() {
+            self.check_type_annotation(&rt);
+        }
+        self.enter_callable(n.definition());
+
+        TraversalPolicy::default_to(true)
+    }
+
+    fn exit_event_decl(&mut self, _: &EventDeclarationNode, _: &TraversalContextStack) {
+        self.exit_callable();
+    }
+
+
+    fn visit_local_var_decl_stmt(&mut self, n: &LocalVarDeclarationNode, _: &TraversalContextStack) -> VarDeclarationTraversalPolicy {
+        let r = n.range();
+        if r.start.line > self.pos.line || (r.start.line == self.pos.line && r.start.character >= self.pos.character) {
+            self.snapshot_unl();
+        }
+
+        self.check_type_annotation(&n.var_type());
+
+        TraversalPolicy::default_to(true)
+    }
+
+
+    fn visit_nested_expr(&mut self, n: &NestedExpressionNode, ctx: &TraversalContextStack) -> NestedExpressionTraversalPolicy {
+        self.check_accessor(n.clone().into(), ctx);
+        TraversalPolicy::default_to(true)
+    }
+
+    fn visit_this_expr(&mut self, n: &ThisExpressionNode, ctx: &TraversalContextStack) {
+        self.check_accessor(n.clone().into(), ctx);
+    }
+
+    fn visit_super_expr(&mut self, n: &SuperExpressionNode, ctx: &TraversalContextStack) {
+        self.check_accessor(n.clone().into(), ctx);
+    }
+
+    fn visit_parent_expr(&mut self, n: &ParentExpressionNode, ctx: &TraversalContextStack) {
+        self.check_accessor(n.clone().into(), ctx);
+    }
+
+    fn visit_virtual_parent_expr(&mut self, n: &VirtualParentExpressionNode, ctx: &TraversalContextStack) {
+        self.check_accessor(n.clone().into(), ctx);
+    }
+
+    fn visit_identifier_expr(&mut self, n: &IdentifierNode, ctx: &TraversalContextStack) {
+        self.check_accessor(n.clone().into(), ctx);
+    }
+
+    fn visit_func_call_expr(&mut self, n: &FunctionCallExpressionNode, ctx: &TraversalContextStack) -> FunctionCallExpressionTraversalPolicy {
+        self.check_accessor(n.clone().into(), ctx);
+        TraversalPolicy::default_to(true)
+    }
+
+    fn visit_array_expr(&mut self, n: &ArrayExpressionNode, ctx: &TraversalContextStack) -> ArrayExpressionTraversalPolicy {
+        self.check_accessor(n.clone().into(), ctx);
+        TraversalPolicy::default_to(true)
+    }
+
+    fn visit_member_access_expr(&mut self, n: &MemberAccessExpressionNode, ctx: &TraversalContextStack) -> MemberFieldExpressionTraversalPolicy {
+        self.check_accessor(n.clone().into(), ctx);
+        TraversalPolicy::default_to(true)
+    }
+
+    fn visit_type_cast_expr(&mut self, n: &TypeCastExpressionNode, _: &TraversalContextStack) -> TypeCastExpressionTraversalPolicy {
+        if n.target_type().spans_position(self.pos) {
+            self.resolved.in_type_annotation = true;
+        }
+
+        TraversalPolicy::default_to(true)
+    }
+
+    fn visit_new_expr(&mut self, n: &NewExpressionNode, _: &TraversalContextStack) -> NewExpressionTraversalPolicy {
+        if n.class().spans_position(self.pos) {
+            self.resolved.in_type_annotation = true;
+        }
+
+        TraversalPolicy::default_to(true)
+    }
+}
+
+impl SyntaxNodeVisitorChainLink for CompletionResolver<'_> {}
+
+
+fn resolve_completion_context<'a>(position: lsp::Position, trigger: &CompletionTrigger, script_state: &'a ScriptState, symtab_marcher: SymbolTableMarcher<'a>) -> ResolvedCompletionContext {
+    let accessor_end = if let CompletionTrigger::MemberAccess { accessor_end } = trigger {
+        Some(*accessor_end)
+    } else {
+        None
+    };
+
+    let (mut pos_filter, _) = PositionFilter::new(position);
+    pos_filter.filter_statements = false;
+
+    let (sympath_builder, sympath_builder_payload) = SymbolPathBuilder::new(&script_state.buffer);
+    let (unl_builder, unl_payload) = UnqualifiedNameLookupBuilder::new(&script_state.buffer, sympath_builder_payload.clone(), symtab_marcher.clone());
+    let resolver = CompletionResolver::new_rc(
+        position,
+        accessor_end,
+        &script_state.buffer,
+        symtab_marcher.clone(),
+        sympath_builder_payload.clone(),
+        unl_payload.clone()
+    );
+
+    let mut chain = SyntaxNodeVisitorChain::new()
+        .link(pos_filter)
+        .link(sympath_builder)
+        .link_rc(resolver.clone())
+        .link(unl_builder);
+
+    script_state.script.visit_nodes(&mut chain);
+
+    let mut resolved = resolver.borrow().resolved.clone();
+
+    // the expression may not be a part of the syntax tree if the code is erroneous,
+    // in that case try to make sense of a simple accessor expression from the text alone
+    if resolved.accessor_type.is_none() {
+        if let (Some(accessor_end), Some(unl)) = (accessor_end, resolved.unl.as_ref()) {
+            let accessor_text = script_state.buffer.text_at(lsp::Range::new(lsp::Position::new(accessor_end.line, 0), accessor_end)).to_string();
+            let accessor_chars: Vec<char> = accessor_text.chars().collect();
+            let accessor_name = preceding_word(&accessor_chars);
+            let accessor_start = accessor_chars.len() - accessor_name.chars().count();
+            let is_simple = accessor_chars[..accessor_start].last() != Some(&'.');
+
+            if !accessor_name.is_empty() && is_simple {
+                let ctx_root = resolved.sympath_ctx.root().unwrap_or_default().to_owned();
+                let expr_path: Option<SymbolPathBuf> = match accessor_name.as_str() {
+                    "this" => Some(ThisVarSymbolPath::new(&ctx_root).into()),
+                    "super" => Some(SuperVarSymbolPath::new(&ctx_root).into()),
+                    "parent" => Some(ParentVarSymbolPath::new(&ctx_root).into()),
+                    "virtual_parent" => Some(VirtualParentVarSymbolPath::new(&ctx_root).into()),
+                    name => {
+                        unl.get(name, SymbolCategory::Data)
+                            .map(|p| p.to_owned())
+                            .or_else(|| {
+                                let global_path: SymbolPathBuf = GlobalDataSymbolPath::new(name).into();
+                                symtab_marcher.contains_symbol(&global_path).then_some(global_path)
+                            })
+                    }
+                };
+
+                resolved.accessor_type = expr_path.map(|p| produce_type(&p, &symtab_marcher));
+            }
+        }
+    }
+
+    resolved
+}
+
+
+
+fn member_completion_items(type_path: &SymbolPath, sympath_ctx: &SymbolPath, marcher: &SymbolTableMarcher<'_>) -> Vec<lsp::CompletionItem> {
+    let owner_types = type_hierarchy(type_path, marcher);
+    let ctx_types = sympath_ctx.root()
+        .map(|ctx_root| type_hierarchy(ctx_root, marcher))
+        .unwrap_or_default();
+
+    let mut visited_names = HashSet::new();
+    let mut items = Vec::new();
+    for (i, owner) in owner_types.iter().enumerate() {
+        for ch in marcher.get_symbol_children(owner) {
+            let accessible = match member_access_modifier(ch) {
+                Some(AccessModifier::Private) => ctx_types.first() == Some(owner),
+                Some(AccessModifier::Protected) => ctx_types.contains(owner),
+                _ => true
+            };
+
+            let is_special = matches!(ch,
+                SymbolVariant::ThisVar(_)
+                | SymbolVariant::SuperVar(_)
+                | SymbolVariant::StateSuperVar(_)
+                | SymbolVariant::ParentVar(_)
+                | SymbolVariant::VirtualParentVar(_)
+            );
+
+            if accessible && !is_special && visited_names.insert(ch.name().to_string()) {
+                let mut item = completion_item(ch, marcher);
+                // members of the most derived types should come first
+                item.sort_text = Some(format!("{:02}_{}", i, ch.name()));
+                items.push(item);
+            }
+        }
+    }
+
+    items
+}
+
+/// Paths of all types, which members are accessible through a given type, starting from the most derived one
+fn type_hierarchy(type_path: &SymbolPath, marcher: &SymbolTableMarcher<'_>) -> Vec<SymbolPathBuf> {
+    let mut paths = Vec::new();
+    match marcher.get_symbol(type_path) {
+        Some(SymbolVariant::Class(_)) => {
+            paths.extend(marcher.class_hierarchy(type_path).map(|class| class.path().to_owned()));
+        },
+        Some(SymbolVariant::State(state)) => {
+            paths.extend(marcher.state_hierarchy(type_path).map(|state| state.path().to_owned()));
+
+            let state_base_path = BasicTypeSymbolPath::new(StateSymbol::DEFAULT_STATE_BASE_NAME);
+            paths.extend(marcher.class_hierarchy(&state_base_path).map(|class| class.path().to_owned()));
+
+            // states have access to their parent class' members
+            paths.extend(marcher.class_hierarchy(state.parent_class_path()).map(|class| class.path().to_owned()));
+        },
+        Some(_) => {
+            paths.push(type_path.to_owned());
+        },
+        None => {}
+    }
+
+    paths
+}
+
+fn member_access_modifier(symvar: &SymbolVariant) -> Option<AccessModifier> {
+    let has = |private: bool, protected: bool| {
+        if private {
+            Some(AccessModifier::Private)
+        } else if protected {
+            Some(AccessModifier::Protected)
+        } else {
+            None
+        }
+    };
+
+    match symvar {
+        SymbolVariant::MemberFunc(s) => has(
+            s.specifiers.contains(AccessModifier::Private.into()),
+            s.specifiers.contains(AccessModifier::Protected.into())
+        ),
+        SymbolVariant::MemberVar(s) => has(
+            s.specifiers.contains(AccessModifier::Private.into()),
+            s.specifiers.contains(AccessModifier::Protected.into())
+        ),
+        SymbolVariant::Autobind(s) => has(
+            s.specifiers.contains(AccessModifier::Private.into()),
+            s.specifiers.contains(AccessModifier::Protected.into())
+        ),
+        _ => None
+    }
+}
+
+fn type_completion_items(marcher: &SymbolTableMarcher<'_>) -> Vec<lsp::CompletionItem> {
+    let mut items: Vec<_> = marcher.get_root_symbols()
+        .filter(|symvar| matches!(symvar,
+            SymbolVariant::Class(_)
+            | SymbolVariant::Struct(_)
+            | SymbolVariant::Enum(_)
+            | SymbolVariant::Primitive(_)
+        ))
+        .map(|symvar| completion_item(symvar, marcher))
+        .collect();
+
+    items.push(lsp::CompletionItem {
+        label: ArrayTypeSymbol::TYPE_NAME.to_string(),
+        kind: Some(lsp::CompletionItemKind::CLASS),
+        detail: Some("array<T>".to_string()),
+        ..Default::default()
+    });
+
+    items
+}
+
+fn statement_completion_items(unl: &UnqualifiedNameLookup, marcher: &SymbolTableMarcher<'_>) -> Vec<lsp::CompletionItem> {
+    let mut visited_names = HashSet::new();
+    let mut items = Vec::new();
+    for path in unl.iter() {
+        if let Some(symvar) = marcher.get_symbol(path) {
+            if visited_names.insert(symvar.name().to_string()) {
+                items.push(completion_item(symvar, marcher));
+            }
+        }
+    }
+
+    let globals = marcher.get_root_symbols()
+        .filter(|symvar| matches!(symvar,
+            SymbolVariant::GlobalFunc(_)
+            | SymbolVariant::GlobalFuncReplacer(_)
+            | SymbolVariant::GlobalVar(_)
+            | SymbolVariant::EnumVariant(_)
+            | SymbolVariant::Constructor(_)
+        ));
+
+    for symvar in globals {
+        if visited_names.insert(symvar.name().to_string()) {
+            items.push(completion_item(symvar, marcher));
+        }
+    }
+
+    items
+}
+
+fn completion_item(symvar: &SymbolVariant, marcher: &SymbolTableMarcher<'_>) -> lsp::CompletionItem {
+    let detail = marcher.find_table_with_symbol(symvar).map(|symtab| {
+        let mut buf = String::new();
+        symvar.render(&mut buf, symtab, marcher);
+        buf
+    });
+
+    lsp::CompletionItem {
+        label: symvar.name().to_string(),
+        kind: Some(completion_item_kind(symvar)),
+        detail,
+        documentation: symvar.doc_comment().map(|doc| lsp::Documentation::MarkupContent(lsp::MarkupContent {
+            kind: lsp::MarkupKind::Markdown,
+            value: doc.to_string()
+        })),
+        ..Default::default()
+    }
+}
+
+fn completion_item_kind(symvar: &SymbolVariant) -> lsp::CompletionItemKind {
+    match symvar {
+        SymbolVariant::Class(_) => lsp::CompletionItemKind::CLASS,
+        SymbolVariant::State(_) => lsp::CompletionItemKind::CLASS,
+        SymbolVariant::Struct(_) => lsp::CompletionItemKind::STRUCT,
+        SymbolVariant::Enum(_) => lsp::CompletionItemKind::ENUM,
+        SymbolVariant::Array(_) => lsp::CompletionItemKind::CLASS,
+        SymbolVariant::ArrayFunc(_) => lsp::CompletionItemKind::METHOD,
+        SymbolVariant::ArrayFuncParam(_) => lsp::CompletionItemKind::VARIABLE,
+        SymbolVariant::GlobalFunc(_) => lsp::CompletionItemKind::FUNCTION,
+        SymbolVariant::MemberFunc(_) => lsp::CompletionItemKind::METHOD,
+        SymbolVariant::Event(_) => lsp::CompletionItemKind::EVENT,
+        SymbolVariant::Constructor(_) => lsp::CompletionItemKind::CONSTRUCTOR,
+        SymbolVariant::MemberFuncInjector(_) => lsp::CompletionItemKind::METHOD,
+        SymbolVariant::MemberFuncReplacer(_) => lsp::CompletionItemKind::METHOD,
+        SymbolVariant::GlobalFuncReplacer(_) => lsp::CompletionItemKind::FUNCTION,
+        SymbolVariant::MemberFuncWrapper(_) => lsp::CompletionItemKind::METHOD,
+        SymbolVariant::WrappedMethod(_) => lsp::CompletionItemKind::METHOD,
+        SymbolVariant::Primitive(_) => lsp::CompletionItemKind::KEYWORD,
+        SymbolVariant::EnumVariant(_) => lsp::CompletionItemKind::ENUM_MEMBER,
+        SymbolVariant::FuncParam(_) => lsp::CompletionItemKind::VARIABLE,
+        SymbolVariant::GlobalVar(_) => lsp::CompletionItemKind::VARIABLE,
+        SymbolVariant::MemberVar(_) => lsp::CompletionItemKind::FIELD,
+        SymbolVariant::Autobind(_) => lsp::CompletionItemKind::FIELD,
+        SymbolVariant::LocalVar(_) => lsp::CompletionItemKind::VARIABLE,
+        SymbolVariant::ThisVar(_) => lsp::CompletionItemKind::KEYWORD,
+        SymbolVariant::SuperVar(_) => lsp::CompletionItemKind::KEYWORD,
+        SymbolVariant::StateSuperVar(_) => lsp::CompletionItemKind::KEYWORD,
+        SymbolVariant::ParentVar(_) => lsp::CompletionItemKind::KEYWORD,
+        SymbolVariant::VirtualParentVar(_) => lsp::CompletionItemKind::KEYWORD,
+        SymbolVariant::MemberVarInjector(_) => lsp::CompletionItemKind::FIELD,
+    }
+}
+
+
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+
+    fn deduce(line_prefix: &str) -> CompletionTrigger {
+        CompletionTrigger::deduce(line_prefix, lsp::Position::new(3, line_prefix.chars().count() as u32))
+    }
+
+    #[test]
+    fn test_member_access_trigger() {
+        assert!(matches!(deduce("    player."), CompletionTrigger::MemberAccess { accessor_end } if accessor_end == lsp::Position::new(3, 10)));
+        assert!(matches!(deduce("    player.Get"), CompletionTrigger::MemberAccess { accessor_end } if accessor_end == lsp::Position::new(3, 10)));
+        assert!(matches!(deduce("    player . Get"), CompletionTrigger::MemberAccess { accessor_end } if accessor_end == lsp::Position::new(3, 10)));
+        assert!(matches!(deduce("    GetPlayer()."), CompletionTrigger::MemberAccess { accessor_end } if accessor_end == lsp::Position::new(3, 15)));
+
+        // accessor on a previous line
+        assert!(matches!(deduce("    .Get"), CompletionTrigger::Other));
+    }
+
+    #[test]
+    fn test_type_annotation_trigger() {
+        assert!(matches!(deduce("    var player : "), CompletionTrigger::TypeAnnotation));
+        assert!(matches!(deduce("    var player: CR4"), CompletionTrigger::TypeAnnotation));
+        assert!(matches!(deduce("function Foo(a : int) : "), CompletionTrigger::TypeAnnotation));
+        assert!(matches!(deduce("    var arr : array<"), CompletionTrigger::TypeAnnotation));
+        assert!(matches!(deduce("    var arr : array< CName"), CompletionTrigger::TypeAnnotation));
+        assert!(matches!(deduce("    obj = new "), CompletionTrigger::TypeAnnotation));
+        assert!(matches!(deduce("    obj = new CMy"), CompletionTrigger::TypeAnnotation));
+        assert!(matches!(deduce("class CFoo extends CB"), CompletionTrigger::TypeAnnotation));
+    }
+
+    #[test]
+    fn test_other_trigger() {
+        assert!(matches!(deduce(""), CompletionTrigger::Other));
+        assert!(matches!(deduce("    "), CompletionTrigger::Other));
+        assert!(matches!(deduce("    player"), CompletionTrigger::Other));
+        assert!(matches!(deduce("    x = a > b ? a : "), CompletionTrigger::Other));
+        assert!(matches!(deduce("        case 1: "), CompletionTrigger::Other));
+        assert!(matches!(deduce("        default: Log"), CompletionTrigger::Other));
+        assert!(matches!(deduce("    if (a < "), CompletionTrigger::Other));
+        assert!(matches!(deduce("    renew "), CompletionTrigger::Other));
+    }
+}
@@ -9,8 +9,11 @@ impl Backend {
         if diff.game_directory_changed || diff.content_repositories_changed {
             self.setup_repository_content_scanners().await;
             self.build_content_graph(true).await;
+            self.register_file_watchers().await;
             
             self.reporter.commit_all_diagnostics().await;
+        } else if diff.enable_workspace_analysis_changed {
+            self.start_workspace_analysis().await;
         }
     }
 }
\ No newline at end of file
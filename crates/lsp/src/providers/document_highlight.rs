@@ -0,0 +1,230 @@
+use std::{cell::RefCell, rc::Rc};
+use abs_path::AbsPath;
+use tower_lsp::lsp_types as lsp;
+use tower_lsp::jsonrpc::Result;
+use witcherscript::{ast::*, script_document::ScriptDocument};
+use witcherscript_analysis::symbol_analysis::symbol_path::{SymbolPath, SymbolPathBuf};
+use witcherscript_analysis::symbol_analysis::symbol_table::marcher::SymbolTableMarcher;
+use witcherscript_analysis::symbol_analysis::symbols::*;
+use witcherscript_analysis::symbol_analysis::unqualified_name_lookup::*;
+use witcherscript_analysis::jobs::{ReferenceScannerVisitor, ReferenceSink};
+use witcherscript_analysis::utils::*;
+use crate::Backend;
+use super::common::resolve_text_document_position;
+
+
+impl Backend {
+    pub async fn document_highlight_impl(&self, params: lsp::DocumentHighlightParams) -> Result<Option<Vec<lsp::DocumentHighlight>>> {
+        let doc_path = AbsPath::try_from(params.text_document_position_params.text_document.uri.clone()).unwrap();
+
+        if doc_path.extension().unwrap_or_default() != "ws" {
+            return Ok(None);
+        }
+
+        let content_path;
+        if let Some(path) = self.scripts.get(&doc_path).and_then(|ss| ss.content_info.as_ref().map(|ci| ci.content_path.to_owned())) {
+            content_path = path;
+        }
+        else {
+            return Ok(None);
+        }
+
+        let symtabs = self.symtabs.read().await;
+        let symtabs_marcher = self.march_symbol_tables(&symtabs, &content_path).await;
+
+        let script_state;
+        if let Some(ss) = self.scripts.get(&doc_path) {
+            script_state = ss;
+        } else {
+            return Ok(None);
+        }
+
+        let position_target = resolve_text_document_position(params.text_document_position_params.position, &script_state, symtabs_marcher.clone());
+
+        let sympath;
+        if let Some(path) = position_target.and_then(|t| t.target_symbol_path(&symtabs_marcher)) {
+            sympath = path;
+        } else {
+            return Ok(None);
+        }
+
+        let symvar;
+        if let Some(v) = symtabs_marcher.get_symbol(&sympath) {
+            symvar = v;
+        } else {
+            return Ok(None);
+        }
+
+        // struct can also be referenced through its constructor
+        let mut searched_paths: Vec<SymbolPathBuf> = vec![sympath.clone()];
+        if symvar.is_struct() {
+            searched_paths.push(GlobalCallableSymbolPath::new(symvar.name()).into());
+        }
+
+        let mut highlights = Vec::new();
+        if let Some(loc) = symvar.location().filter(|loc| loc.abs_source_path() == doc_path) {
+            highlights.push(lsp::DocumentHighlight {
+                range: loc.label_range,
+                kind: Some(lsp::DocumentHighlightKind::TEXT)
+            });
+        }
+
+        let (sympath_builder, sympath_builder_payload) = SymbolPathBuilder::new(&script_state.buffer);
+        let (unl_builder, unl_payload) = UnqualifiedNameLookupBuilder::new(&script_state.buffer, sympath_builder_payload.clone(), symtabs_marcher.clone());
+        let (write_marker, write_ranges) = WriteMarker::new(
+            &script_state.buffer,
+            symtabs_marcher.clone(),
+            sympath_builder_payload.clone(),
+            unl_payload.clone()
+        );
+        let mut sink = DocumentHighlightSink {
+            searched_paths,
+            write_ranges,
+            highlights
+        };
+        let scanner = ReferenceScannerVisitor::new(
+            &mut sink,
+            &script_state.buffer,
+            symtabs_marcher.clone(),
+            sympath_builder_payload,
+            unl_payload
+        );
+
+        // write marker has to come before the scanner to know about the write by the time the identifier is visited
+        let mut chain = SyntaxNodeVisitorChain::new()
+            .link(sympath_builder)
+            .link(unl_builder)
+            .link(write_marker)
+            .link(scanner);
+
+        script_state.script.visit_nodes(&mut chain);
+        drop(chain);
+
+        Ok(Some(sink.highlights))
+    }
+}
+
+
+/// Turns references to any of the searched symbols into highlights
+struct DocumentHighlightSink {
+    searched_paths: Vec<SymbolPathBuf>,
+    write_ranges: Rc<RefCell<Vec<lsp::Range>>>,
+    highlights: Vec<lsp::DocumentHighlight>
+}
+
+impl ReferenceSink for DocumentHighlightSink {
+    fn insert_reference(&mut self, sympath: &SymbolPath, range: lsp::Range) {
+        if self.searched_paths.iter().any(|p| p.as_sympath() == sympath) {
+            let kind = if self.write_ranges.borrow().contains(&range) {
+                lsp::DocumentHighlightKind::WRITE
+            } else {
+                lsp::DocumentHighlightKind::READ
+            };
+
+            self.highlights.push(lsp::DocumentHighlight {
+                range,
+                kind: Some(kind)
+            });
+        }
+    }
+}
+
+
+/// A node visitor that collects ranges of identifiers that are written to,
+/// i.e. places where something is assigned to or passed as an `out` argument.
+/// Assignments and calls are visited before their operands, so these get filled before said identifiers are visited.
+/// Expects to work after SymbolPathBuilder and UnqualifiedNameLookupBuilder in visitor chain.
+struct WriteMarker<'a> {
+    doc: &'a ScriptDocument,
+    symtab_marcher: SymbolTableMarcher<'a>,
+    sympath_builder_payload: Rc<RefCell<SymbolPathBuilderPayload>>,
+    unl_builder_payload: Rc<RefCell<UnqualifiedNameLookup>>,
+    write_ranges: Rc<RefCell<Vec<lsp::Range>>>
+}
+
+impl<'a> WriteMarker<'a> {
+    fn new(
+        doc: &'a ScriptDocument,
+        symtab_marcher: SymbolTableMarcher<'a>,
+        sympath_builder_payload: Rc<RefCell<SymbolPathBuilderPayload>>,
+        unl_builder_payload: Rc<RefCell<UnqualifiedNameLookup>>
+    ) -> (Self, Rc<RefCell<Vec<lsp::Range>>>) {
+        let write_ranges = Rc::new(RefCell::new(Vec::new()));
+
+        let marker = Self {
+            doc,
+            symtab_marcher,
+            sympath_builder_payload,
+            unl_builder_payload,
+            write_ranges: write_ranges.clone()
+        };
+
+        (marker, write_ranges)
+    }
+
+    /// Marks the identifier that given expression ultimately writes to, if there is one
+    fn mark_written(&mut self, expr: ExpressionNode) {
+        match expr.value() {
+            Expression::Identifier(n) => self.write_ranges.borrow_mut().push(n.range()),
+            Expression::MemberAccess(n) => self.write_ranges.borrow_mut().push(n.member().range()),
+            Expression::Nested(n) => self.mark_written(n.inner()),
+            _ => {}
+        }
+    }
+
+    /// Returns whether parameters of the called function are `out` parameters, ordered by their ordinal
+    fn callee_out_params(&self, n: &FunctionCallExpressionNode) -> Vec<bool> {
+        let callee_path = evaluate_expression(
+            n.func(), TraversalContext::FunctionCallExpressionFunc,
+            self.doc,
+            self.symtab_marcher.clone(),
+            self.sympath_builder_payload.clone(),
+            self.unl_builder_payload.clone()
+        );
+
+        resolve_callee(&callee_path, &self.symtab_marcher)
+            .map(|callee| callee.params.iter().map(|param| param.is_out()).collect())
+            .unwrap_or_default()
+    }
+}
+
+impl SyntaxNodeVisitor for WriteMarker<'_> {
+    fn traversal_policy_default(&self) -> bool {
+        true
+    }
+
+
+    fn visit_member_default_val(&mut self, n: &MemberDefaultValueNode, _: &TraversalContextStack) -> MemberDefaultValueTraversalPolicy {
+        self.write_ranges.borrow_mut().push(n.member().range());
+
+        TraversalPolicy::default_to(true)
+    }
+
+    fn visit_member_defaults_block_assignment(&mut self, n: &MemberDefaultsBlockAssignmentNode, _: &TraversalContextStack) -> MemberDefaultValueTraversalPolicy {
+        self.write_ranges.borrow_mut().push(n.member().range());
+
+        TraversalPolicy::default_to(true)
+    }
+
+    fn visit_assign_op_expr(&mut self, n: &AssignmentOperationExpressionNode, _: &TraversalContextStack) -> AssignmentOperationExpressionTraversalPolicy {
+        self.mark_written(n.left());
+
+        TraversalPolicy::default_to(true)
+    }
+
+    fn visit_func_call_expr(&mut self, n: &FunctionCallExpressionNode, _: &TraversalContextStack) -> FunctionCallExpressionTraversalPolicy {
+        let args = n.args().map(|args| args.iter().collect::<Vec<_>>()).unwrap_or_default();
+        if !args.is_empty() {
+            let out_params = self.callee_out_params(n);
+            for (arg, is_out) in args.into_iter().zip(out_params) {
+                if let (FunctionCallArgument::Some(expr), true) = (arg, is_out) {
+                    self.mark_written(expr);
+                }
+            }
+        }
+
+        TraversalPolicy::default_to(true)
+    }
+}
+
+impl SyntaxNodeVisitorChainLink for WriteMarker<'_> {}
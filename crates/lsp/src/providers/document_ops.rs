@@ -1,16 +1,19 @@
 use std::collections::HashSet;
+use std::str::FromStr;
 use filetime::FileTime;
 use tower_lsp::lsp_types as lsp;
 use abs_path::AbsPath;
 use witcherscript::{script_document::ScriptDocument, Script};
 use witcherscript_project::{redkit::RedkitManifest, Manifest};
-use crate::{Backend, ScriptState};
+use crate::{Backend, ManifestState, ScriptState};
 
 
 impl Backend {
     pub async fn did_open_impl(&self, params: lsp::DidOpenTextDocumentParams) {
         let doc_path = AbsPath::try_from(params.text_document.uri.clone()).unwrap();
         if params.text_document.language_id == Backend::LANGUAGE_ID {
+            self.open_scripts.insert(doc_path.clone());
+
             if !self.scripts.contains_key(&doc_path) {
                 // Scripts that are not a part of a workspace projects or their dependencies
                 // are not included in content source trees and thus knowledge about them is limited.
@@ -27,10 +30,17 @@ impl Backend {
                     modified_timestamp: FileTime::now(),
                     content_info: None
                 });
-    
-                self.run_script_analysis(vec![doc_path.clone()], true).await;
-                self.reporter.commit_diagnostics(&doc_path).await;
             }
+
+            // scripts known to the content graph don't get diagnostics until they are opened, unless workspace analysis took care of them
+            self.run_script_analysis(vec![doc_path.clone()], true).await;
+            self.reporter.commit_diagnostics(&doc_path).await;
+        }
+        else if doc_path.file_name().unwrap_or_default() == Manifest::FILE_NAME {
+            self.manifests.insert(doc_path, ManifestState {
+                buffer: ScriptDocument::from_str(&params.text_document.text),
+                manifest: Manifest::from_str(&params.text_document.text).ok()
+            });
         }
     }
     
@@ -52,7 +62,22 @@ impl Backend {
     
             content_info = script_state.content_info.clone();
             should_notify = true;
-        } 
+        }
+        else if let Some(mut entry) = self.manifests.get_mut(&doc_path) {
+            let manifest_state = entry.value_mut();
+
+            for edit in params.content_changes {
+                manifest_state.buffer.edit(&edit);
+            }
+
+            // manifests are not parsed with tree-sitter, so there is no need to keep track of edits
+            let text = manifest_state.buffer.text();
+            manifest_state.buffer.replace(&text);
+
+            if let Ok(manifest) = Manifest::from_str(&text) {
+                manifest_state.manifest = Some(manifest);
+            }
+        }
     
         if should_notify {
             if let Some(content_info) = content_info {
@@ -97,6 +122,17 @@ impl Backend {
                 self.scan_source_tree(&containing_content_path).await;
             }
         } else if (doc_path.file_name().unwrap() == Manifest::FILE_NAME || doc_path.extension().unwrap() == RedkitManifest::EXTENSION) && belongs_to_workspace {
+            if let Some(text) = params.text {
+                if let Some(mut entry) = self.manifests.get_mut(&doc_path) {
+                    let manifest_state = entry.value_mut();
+                    manifest_state.buffer.replace(&text);
+
+                    if let Ok(manifest) = Manifest::from_str(&text) {
+                        manifest_state.manifest = Some(manifest);
+                    }
+                }
+            }
+
             self.build_content_graph(false).await;
         }
     
@@ -106,6 +142,9 @@ impl Backend {
     pub async fn did_close_impl(&self, params: lsp::DidCloseTextDocumentParams) {
         let doc_path = AbsPath::try_from(params.text_document.uri.clone()).unwrap();
         if doc_path.extension().map(|ext| ext == "ws").unwrap_or(false) {
+            self.semantic_tokens_cache.remove(&doc_path);
+            self.open_scripts.remove(&doc_path);
+
             let mut should_remove_script = false;
             if self.scripts.get(&doc_path).map(|s| s.content_info.is_none()).unwrap_or(false) {
                 self.reporter.purge_diagnostics(&doc_path);
@@ -117,6 +156,9 @@ impl Backend {
                 self.scripts.remove(&doc_path);
             }
         }
+        else if doc_path.file_name().unwrap_or_default() == Manifest::FILE_NAME {
+            self.manifests.remove(&doc_path);
+        }
     }
     
     pub async fn did_create_files_impl(&self, params: lsp::CreateFilesParams) {
@@ -0,0 +1,208 @@
+use tower_lsp::lsp_types as lsp;
+use tower_lsp::jsonrpc::Result;
+use abs_path::AbsPath;
+use witcherscript::ast::*;
+use witcherscript::script_document::ScriptDocument;
+use witcherscript::tokens::CommentKind;
+use crate::Backend;
+
+
+impl Backend {
+    pub async fn folding_range_impl(&self, params: lsp::FoldingRangeParams) -> Result<Option<Vec<lsp::FoldingRange>>> {
+        let doc_path = AbsPath::try_from(params.text_document.uri.clone()).unwrap();
+
+        if doc_path.extension().unwrap_or_default() != "ws" {
+            return Ok(None);
+        }
+
+        if let Some(script_state) = self.scripts.get(&doc_path) {
+            let mut collector = FoldingRangeCollector::new(&script_state.buffer);
+            script_state.script.visit_nodes(&mut collector);
+
+            Ok(Some(collector.ranges))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+
+struct FoldingRangeCollector<'a> {
+    doc: &'a ScriptDocument,
+    ranges: Vec<lsp::FoldingRange>
+}
+
+impl<'a> FoldingRangeCollector<'a> {
+    fn new(doc: &'a ScriptDocument) -> Self {
+        Self {
+            doc,
+            ranges: Vec::new()
+        }
+    }
+
+    /// Folds lines from `start_line` up to the line preceding `block_range`'s end,
+    /// so that the closing brace stays visible
+    fn push_block(&mut self, start_line: u32, block_range: lsp::Range) {
+        if block_range.end.line > start_line + 1 {
+            self.ranges.push(lsp::FoldingRange {
+                start_line,
+                end_line: block_range.end.line - 1,
+                kind: None,
+                ..Default::default()
+            });
+        }
+    }
+
+    fn push_lines(&mut self, start_line: u32, end_line: u32, kind: lsp::FoldingRangeKind) {
+        if end_line > start_line {
+            self.ranges.push(lsp::FoldingRange {
+                start_line,
+                end_line,
+                kind: Some(kind),
+                ..Default::default()
+            });
+        }
+    }
+
+    fn push_function_definition(&mut self, start_line: u32, def: FunctionDefinitionNode) {
+        if let FunctionDefinition::Some(block) = def.value() {
+            self.push_block(start_line, block.range());
+        }
+    }
+
+    /// Multi-line block comments, groups of consecutive line comments and `// region` markers
+    fn collect_comments(&mut self, root: &RootNode) {
+        let mut region_stack: Vec<u32> = Vec::new();
+        // start and end line of the current group of line comments
+        let mut line_group: Option<(u32, u32)> = None;
+
+        for n in root.comments() {
+            let comment = n.value(self.doc);
+            let range = n.range();
+
+            if comment.kind == CommentKind::Block {
+                self.push_lines(range.start.line, range.end.line, lsp::FoldingRangeKind::Comment);
+                continue;
+            }
+
+            let content = comment.content().trim();
+            let content = content.strip_prefix('#').unwrap_or(content);
+            if is_region_marker(content, "endregion") {
+                if let Some(start_line) = region_stack.pop() {
+                    self.push_lines(start_line, range.start.line, lsp::FoldingRangeKind::Region);
+                }
+                continue;
+            }
+            if is_region_marker(content, "region") {
+                region_stack.push(range.start.line);
+                continue;
+            }
+
+            line_group = match line_group {
+                Some((start, end)) if end + 1 == range.start.line => Some((start, range.start.line)),
+                Some((start, end)) => {
+                    self.push_lines(start, end, lsp::FoldingRangeKind::Comment);
+                    Some((range.start.line, range.start.line))
+                },
+                None => Some((range.start.line, range.start.line))
+            };
+        }
+
+        if let Some((start, end)) = line_group {
+            self.push_lines(start, end, lsp::FoldingRangeKind::Comment);
+        }
+    }
+}
+
+/// Whether the comment content is the given marker, optionally followed by a label, e.g. `region Helpers`
+fn is_region_marker(content: &str, marker: &str) -> bool {
+    content.strip_prefix(marker)
+        .map(|rest| rest.chars().next().map(|c| c.is_whitespace()).unwrap_or(true))
+        .unwrap_or(false)
+}
+
+impl SyntaxNodeVisitor for FoldingRangeCollector<'_> {
+    fn visit_root(&mut self, n: &RootNode) -> RootTraversalPolicy {
+        self.collect_comments(n);
+
+        TraversalPolicy::default_to(true)
+    }
+
+    fn visit_class_decl(&mut self, n: &ClassDeclarationNode) -> ClassDeclarationTraversalPolicy {
+        self.push_block(n.name().range().start.line, n.definition().range());
+
+        TraversalPolicy::default_to(true)
+    }
+
+    fn visit_state_decl(&mut self, n: &StateDeclarationNode) -> StateDeclarationTraversalPolicy {
+        self.push_block(n.name().range().start.line, n.definition().range());
+
+        TraversalPolicy::default_to(true)
+    }
+
+    fn visit_struct_decl(&mut self, n: &StructDeclarationNode) -> StructDeclarationTraversalPolicy {
+        self.push_block(n.name().range().start.line, n.definition().range());
+
+        TraversalPolicy::default_to(true)
+    }
+
+    fn visit_enum_decl(&mut self, n: &EnumDeclarationNode) -> EnumDeclarationTraversalPolicy {
+        self.push_block(n.name().range().start.line, n.definition().range());
+
+        TraversalPolicy::default_to(true)
+    }
+
+    fn visit_global_func_decl(&mut self, n: &FunctionDeclarationNode) -> FunctionDeclarationTraversalPolicy {
+        self.push_function_definition(n.name().range().start.line, n.definition());
+
+        TraversalPolicy::default_to(true)
+    }
+
+    fn visit_member_func_decl(&mut self, n: &FunctionDeclarationNode, _: &TraversalContextStack) -> FunctionDeclarationTraversalPolicy {
+        self.push_function_definition(n.name().range().start.line, n.definition());
+
+        TraversalPolicy::default_to(true)
+    }
+
+    fn visit_event_decl(&mut self, n: &EventDeclarationNode, _: &TraversalContextStack) -> EventDeclarationTraversalPolicy {
+        self.push_function_definition(n.name().range().start.line, n.definition());
+
+        TraversalPolicy::default_to(true)
+    }
+
+    fn visit_member_defaults_block(&mut self, n: &MemberDefaultsBlockNode, _: &TraversalContextStack) -> MemberDefaultsBlockTraversalPolicy {
+        self.push_block(n.range().start.line, n.range());
+
+        TraversalPolicy::default_to(true)
+    }
+
+    fn visit_switch_stmt(&mut self, n: &SwitchConditionalNode, _: &TraversalContextStack) -> SwitchConditionalTraversalPolicy {
+        self.push_block(n.range().start.line, n.body().range());
+
+        TraversalPolicy::default_to(true)
+    }
+}
+
+
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+
+    #[test]
+    fn test_region_marker() {
+        assert!(is_region_marker("region", "region"));
+        assert!(is_region_marker("region Helpers", "region"));
+        assert!(is_region_marker("region\tHelpers", "region"));
+        assert!(is_region_marker("endregion", "endregion"));
+        assert!(is_region_marker("endregion Helpers", "endregion"));
+
+        assert!(!is_region_marker("regional settings", "region"));
+        assert!(!is_region_marker("regions", "region"));
+        assert!(!is_region_marker("endregions are handled here", "endregion"));
+        assert!(!is_region_marker("endregion", "region"));
+        assert!(!is_region_marker("the region", "region"));
+    }
+}
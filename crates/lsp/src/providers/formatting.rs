@@ -0,0 +1,84 @@
+use abs_path::AbsPath;
+use tower_lsp::lsp_types as lsp;
+use tower_lsp::jsonrpc::Result;
+use witcherscript::formatting::{self, FormatterOptions};
+use crate::config::BraceStyle;
+use crate::Backend;
+
+
+impl Backend {
+    pub async fn formatting_impl(&self, params: lsp::DocumentFormattingParams) -> Result<Option<Vec<lsp::TextEdit>>> {
+        let doc_path = AbsPath::try_from(params.text_document.uri).unwrap();
+
+        if doc_path.extension().unwrap_or_default() != "ws" {
+            return Ok(None);
+        }
+
+        let options = self.formatter_options(&params.options).await;
+
+        let script_state;
+        if let Some(ss) = self.scripts.get(&doc_path) {
+            script_state = ss;
+        } else {
+            return Ok(None);
+        }
+
+        Ok(formatting::format_document(&script_state.script, &script_state.buffer, &options))
+    }
+
+    pub async fn range_formatting_impl(&self, params: lsp::DocumentRangeFormattingParams) -> Result<Option<Vec<lsp::TextEdit>>> {
+        let doc_path = AbsPath::try_from(params.text_document.uri).unwrap();
+
+        if doc_path.extension().unwrap_or_default() != "ws" {
+            return Ok(None);
+        }
+
+        let options = self.formatter_options(&params.options).await;
+
+        let script_state;
+        if let Some(ss) = self.scripts.get(&doc_path) {
+            script_state = ss;
+        } else {
+            return Ok(None);
+        }
+
+        Ok(formatting::format_range(&script_state.script, &script_state.buffer, params.range, &options))
+    }
+
+    pub async fn on_type_formatting_impl(&self, params: lsp::DocumentOnTypeFormattingParams) -> Result<Option<Vec<lsp::TextEdit>>> {
+        let doc_path = AbsPath::try_from(params.text_document_position.text_document.uri).unwrap();
+
+        if doc_path.extension().unwrap_or_default() != "ws" {
+            return Ok(None);
+        }
+
+        let options = self.formatter_options(&params.options).await;
+
+        let script_state;
+        if let Some(ss) = self.scripts.get(&doc_path) {
+            script_state = ss;
+        } else {
+            return Ok(None);
+        }
+
+        Ok(formatting::format_on_type(&script_state.script, &script_state.buffer, params.text_document_position.position, &params.ch, &options))
+    }
+
+
+    /// Indentation comes from the editor, the rest of the style from the configuration
+    async fn formatter_options(&self, options: &lsp::FormattingOptions) -> FormatterOptions {
+        let config = self.config.read().await;
+
+        FormatterOptions {
+            tab_size: options.tab_size,
+            use_tabs: !options.insert_spaces,
+            brace_style: match config.formatting.brace_style {
+                BraceStyle::SameLine => formatting::BraceStyle::SameLine,
+                BraceStyle::NextLine => formatting::BraceStyle::NextLine,
+            },
+            space_around_operators: config.formatting.space_around_operators,
+            space_before_type_colon: config.formatting.space_before_type_colon,
+            space_after_control_keywords: config.formatting.space_after_control_keywords
+        }
+    }
+}
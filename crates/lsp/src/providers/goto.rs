@@ -2,6 +2,7 @@ use tower_lsp::lsp_types as lsp;
 use tower_lsp::jsonrpc::Result;
 use abs_path::AbsPath;
 use witcherscript_analysis::symbol_analysis::{symbol_path::SymbolPathBuf, symbols::*};
+use witcherscript_project::Manifest;
 use crate::{notifications, Backend};
 use super::common::resolve_text_document_position;
 
@@ -10,6 +11,10 @@ impl Backend {
     pub async fn goto_definition_impl(&self, params: lsp::GotoDefinitionParams) -> Result<Option<lsp::GotoDefinitionResponse>> {
         let doc_path = AbsPath::try_from(params.text_document_position_params.text_document.uri.clone()).unwrap();
     
+        if doc_path.file_name().unwrap_or_default() == Manifest::FILE_NAME {
+            return self.manifest_goto_definition(&doc_path, params.text_document_position_params.position).await;
+        }
+
         if doc_path.extension().unwrap_or_default() != "ws" {
             return Ok(None);
         }
@@ -233,6 +238,86 @@ impl Backend {
     }
 
 
+    pub async fn goto_implementation_impl(&self, params: lsp::request::GotoImplementationParams) -> Result<Option<lsp::request::GotoImplementationResponse>> {
+        let doc_path = AbsPath::try_from(params.text_document_position_params.text_document.uri.clone()).unwrap();
+    
+        if doc_path.extension().unwrap_or_default() != "ws" {
+            return Ok(None);
+        }
+    
+        let content_path;
+        if let Some(path) = self.scripts.get(&doc_path).and_then(|ss| ss.content_info.as_ref().map(|ci| ci.content_path.to_owned())) {
+            content_path = path;
+        } 
+        else {
+            self.client.send_notification::<notifications::client::show_foreign_script_warning::Type>(()).await;
+            return Ok(None);
+        }
+
+        let inspected;
+        if let Some(i) = self.inspect_symbol_at_position(&content_path, &doc_path, params.text_document_position_params.position).await {
+            inspected = i;
+        } else {
+            return Ok(None);
+        }
+
+        let symvar;
+        if let Some(v) = inspected.symvar {
+            symvar = v;
+        } else {
+            return Ok(None);
+        }
+
+        let func_path;
+        if let Some(p) = implemented_func_path(&symvar) {
+            func_path = p;
+        } else {
+            return Ok(None);
+        }
+
+        let symtabs = self.symtabs.read().await;
+        let origin_content_path = self.origin_content_path(&symtabs, &content_path, &func_path).await;
+
+        // overrides in derived types only make sense for methods and events 
+        let mut searched_paths = vec![func_path.clone()];
+        if func_path.parent().is_some() {
+            searched_paths.extend(self.find_member_overrides(&symtabs, &origin_content_path, &func_path).await);
+        }
+
+        // both overrides and annotation-based redefinitions can only exist in the content 
+        // that declares the original function or in contents that depend on it
+        let searched_contents: Vec<AbsPath> =
+            [origin_content_path.clone()].into_iter()
+            .chain(self.content_graph
+                .read().await
+                .walk_dependants(&origin_content_path)
+                .map(|n| n.content.path().to_owned()))
+            .collect();
+
+        let mut locations: Vec<SymbolLocation> = Vec::new();
+        for symtab in searched_contents.iter().filter_map(|p| symtabs.get(p)) {
+            for path in searched_paths.iter() {
+                if let Some(loc) = symtab.get_symbol(path).and_then(|v| v.location()) {
+                    if Some(loc) != inspected.loc.as_ref() && !locations.contains(loc) {
+                        locations.push(loc.to_owned());
+                    }
+                }
+            }
+        }
+
+        let origin_selection_range = Some(inspected.origin_selection_range);
+        Ok(Some(lsp::request::GotoImplementationResponse::Link(locations
+            .into_iter()
+            .map(|loc| lsp::LocationLink {
+                origin_selection_range,
+                target_uri: loc.abs_source_path().to_uri(),
+                target_range: loc.range,
+                target_selection_range: loc.label_range
+            })
+            .collect()
+        )))
+    }
+
 
     async fn inspect_symbol_at_position(&self, content_path: &AbsPath, doc_path: &AbsPath, position: lsp::Position) -> Option<Inspected> {
         let symtabs = self.symtabs.read().await;
@@ -291,3 +376,53 @@ struct Inspected {
     symvar: Option<SymbolVariant>,
     loc: Option<SymbolLocation>
 }
+
+/// Returns the path of the function which implementations should be looked for if the symbol is a function
+fn implemented_func_path(symvar: &SymbolVariant) -> Option<SymbolPathBuf> {
+    // wrappedMethod stands for the method being wrapped
+    if let Some(wrapped_method_sym) = symvar.try_as_wrapped_method_ref() {
+        Some(wrapped_method_sym.wrapped_path().to_owned().into())
+    } else if symvar.is_member_func() || symvar.is_event() || symvar.is_global_func() || symvar.is_member_func_injector()
+           || symvar.is_member_func_wrapper() || symvar.is_member_func_replacer() || symvar.is_global_func_replacer() {
+        Some(symvar.path().to_owned())
+    } else {
+        None
+    }
+}
+
+
+
+
+#[cfg(test)]
+mod test {
+    use std::path::Path;
+    use std::sync::Arc;
+    use super::*;
+
+
+    #[test]
+    fn test_implemented_func_path() {
+        let location = SymbolLocation {
+            scripts_root: Arc::new(AbsPath::resolve(env!("CARGO_MANIFEST_DIR"), None).unwrap()),
+            local_source_path: Arc::from(Path::new("test.ws")),
+            range: lsp::Range::default(),
+            label_range: lsp::Range::default()
+        };
+
+        let class_path = BasicTypeSymbolPath::new("CFoo");
+        let method_path = MemberCallableSymbolPath::new(&class_path, "Bar");
+        let method = MemberFunctionSymbol::new(method_path.clone(), location.clone());
+
+        let class: SymbolVariant = ClassSymbol::new(class_path.clone(), location.clone()).into();
+        assert_eq!(implemented_func_path(&class), None);
+
+        let injector: SymbolVariant = MemberFunctionInjectorSymbol::new(method.clone()).into();
+        assert_eq!(implemented_func_path(&injector), Some(method_path.clone().into()));
+
+        let wrapped_method: SymbolVariant = WrappedMethodSymbol::new(&method_path).into();
+        assert_eq!(implemented_func_path(&wrapped_method), Some(method_path.clone().into()));
+
+        let method: SymbolVariant = method.into();
+        assert_eq!(implemented_func_path(&method), Some(method_path.into()));
+    }
+}
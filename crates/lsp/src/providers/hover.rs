@@ -7,6 +7,7 @@ use witcherscript_analysis::symbol_analysis::symbol_table::iter::*;
 use witcherscript_analysis::symbol_analysis::symbol_table::marcher::SymbolTableMarcher;
 use witcherscript_analysis::symbol_analysis::symbol_table::SymbolTable;
 use witcherscript_analysis::symbol_analysis::symbols::*;
+use witcherscript_project::Manifest;
 use crate::Backend;
 use super::common::{resolve_text_document_position, PositionTargetKind};
 
@@ -15,6 +16,10 @@ impl Backend {
     pub async fn hover_impl(&self, params: lsp::HoverParams) -> Result<Option<lsp::Hover>> {
         let doc_path = AbsPath::try_from(params.text_document_position_params.text_document.uri.clone()).unwrap();
     
+        if doc_path.file_name().unwrap_or_default() == Manifest::FILE_NAME {
+            return self.manifest_hover(&doc_path, params.text_document_position_params.position).await;
+        }
+
         if doc_path.extension().unwrap_or_default() != "ws" {
             return Ok(None);
         }
@@ -43,6 +48,7 @@ impl Backend {
         
         if let Some(position_target) = position_target {
             let mut value = None;
+            let mut doc_comment = None;
     
             if let Some(sympath) = position_target.target_symbol_path(&symtabs_marcher) {
                 let category = sympath
@@ -51,21 +57,32 @@ impl Backend {
                 .unwrap_or(SymbolCategory::Type);
     
                 let mut buf = String::new();
-                symtabs_marcher.get_symbol_with_table(&sympath)
-                    .map(|(symtab, symvar)| symvar.render(&mut buf, symtab, &symtabs_marcher))
-                    .unwrap_or_else(|| buf = SymbolPathBuf::unknown(category).to_string());
+                if let Some((symtab, symvar)) = symtabs_marcher.get_symbol_with_table(&sympath) {
+                    symvar.render(&mut buf, symtab, &symtabs_marcher);
+                    doc_comment = symvar.doc_comment().map(|doc| doc.to_string());
+                } else {
+                    buf = SymbolPathBuf::unknown(category).to_string();
+                }
     
                 value = Some(buf);
             } else if let PositionTargetKind::ArrayTypeIdentifier = position_target.kind {
                 value = Some("array<T>".to_string());
             }
     
-            Ok(value.map(|value| lsp::Hover {
-                contents: lsp::HoverContents::Scalar(lsp::MarkedString::LanguageString(lsp::LanguageString {
-                    language: Backend::LANGUAGE_ID.to_string(),
-                    value,
-                })),
-                range: None
+            Ok(value.map(|value| {
+                let mut markdown = format!("```{}\n{}\n```", Backend::LANGUAGE_ID, value);
+                if let Some(doc_comment) = doc_comment {
+                    markdown += "\n\n---\n\n";
+                    markdown += &doc_comment;
+                }
+
+                lsp::Hover {
+                    contents: lsp::HoverContents::Markup(lsp::MarkupContent {
+                        kind: lsp::MarkupKind::Markdown,
+                        value: markdown
+                    }),
+                    range: None
+                }
             }))
         } else {
             Ok(None)
@@ -74,14 +91,14 @@ impl Backend {
 }
 
 
-trait RenderTooltip {
+pub(super) trait RenderTooltip {
     fn render(&self, buf: &mut String, symtab: &SymbolTable, marcher: &SymbolTableMarcher<'_>);
 }
 
 /// For when the text is supposed to be rendered as a part of some bigger tooltip
 /// e.g. a member var tooltip contains a partial tooltip for containing class at the top 
 /// to know where that var comes from
-trait RenderPartialTooltip {
+pub(super) trait RenderPartialTooltip {
     fn render_partial(&self, buf: &mut String);
 }
 
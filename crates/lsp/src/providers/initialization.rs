@@ -1,4 +1,5 @@
 use std::path::PathBuf;
+use std::sync::atomic::Ordering;
 use abs_path::AbsPath;
 use serde::Deserialize;
 use tower_lsp::lsp_types::notification::Notification;
@@ -6,7 +7,9 @@ use tower_lsp::lsp_types as lsp;
 use tower_lsp::jsonrpc::Result;
 use witcherscript_project::redkit::RedkitManifest;
 use witcherscript_project::Manifest;
+use crate::config::FormattingConfig;
 use crate::{notifications, Backend};
+use super::semantic_tokens::{SEMANTIC_TOKEN_TYPES, SEMANTIC_TOKEN_MODIFIERS};
 
 
 #[derive(Debug, Deserialize)]
@@ -16,7 +19,16 @@ struct InitializationOptions {
     native_content_path: PathBuf,
     game_directory: PathBuf,
     content_repositories: Vec<PathBuf>,
-    enable_syntax_analysis: bool
+    enable_syntax_analysis: bool,
+    // options added later on may be missing if the client is older than the server
+    #[serde(default = "default_enable_workspace_analysis")]
+    enable_workspace_analysis: bool,
+    #[serde(default)]
+    formatting: FormattingConfig
+}
+
+fn default_enable_workspace_analysis() -> bool {
+    true
 }
 
 impl Backend {
@@ -60,6 +72,8 @@ impl Backend {
                     config.game_directory = val.game_directory;
                     config.content_repositories = val.content_repositories;
                     config.enable_syntax_analysis = val.enable_syntax_analysis;
+                    config.enable_workspace_analysis = val.enable_workspace_analysis;
+                    config.formatting = val.formatting;
                 },
                 Err(err) => {
                     self.reporter.log_error(format!("InitializationOptions deserialization fail: {}", err)).await;
@@ -69,6 +83,38 @@ impl Backend {
             self.reporter.log_error("Initialization options missing!").await;
         }
 
+        let supports_pull_diagnostics = params.capabilities.text_document.as_ref()
+            .and_then(|td| td.diagnostic.as_ref())
+            .is_some();
+
+        if supports_pull_diagnostics {
+            let refresh_support = params.capabilities.workspace.as_ref()
+                .and_then(|ws| ws.diagnostic.as_ref())
+                .and_then(|diag| diag.refresh_support)
+                .unwrap_or(false);
+
+            self.reporter.enable_pull_diagnostics(refresh_support);
+        }
+
+        let watched_files_caps = params.capabilities.workspace.as_ref()
+            .and_then(|ws| ws.did_change_watched_files.as_ref());
+        let supports_file_watchers = watched_files_caps
+            .and_then(|dcwf| dcwf.dynamic_registration)
+            .unwrap_or(false);
+        let supports_relative_patterns = watched_files_caps
+            .and_then(|dcwf| dcwf.relative_pattern_support)
+            .unwrap_or(false);
+
+        self.file_watchers_support.store(supports_file_watchers, Ordering::Relaxed);
+        self.relative_pattern_support.store(supports_relative_patterns, Ordering::Relaxed);
+
+        let supports_type_hierarchy = params.capabilities.text_document.as_ref()
+            .and_then(|td| td.type_hierarchy.as_ref())
+            .and_then(|th| th.dynamic_registration)
+            .unwrap_or(false);
+
+        self.type_hierarchy_support.store(supports_type_hierarchy, Ordering::Relaxed);
+
         if let Some(workspace_folders) = params.workspace_folders {
             let mut workspace_roots = self.workspace_roots.write().await;
             *workspace_roots = workspace_folders.into_iter()
@@ -144,8 +190,59 @@ impl Backend {
                 definition_provider: Some(lsp::OneOf::Left(true)),
                 declaration_provider: Some(lsp::DeclarationCapability::Simple(true)),
                 type_definition_provider: Some(lsp::TypeDefinitionProviderCapability::Simple(true)),
-                implementation_provider: None,
+                implementation_provider: Some(lsp::ImplementationProviderCapability::Simple(true)),
                 hover_provider: Some(lsp::HoverProviderCapability::Simple(true)),
+                completion_provider: Some(lsp::CompletionOptions {
+                    trigger_characters: Some(vec![".".into()]),
+                    ..Default::default()
+                }),
+                signature_help_provider: Some(lsp::SignatureHelpOptions {
+                    trigger_characters: Some(vec!["(".into(), ",".into()]),
+                    retrigger_characters: None,
+                    work_done_progress_options: Default::default()
+                }),
+                references_provider: Some(lsp::OneOf::Left(true)),
+                rename_provider: Some(lsp::OneOf::Right(lsp::RenameOptions {
+                    prepare_provider: Some(true),
+                    work_done_progress_options: Default::default()
+                })),
+                workspace_symbol_provider: Some(lsp::OneOf::Left(true)),
+                semantic_tokens_provider: Some(lsp::SemanticTokensServerCapabilities::SemanticTokensOptions(lsp::SemanticTokensOptions {
+                    legend: lsp::SemanticTokensLegend {
+                        token_types: SEMANTIC_TOKEN_TYPES.to_vec(),
+                        token_modifiers: SEMANTIC_TOKEN_MODIFIERS.to_vec()
+                    },
+                    range: None,
+                    full: Some(lsp::SemanticTokensFullOptions::Delta { delta: Some(true) }),
+                    work_done_progress_options: Default::default()
+                })),
+                document_formatting_provider: Some(lsp::OneOf::Left(true)),
+                document_range_formatting_provider: Some(lsp::OneOf::Left(true)),
+                document_on_type_formatting_provider: Some(lsp::DocumentOnTypeFormattingOptions {
+                    first_trigger_character: "}".into(),
+                    more_trigger_character: Some(vec![";".into()])
+                }),
+                folding_range_provider: Some(lsp::FoldingRangeProviderCapability::Simple(true)),
+                inlay_hint_provider: Some(lsp::OneOf::Left(true)),
+                call_hierarchy_provider: Some(lsp::CallHierarchyServerCapability::Simple(true)),
+                code_action_provider: Some(lsp::CodeActionProviderCapability::Options(lsp::CodeActionOptions {
+                    code_action_kinds: Some(vec![lsp::CodeActionKind::QUICKFIX]),
+                    ..Default::default()
+                })),
+                code_lens_provider: Some(lsp::CodeLensOptions {
+                    resolve_provider: Some(true)
+                }),
+                document_highlight_provider: Some(lsp::OneOf::Left(true)),
+                document_link_provider: Some(lsp::DocumentLinkOptions {
+                    resolve_provider: Some(false),
+                    work_done_progress_options: Default::default()
+                }),
+                diagnostic_provider: Some(lsp::DiagnosticServerCapabilities::Options(lsp::DiagnosticOptions {
+                    identifier: Some(Backend::SERVER_NAME.into()),
+                    inter_file_dependencies: true,
+                    workspace_diagnostics: true,
+                    work_done_progress_options: Default::default()
+                })),
                 ..lsp::ServerCapabilities::default()
             }
         })
@@ -161,13 +258,60 @@ impl Backend {
                 register_options: None 
             }
         ]).await.unwrap();
+
+        self.register_type_hierarchy().await;
     
         self.setup_workspace_content_scanners().await;
         self.setup_repository_content_scanners().await;
         self.build_content_graph(true).await;
+        self.register_file_watchers().await;
 
         self.client.send_notification::<notifications::scripts::did_finish_initial_indexing::Type>(()).await;
 
         self.reporter.commit_all_diagnostics().await;
     }
-}
\ No newline at end of file
+}
+
+
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::config::BraceStyle;
+
+
+    #[test]
+    fn test_init_options_defaults() {
+        let old_init_opts = serde_json::json!({
+            "rayonThreads": 0,
+            "nativeContentPath": "/ext/native",
+            "gameDirectory": "/game",
+            "contentRepositories": [],
+            "enableSyntaxAnalysis": true
+        });
+
+        let opts = serde_json::from_value::<InitializationOptions>(old_init_opts).unwrap();
+        assert!(opts.enable_workspace_analysis);
+        assert_eq!(opts.formatting, FormattingConfig::default());
+
+        let partial_formatting_init_opts = serde_json::json!({
+            "rayonThreads": 0,
+            "nativeContentPath": "/ext/native",
+            "gameDirectory": "/game",
+            "contentRepositories": [],
+            "enableSyntaxAnalysis": true,
+            "enableWorkspaceAnalysis": false,
+            "formatting": {
+                "braceStyle": "sameLine"
+            }
+        });
+
+        let opts = serde_json::from_value::<InitializationOptions>(partial_formatting_init_opts).unwrap();
+        assert!(!opts.enable_workspace_analysis);
+        assert_eq!(opts.formatting, FormattingConfig {
+            brace_style: BraceStyle::SameLine,
+            ..FormattingConfig::default()
+        });
+    }
+}
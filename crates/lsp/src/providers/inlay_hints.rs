@@ -0,0 +1,163 @@
+use std::{cell::RefCell, rc::Rc};
+use abs_path::AbsPath;
+use tower_lsp::lsp_types as lsp;
+use tower_lsp::jsonrpc::Result;
+use witcherscript::{ast::*, script_document::ScriptDocument};
+use witcherscript_analysis::symbol_analysis::symbol_table::marcher::SymbolTableMarcher;
+use witcherscript_analysis::symbol_analysis::unqualified_name_lookup::*;
+use witcherscript_analysis::utils::*;
+use crate::Backend;
+
+
+impl Backend {
+    pub async fn inlay_hint_impl(&self, params: lsp::InlayHintParams) -> Result<Option<Vec<lsp::InlayHint>>> {
+        let doc_path = AbsPath::try_from(params.text_document.uri.clone()).unwrap();
+
+        if doc_path.extension().unwrap_or_default() != "ws" {
+            return Ok(None);
+        }
+
+        let content_path;
+        if let Some(path) = self.scripts.get(&doc_path).and_then(|ss| ss.content_info.as_ref().map(|ci| ci.content_path.to_owned())) {
+            content_path = path;
+        }
+        else {
+            return Ok(None);
+        }
+
+        let symtabs = self.symtabs.read().await;
+        let symtabs_marcher = self.march_symbol_tables(&symtabs, &content_path).await;
+
+        let script_state;
+        if let Some(ss) = self.scripts.get(&doc_path) {
+            script_state = ss;
+        } else {
+            return Ok(None);
+        }
+
+        let (sympath_builder, sympath_builder_payload) = SymbolPathBuilder::new(&script_state.buffer);
+        let (unl_builder, unl_payload) = UnqualifiedNameLookupBuilder::new(&script_state.buffer, sympath_builder_payload.clone(), symtabs_marcher.clone());
+        let collector = InlayHintCollector::new_rc(
+            params.range,
+            &script_state.buffer,
+            symtabs_marcher,
+            sympath_builder_payload.clone(),
+            unl_payload.clone()
+        );
+
+        let mut chain = SyntaxNodeVisitorChain::new()
+            .link(sympath_builder)
+            .link(unl_builder)
+            .link_rc(collector.clone());
+
+        script_state.script.visit_nodes(&mut chain);
+
+        let hints = std::mem::take(&mut collector.borrow_mut().hints);
+        Ok(Some(hints))
+    }
+}
+
+
+/// A node visitor that puts hints at arguments of function calls.
+/// Literal arguments are preceded by the name of the parameter and arguments passed to `out` parameters are marked as such.
+/// Expects to work after SymbolPathBuilder and UnqualifiedNameLookupBuilder in visitor chain.
+struct InlayHintCollector<'a> {
+    range: lsp::Range,
+    doc: &'a ScriptDocument,
+    symtab_marcher: SymbolTableMarcher<'a>,
+    sympath_builder_payload: Rc<RefCell<SymbolPathBuilderPayload>>,
+    unl_builder_payload: Rc<RefCell<UnqualifiedNameLookup>>,
+    hints: Vec<lsp::InlayHint>
+}
+
+impl<'a> InlayHintCollector<'a> {
+    fn new_rc(
+        range: lsp::Range,
+        doc: &'a ScriptDocument,
+        symtab_marcher: SymbolTableMarcher<'a>,
+        sympath_builder_payload: Rc<RefCell<SymbolPathBuilderPayload>>,
+        unl_builder_payload: Rc<RefCell<UnqualifiedNameLookup>>
+    ) -> Rc<RefCell<Self>> {
+        Rc::new(RefCell::new(Self {
+            range,
+            doc,
+            symtab_marcher,
+            sympath_builder_payload,
+            unl_builder_payload,
+            hints: Vec::new()
+        }))
+    }
+
+    /// Returns parameters of the called function ordered by their ordinal
+    fn callee_params(&self, n: &FunctionCallExpressionNode) -> Vec<CalleeParam<'a>> {
+        let callee_path = evaluate_expression(
+            n.func(), TraversalContext::FunctionCallExpressionFunc,
+            self.doc,
+            self.symtab_marcher.clone(),
+            self.sympath_builder_payload.clone(),
+            self.unl_builder_payload.clone()
+        );
+
+        resolve_callee(&callee_path, &self.symtab_marcher)
+            .map(|callee| callee.params)
+            .unwrap_or_default()
+    }
+}
+
+impl SyntaxNodeVisitor for InlayHintCollector<'_> {
+    fn traversal_policy_default(&self) -> bool {
+        true
+    }
+
+    fn visit_func_call_expr(&mut self, n: &FunctionCallExpressionNode, _: &TraversalContextStack) -> FunctionCallExpressionTraversalPolicy {
+        let call_range = n.range();
+        let args = n.args().map(|args| args.iter().collect::<Vec<_>>()).unwrap_or_default();
+        if call_range.end < self.range.start || call_range.start > self.range.end || args.is_empty() {
+            return TraversalPolicy::default_to(true);
+        }
+
+        let params = self.callee_params(n);
+        for (arg, param) in args.into_iter().zip(params) {
+            let arg_expr;
+            if let FunctionCallArgument::Some(expr) = arg {
+                arg_expr = expr;
+            } else {
+                continue;
+            }
+
+            let label = if param.is_out() {
+                "out".to_string()
+            } else if is_literal(arg_expr.clone()) {
+                format!("{}:", param.name())
+            } else {
+                continue;
+            };
+
+            self.hints.push(lsp::InlayHint {
+                position: arg_expr.range().start,
+                label: lsp::InlayHintLabel::String(label),
+                kind: Some(lsp::InlayHintKind::PARAMETER),
+                text_edits: None,
+                tooltip: None,
+                padding_left: None,
+                padding_right: Some(true),
+                data: None
+            });
+        }
+
+        TraversalPolicy::default_to(true)
+    }
+}
+
+impl SyntaxNodeVisitorChainLink for InlayHintCollector<'_> {}
+
+
+fn is_literal(n: ExpressionNode) -> bool {
+    match n.value() {
+        Expression::Literal(_) => true,
+        // negative numbers
+        Expression::UnaryOperation(op) => matches!(op.right().value(), Expression::Literal(_)),
+        Expression::Nested(nested) => is_literal(nested.inner()),
+        _ => false
+    }
+}
@@ -0,0 +1,264 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+use abs_path::AbsPath;
+use tower_lsp::lsp_types as lsp;
+use tower_lsp::jsonrpc::Result;
+use witcherscript_project::content::{ProjectDirectory, RedkitProjectDirectory};
+use witcherscript_project::manifest::{DependencyEntry, DependencyValue};
+use witcherscript_project::{try_make_content, Content, Manifest};
+use crate::Backend;
+
+
+impl Backend {
+    /// Completes names of dependencies in the `[dependencies]` table with contents found in repositories
+    pub(super) async fn manifest_completion(&self, doc_path: &AbsPath, position: lsp::Position) -> Result<Option<lsp::CompletionResponse>> {
+        let (text, manifest);
+        if let Some(ms) = self.manifests.get(doc_path) {
+            text = ms.buffer.text();
+            manifest = ms.manifest.clone();
+        } else {
+            return Ok(None);
+        }
+
+        let lines: Vec<&str> = text.lines().collect();
+        let line_idx = position.line as usize;
+
+        let in_dependencies_table = lines.iter()
+            .take(line_idx)
+            .rev()
+            .map(|line| line.trim())
+            .find(|line| line.starts_with('['))
+            .map(|header| header.split_whitespace().collect::<String>() == "[dependencies]")
+            .unwrap_or(false);
+
+        if !in_dependencies_table {
+            return Ok(None);
+        }
+
+        let line = lines.get(line_idx).copied().unwrap_or_default();
+        let prefix: String = line.chars().take(position.character as usize).collect();
+        let suffix: String = line.chars().skip(position.character as usize).collect();
+        let key_prefix = prefix.trim_start();
+        // only the key part of the entry is completed
+        if key_prefix.contains('=') || key_prefix.starts_with('#') || key_prefix.starts_with('[') {
+            return Ok(None);
+        }
+
+        let replaced_range = lsp::Range::new(
+            lsp::Position::new(position.line, position.character - key_prefix.chars().count() as u32),
+            position
+        );
+
+        let this_content_name = manifest.as_ref().map(|m| m.content.name.clone()).unwrap_or_default();
+        let mut excluded_names: HashSet<String> = manifest
+            .map(|m| m.dependencies.iter().map(|e| e.name.clone()).collect())
+            .unwrap_or_default();
+        excluded_names.insert(this_content_name);
+
+        let content_graph = self.content_graph.read().await;
+        let mut items = Vec::new();
+        for content in content_graph.repository_contents() {
+            let name = content.content_name();
+            if !excluded_names.insert(name.to_string()) {
+                continue;
+            }
+
+            let key = toml_key(name);
+            let new_text = if suffix.contains('=') {
+                key
+            } else {
+                format!("{} = true", key)
+            };
+
+            items.push(lsp::CompletionItem {
+                label: name.to_string(),
+                kind: Some(lsp::CompletionItemKind::MODULE),
+                detail: Some(content.path().to_string()),
+                documentation: content_description(content.as_ref()).map(lsp::Documentation::String),
+                filter_text: Some(name.to_string()),
+                text_edit: Some(lsp::CompletionTextEdit::Edit(lsp::TextEdit::new(replaced_range, new_text))),
+                ..Default::default()
+            });
+        }
+
+        Ok(Some(lsp::CompletionResponse::Array(items)))
+    }
+
+    /// Shows information about the content that a dependency entry resolves to
+    pub(super) async fn manifest_hover(&self, doc_path: &AbsPath, position: lsp::Position) -> Result<Option<lsp::Hover>> {
+        let entry;
+        if let Some(e) = self.manifest_dependency_at_position(doc_path, position) {
+            entry = e;
+        } else {
+            return Ok(None);
+        }
+
+        let content;
+        if let Some(c) = self.resolve_manifest_dependency(doc_path, &entry).await {
+            content = c;
+        } else {
+            return Ok(None);
+        }
+
+        let mut value = format!("**{}**", content.content_name());
+        if let Some(description) = content_description(content.as_ref()) {
+            value += &format!("\n\n{}", description);
+        }
+        if let Some(version) = content_version(content.as_ref()) {
+            value += &format!("\n\nVersion: {}", version);
+        }
+        value += &format!("\n\nPath: `{}`", content.path());
+
+        Ok(Some(lsp::Hover {
+            contents: lsp::HoverContents::Markup(lsp::MarkupContent {
+                kind: lsp::MarkupKind::Markdown,
+                value
+            }),
+            range: Some(entry.name_range)
+        }))
+    }
+
+    /// Goes to the manifest of the content that a dependency entry resolves to
+    pub(super) async fn manifest_goto_definition(&self, doc_path: &AbsPath, position: lsp::Position) -> Result<Option<lsp::GotoDefinitionResponse>> {
+        let entry;
+        if let Some(e) = self.manifest_dependency_at_position(doc_path, position) {
+            entry = e;
+        } else {
+            return Ok(None);
+        }
+
+        if position < entry.name_range.start || position > entry.name_range.end {
+            return Ok(None);
+        }
+
+        let target = self.resolve_manifest_dependency(doc_path, &entry).await
+            .and_then(|content| content_manifest_location(content.as_ref()));
+
+        Ok(target.map(|(target_uri, target_range)| lsp::GotoDefinitionResponse::Link(vec![
+            lsp::LocationLink {
+                origin_selection_range: Some(entry.name_range),
+                target_uri,
+                target_range,
+                target_selection_range: target_range
+            }
+        ])))
+    }
+
+    pub async fn document_link_impl(&self, params: lsp::DocumentLinkParams) -> Result<Option<Vec<lsp::DocumentLink>>> {
+        let doc_path = AbsPath::try_from(params.text_document.uri.clone()).unwrap();
+
+        if doc_path.file_name().unwrap_or_default() != Manifest::FILE_NAME {
+            return Ok(None);
+        }
+
+        let path_entries: Vec<DependencyEntry>;
+        if let Some(manifest) = self.manifests.get(&doc_path).and_then(|ms| ms.manifest.clone()) {
+            path_entries = manifest.dependencies.iter()
+                .filter(|e| matches!(e.value, DependencyValue::FromPath { .. }))
+                .cloned()
+                .collect();
+        } else {
+            return Ok(None);
+        }
+
+        let mut links = Vec::new();
+        for entry in path_entries {
+            let target = self.resolve_manifest_dependency(&doc_path, &entry).await
+                .and_then(|content| content_manifest_location(content.as_ref()));
+
+            if let Some((target_uri, _)) = target {
+                links.push(lsp::DocumentLink {
+                    range: entry.value_range,
+                    target: Some(target_uri),
+                    tooltip: Some("Open dependency manifest".into()),
+                    data: None
+                });
+            }
+        }
+
+        Ok(Some(links))
+    }
+
+
+    fn manifest_dependency_at_position(&self, doc_path: &AbsPath, position: lsp::Position) -> Option<DependencyEntry> {
+        let manifest = self.manifests.get(doc_path)?.manifest.clone()?;
+        manifest.dependencies.iter()
+            .find(|e| (e.name_range.start <= position && position <= e.name_range.end) || (e.value_range.start <= position && position <= e.value_range.end))
+            .cloned()
+    }
+
+    /// Finds the content that a dependency entry in a manifest refers to.
+    /// Contents in the graph are looked at first, but dependencies that are not part of it yet can be found as well.
+    async fn resolve_manifest_dependency(&self, manifest_path: &AbsPath, entry: &DependencyEntry) -> Option<Arc<dyn Content>> {
+        let content_path = manifest_path.parent()?;
+        let content_graph = self.content_graph.read().await;
+
+        match &entry.value {
+            DependencyValue::FromPath { path } => {
+                let dep_path = AbsPath::resolve(path, Some(&content_path)).ok()?;
+                content_graph.get_node_by_path(&dep_path)
+                    .map(|n| n.content.clone())
+                    .or_else(|| content_graph.repository_contents().find(|c| c.path() == &dep_path).cloned())
+                    .or_else(|| try_make_content(&dep_path).ok().map(Arc::from))
+            },
+            DependencyValue::FromRepo(_) => {
+                let linked = content_graph
+                    .direct_dependencies(&content_path)
+                    .map(|n| n.content.clone())
+                    .find(|c| c.content_name() == entry.name);
+
+                if linked.is_some() {
+                    return linked;
+                }
+
+                // if the dependency is inactive or ambiguous it won't be in the graph
+                let mut matching = content_graph.repository_contents().filter(|c| c.content_name() == entry.name);
+                match (matching.next(), matching.next()) {
+                    (Some(c), None) => Some(c.clone()),
+                    _ => None
+                }
+            }
+        }
+    }
+}
+
+
+/// Quotes the content name if it can't be used as a bare key
+fn toml_key(name: &str) -> String {
+    if name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-') {
+        name.to_string()
+    } else {
+        format!("\"{}\"", name)
+    }
+}
+
+fn content_description(content: &dyn Content) -> Option<String> {
+    if let Some(proj) = content.as_any().downcast_ref::<ProjectDirectory>() {
+        proj.manifest().content.description.clone()
+    } else if let Some(redkit_proj) = content.as_any().downcast_ref::<RedkitProjectDirectory>() {
+        Some(redkit_proj.manifest().description.clone())
+    } else {
+        None
+    }
+}
+
+fn content_version(content: &dyn Content) -> Option<String> {
+    if let Some(proj) = content.as_any().downcast_ref::<ProjectDirectory>() {
+        Some(proj.manifest().content.version.to_string())
+    } else if let Some(redkit_proj) = content.as_any().downcast_ref::<RedkitProjectDirectory>() {
+        Some(redkit_proj.manifest().version.clone())
+    } else {
+        None
+    }
+}
+
+/// Returns the location of the manifest of given content, if it has one
+fn content_manifest_location(content: &dyn Content) -> Option<(lsp::Url, lsp::Range)> {
+    if let Some(proj) = content.as_any().downcast_ref::<ProjectDirectory>() {
+        Some((proj.manifest_path().to_uri(), proj.manifest().content.name_range))
+    } else if let Some(redkit_proj) = content.as_any().downcast_ref::<RedkitProjectDirectory>() {
+        Some((redkit_proj.manifest_path().to_uri(), lsp::Range::default()))
+    } else {
+        None
+    }
+}
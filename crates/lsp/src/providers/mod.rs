@@ -9,6 +9,22 @@ pub mod selection_range;
 pub mod document_symbols;
 pub mod goto;
 pub mod hover;
+pub mod completion;
+pub mod signature_help;
+pub mod references;
+pub mod rename;
+pub mod workspace_symbols;
+pub mod semantic_tokens;
+pub mod formatting;
+pub mod folding_ranges;
+pub mod inlay_hints;
+pub mod call_hierarchy;
+pub mod type_hierarchy;
+pub mod code_actions;
+pub mod code_lens;
+pub mod document_highlight;
+pub mod manifest;
+pub mod pull_diagnostics;
 
 pub mod custom;
 
@@ -0,0 +1,20 @@
+use abs_path::AbsPath;
+use tower_lsp::lsp_types as lsp;
+use tower_lsp::jsonrpc::Result;
+use crate::Backend;
+
+
+impl Backend {
+    pub async fn diagnostic_impl(&self, params: lsp::DocumentDiagnosticParams) -> Result<lsp::DocumentDiagnosticReportResult> {
+        let doc_path = AbsPath::try_from(params.text_document.uri).unwrap();
+        let report = self.reporter.document_diagnostic_report(&doc_path, params.previous_result_id.as_deref());
+
+        Ok(lsp::DocumentDiagnosticReportResult::Report(report))
+    }
+
+    pub async fn workspace_diagnostic_impl(&self, params: lsp::WorkspaceDiagnosticParams) -> Result<lsp::WorkspaceDiagnosticReportResult> {
+        let report = self.reporter.workspace_diagnostic_report(params.previous_result_ids);
+
+        Ok(lsp::WorkspaceDiagnosticReportResult::Report(report))
+    }
+}
@@ -0,0 +1,103 @@
+use std::collections::HashSet;
+use abs_path::AbsPath;
+use tower_lsp::lsp_types as lsp;
+use tower_lsp::jsonrpc::Result;
+use witcherscript_analysis::symbol_analysis::symbol_path::SymbolPathBuf;
+use witcherscript_analysis::symbol_analysis::symbols::*;
+use crate::Backend;
+use super::common::resolve_text_document_position;
+
+
+impl Backend {
+    pub async fn references_impl(&self, params: lsp::ReferenceParams) -> Result<Option<Vec<lsp::Location>>> {
+        let doc_path = AbsPath::try_from(params.text_document_position.text_document.uri.clone()).unwrap();
+
+        if doc_path.extension().unwrap_or_default() != "ws" {
+            return Ok(None);
+        }
+
+        let content_path;
+        if let Some(path) = self.scripts.get(&doc_path).and_then(|ss| ss.content_info.as_ref().map(|ci| ci.content_path.to_owned())) {
+            content_path = path;
+        }
+        else {
+            return Ok(None);
+        }
+
+        let symtabs = self.symtabs.read().await;
+        let symtabs_marcher = self.march_symbol_tables(&symtabs, &content_path).await;
+
+        let script_state;
+        if let Some(ss) = self.scripts.get(&doc_path) {
+            script_state = ss;
+        } else {
+            return Ok(None);
+        }
+
+        let position_target = resolve_text_document_position(params.text_document_position.position, &script_state, symtabs_marcher.clone());
+        drop(script_state);
+
+        let sympath;
+        if let Some(path) = position_target.and_then(|t| t.target_symbol_path(&symtabs_marcher)) {
+            sympath = path;
+        } else {
+            return Ok(None);
+        }
+
+        let symvar;
+        if let Some(v) = symtabs_marcher.get_symbol(&sympath) {
+            symvar = v;
+        } else {
+            return Ok(None);
+        }
+
+        // struct can also be referenced through its constructor
+        let mut searched_paths: Vec<SymbolPathBuf> = vec![sympath.clone()];
+        if symvar.is_struct() {
+            searched_paths.push(GlobalCallableSymbolPath::new(symvar.name()).into());
+        }
+
+        let mut locations = Vec::new();
+        if params.context.include_declaration {
+            if let Some(loc) = symvar.location() {
+                locations.push(lsp::Location::new(loc.abs_source_path().to_uri(), loc.label_range));
+            }
+        }
+
+        // wrapped or replaced methods can be referenced in contents that the wrapper's content doesn't know about
+        let origin_content_path = self.origin_content_path(&symtabs, &content_path, &sympath).await;
+        drop(symtabs_marcher);
+        drop(symtabs);
+
+        locations.extend(self.find_references(&origin_content_path, &searched_paths).await);
+
+        Ok(Some(locations))
+    }
+
+    /// Finds locations of references to symbols with given paths.
+    /// Symbols are expected to be declared in the content with given path.
+    pub(super) async fn find_references(&self, decl_content_path: &AbsPath, searched_paths: &[SymbolPathBuf]) -> Vec<lsp::Location> {
+        // references to the symbol can only exist in the content that declares it or in contents that depend on it
+        let searched_contents: Vec<AbsPath> =
+            [decl_content_path.to_owned()].into_iter()
+            .chain(self.content_graph
+                .read().await
+                .walk_dependants(decl_content_path)
+                .map(|n| n.content.path().to_owned()))
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+
+        let mut locations = Vec::new();
+        let ref_indices = self.ref_indices.read().await;
+        for ref_index in searched_contents.iter().filter_map(|p| ref_indices.get(p)) {
+            for path in searched_paths.iter() {
+                locations.extend(ref_index
+                    .get_references(path)
+                    .map(|loc| lsp::Location::new(loc.abs_source_path().to_uri(), loc.range)));
+            }
+        }
+
+        locations
+    }
+}
@@ -0,0 +1,291 @@
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+use abs_path::AbsPath;
+use tower_lsp::lsp_types as lsp;
+use tower_lsp::jsonrpc::{self, Result};
+use witcherscript::tokens::Keyword;
+use witcherscript_analysis::symbol_analysis::symbol_path::SymbolPathBuf;
+use witcherscript_analysis::symbol_analysis::symbols::*;
+use crate::Backend;
+use super::common::{resolve_text_document_position, type_hierarchy_paths};
+
+
+impl Backend {
+    pub async fn prepare_rename_impl(&self, params: lsp::TextDocumentPositionParams) -> Result<Option<lsp::PrepareRenameResponse>> {
+        let rename_plan;
+        if let Some(plan) = self.plan_rename(params).await? {
+            rename_plan = plan;
+        } else {
+            return Ok(None);
+        }
+
+        Ok(Some(lsp::PrepareRenameResponse::RangeWithPlaceholder {
+            range: rename_plan.origin_range,
+            placeholder: rename_plan.name
+        }))
+    }
+
+    pub async fn rename_impl(&self, params: lsp::RenameParams) -> Result<Option<lsp::WorkspaceEdit>> {
+        if !is_valid_identifier(&params.new_name) {
+            return Err(jsonrpc::Error::invalid_params(format!("\"{}\" is not a valid identifier", params.new_name)));
+        }
+
+        let rename_plan;
+        if let Some(plan) = self.plan_rename(params.text_document_position).await? {
+            rename_plan = plan;
+        } else {
+            return Ok(None);
+        }
+
+        let symtabs = self.symtabs.read().await;
+        let ref_indices = self.ref_indices.read().await;
+
+        let mut changes: HashMap<lsp::Url, Vec<lsp::TextEdit>> = HashMap::new();
+        let mut push_edit = |uri: lsp::Url, range: lsp::Range| {
+            let edits = changes.entry(uri).or_default();
+            // struct and its constructor share the same declaration
+            if !edits.iter().any(|e| e.range == range) {
+                edits.push(lsp::TextEdit::new(range, params.new_name.clone()));
+            }
+        };
+
+        for (content_path, sympath) in rename_plan.declarations.iter() {
+            if let Some(loc) = symtabs.get(content_path).and_then(|symtab| symtab.get_symbol(sympath)).and_then(|v| v.location()) {
+                push_edit(loc.abs_source_path().to_uri(), loc.label_range);
+            }
+        }
+
+        for ref_index in rename_plan.referencing_contents.iter().filter_map(|p| ref_indices.get(p)) {
+            for sympath in rename_plan.sympaths.iter() {
+                for loc in ref_index.get_references(sympath) {
+                    push_edit(loc.abs_source_path().to_uri(), loc.range);
+                }
+            }
+        }
+
+        Ok(Some(lsp::WorkspaceEdit {
+            changes: Some(changes),
+            ..Default::default()
+        }))
+    }
+
+
+    /// Finds out what needs to be changed when renaming a symbol at a given position.
+    /// Returns an error if the symbol cannot be renamed.
+    async fn plan_rename(&self, params: lsp::TextDocumentPositionParams) -> Result<Option<RenamePlan>> {
+        let doc_path = AbsPath::try_from(params.text_document.uri.clone()).unwrap();
+
+        if doc_path.extension().unwrap_or_default() != "ws" {
+            return Ok(None);
+        }
+
+        let content_path;
+        if let Some(path) = self.scripts.get(&doc_path).and_then(|ss| ss.content_info.as_ref().map(|ci| ci.content_path.to_owned())) {
+            content_path = path;
+        }
+        else {
+            return Ok(None);
+        }
+
+        let symtabs = self.symtabs.read().await;
+        let symtabs_marcher = self.march_symbol_tables(&symtabs, &content_path).await;
+
+        let script_state;
+        if let Some(ss) = self.scripts.get(&doc_path) {
+            script_state = ss;
+        } else {
+            return Ok(None);
+        }
+
+        let target_at_pos = resolve_text_document_position(params.position, &script_state, symtabs_marcher.clone());
+        drop(script_state);
+
+        let position_target;
+        if let Some(target) = target_at_pos {
+            position_target = target;
+        } else {
+            return Ok(None);
+        }
+
+        let mut symvar_at_pos = position_target
+            .target_symbol_path(&symtabs_marcher)
+            .and_then(|sympath| symtabs_marcher.get_symbol(&sympath));
+
+        // renaming the constructor means renaming the struct
+        if let Some(constructor) = symvar_at_pos.and_then(|v| v.try_as_constructor_ref()) {
+            symvar_at_pos = symtabs_marcher.get_symbol(&constructor.parent_type_path);
+        }
+
+        let symvar;
+        if let Some(v) = symvar_at_pos.filter(|v| is_renameable(v)) {
+            symvar = v;
+        } else {
+            return Err(rename_error("This symbol cannot be renamed"));
+        }
+
+        let name = symvar.name().to_string();
+        let mut sympaths: Vec<SymbolPathBuf> = vec![symvar.path().to_owned()];
+
+        if symvar.is_struct() {
+            sympaths.push(GlobalCallableSymbolPath::new(&name).into());
+        }
+
+        // methods need to be renamed together with all methods they override and that override them
+        let is_method = symvar.is_member_func() || symvar.is_event() || symvar.is_member_func_injector() || symvar.is_member_func_replacer() || symvar.is_member_func_wrapper();
+        if let Some(parent_path) = symvar.path().parent().filter(|_| is_method) {
+            let mut base_method_path = symvar.path().to_owned();
+
+            let mut parent_hierarchy = type_hierarchy_paths(parent_path, &symtabs_marcher);
+            if let Some(state) = symtabs_marcher.get_symbol(parent_path).and_then(|v| v.try_as_state_ref()) {
+                parent_hierarchy.extend(type_hierarchy_paths(state.parent_class_path(), &symtabs_marcher));
+            }
+
+            for type_path in parent_hierarchy {
+                let method_path = type_path.join_component(&name, SymbolCategory::Callable);
+                if symtabs_marcher.contains_symbol(&method_path) {
+                    base_method_path = method_path;
+                }
+            }
+
+            let base_content_path = self.declaring_content_path(&symtabs, &content_path, &base_method_path).await;
+            let overrides = self.find_member_overrides(&symtabs, &base_content_path, &base_method_path).await;
+
+            sympaths = [base_method_path].into_iter()
+                .chain(overrides)
+                .collect();
+        }
+
+        drop(symtabs_marcher);
+
+        // contents that can contain any of the declarations: the current content, its dependencies
+        // and the contents dependant on those that declare the renamed symbols
+        let mut decl_content_paths = Vec::new();
+        for sympath in sympaths.iter() {
+            decl_content_paths.push(self.declaring_content_path(&symtabs, &content_path, sympath).await);
+        }
+
+        let content_graph = self.content_graph.read().await;
+        let mut candidate_contents = vec![content_path.clone()];
+        candidate_contents.extend(content_graph.walk_dependencies(&content_path).map(|n| n.content.path().to_owned()));
+        for decl_content_path in decl_content_paths {
+            candidate_contents.extend(content_graph.walk_dependants(&decl_content_path).map(|n| n.content.path().to_owned()));
+        }
+
+        let mut visited_contents = HashSet::new();
+        candidate_contents.retain(|p| visited_contents.insert(p.clone()));
+
+        let mut declarations = Vec::new();
+        let mut referencing_contents = HashSet::new();
+        for candidate_path in candidate_contents {
+            let symtab;
+            if let Some(st) = symtabs.get(&candidate_path) {
+                symtab = st;
+            } else {
+                continue;
+            }
+
+            for sympath in sympaths.iter().filter(|p| symtab.contains_symbol(p)) {
+                let in_workspace = content_graph
+                    .get_node_by_path(&candidate_path)
+                    .map(|n| n.in_workspace)
+                    .unwrap_or(false);
+
+                if !in_workspace {
+                    return Err(rename_error("Cannot rename a symbol that is declared outside of the workspace"));
+                }
+
+                declarations.push((candidate_path.clone(), sympath.clone()));
+                referencing_contents.insert(candidate_path.clone());
+                referencing_contents.extend(content_graph.walk_dependants(&candidate_path).map(|n| n.content.path().to_owned()));
+            }
+        }
+
+        Ok(Some(RenamePlan {
+            origin_range: position_target.range,
+            name,
+            sympaths,
+            declarations,
+            referencing_contents
+        }))
+    }
+}
+
+
+struct RenamePlan {
+    /// Range of the identifier at which the rename was requested
+    origin_range: lsp::Range,
+    /// Current name of the symbol
+    name: String,
+    /// Paths of all symbols that need to be renamed together
+    sympaths: Vec<SymbolPathBuf>,
+    /// Paths of contents paired with paths of renamed symbols that they declare
+    declarations: Vec<(AbsPath, SymbolPathBuf)>,
+    /// Paths of contents that can reference renamed symbols
+    referencing_contents: HashSet<AbsPath>
+}
+
+fn is_renameable(symvar: &SymbolVariant) -> bool {
+    // states are not renamed, because they're usually referred to by name in strings, e.g. in GotoState()
+    matches!(symvar,
+        SymbolVariant::Class(_) |
+        SymbolVariant::Struct(_) |
+        SymbolVariant::Enum(_) |
+        SymbolVariant::EnumVariant(_) |
+        SymbolVariant::GlobalFunc(_) |
+        SymbolVariant::MemberFunc(_) |
+        SymbolVariant::Event(_) |
+        SymbolVariant::MemberFuncInjector(_) |
+        SymbolVariant::MemberFuncReplacer(_) |
+        SymbolVariant::GlobalFuncReplacer(_) |
+        SymbolVariant::MemberFuncWrapper(_) |
+        SymbolVariant::FuncParam(_) |
+        SymbolVariant::GlobalVar(_) |
+        SymbolVariant::MemberVar(_) |
+        SymbolVariant::Autobind(_) |
+        SymbolVariant::LocalVar(_) |
+        SymbolVariant::MemberVarInjector(_)
+    )
+}
+
+fn is_valid_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().map(|c| c.is_ascii_alphabetic() || c == '_').unwrap_or(false)
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && Keyword::from_str(name).is_err()
+}
+
+fn rename_error(message: &str) -> jsonrpc::Error {
+    jsonrpc::Error {
+        code: jsonrpc::ErrorCode::InvalidRequest,
+        message: message.to_string().into(),
+        data: None
+    }
+}
+
+
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+
+    #[test]
+    fn test_is_valid_identifier() {
+        assert!(is_valid_identifier("foo"));
+        assert!(is_valid_identifier("_foo"));
+        assert!(is_valid_identifier("Foo_Bar2"));
+        assert!(is_valid_identifier("Class"));
+
+        assert!(!is_valid_identifier(""));
+        assert!(!is_valid_identifier("2foo"));
+        assert!(!is_valid_identifier("foo bar"));
+        assert!(!is_valid_identifier("foo-bar"));
+
+        assert!(!is_valid_identifier("class"));
+        assert!(!is_valid_identifier("function"));
+        assert!(!is_valid_identifier("thePlayer"));
+        assert!(!is_valid_identifier("virtual_parent"));
+        assert!(!is_valid_identifier("NULL"));
+    }
+}
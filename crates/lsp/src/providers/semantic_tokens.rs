@@ -0,0 +1,595 @@
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+use abs_path::AbsPath;
+use tower_lsp::lsp_types as lsp;
+use tower_lsp::jsonrpc::Result;
+use witcherscript::{ast::*, script_document::ScriptDocument, tokens::*};
+use witcherscript::attribs::*;
+use witcherscript_analysis::symbol_analysis::symbol_path::SymbolPathBuf;
+use witcherscript_analysis::symbol_analysis::symbol_table::marcher::SymbolTableMarcher;
+use witcherscript_analysis::symbol_analysis::symbols::*;
+use witcherscript_analysis::symbol_analysis::unqualified_name_lookup::*;
+use witcherscript_analysis::utils::*;
+use witcherscript_project::content::VANILLA_CONTENT_NAME;
+use crate::{Backend, ScriptState};
+use super::common::{PositionTarget, PositionTargetKind};
+
+
+/// Types of tokens reported by the server. Tokens refer to them by their index in this array.
+pub const SEMANTIC_TOKEN_TYPES: [lsp::SemanticTokenType; 13] = [
+    lsp::SemanticTokenType::TYPE,
+    lsp::SemanticTokenType::CLASS,
+    lsp::SemanticTokenType::new("state"),
+    lsp::SemanticTokenType::STRUCT,
+    lsp::SemanticTokenType::ENUM,
+    lsp::SemanticTokenType::ENUM_MEMBER,
+    lsp::SemanticTokenType::FUNCTION,
+    lsp::SemanticTokenType::METHOD,
+    lsp::SemanticTokenType::EVENT,
+    lsp::SemanticTokenType::PARAMETER,
+    lsp::SemanticTokenType::VARIABLE,
+    lsp::SemanticTokenType::PROPERTY,
+    lsp::SemanticTokenType::DECORATOR
+];
+
+/// Modifiers of tokens reported by the server. Tokens refer to them by bit flags, where each bit corresponds to the index in this array.
+pub const SEMANTIC_TOKEN_MODIFIERS: [lsp::SemanticTokenModifier; 6] = [
+    lsp::SemanticTokenModifier::DECLARATION,
+    lsp::SemanticTokenModifier::STATIC,
+    lsp::SemanticTokenModifier::READONLY,
+    lsp::SemanticTokenModifier::DEPRECATED,
+    lsp::SemanticTokenModifier::DEFAULT_LIBRARY,
+    lsp::SemanticTokenModifier::ABSTRACT
+];
+
+const TOKEN_TYPE_TYPE: u32 = 0;
+const TOKEN_TYPE_CLASS: u32 = 1;
+const TOKEN_TYPE_STATE: u32 = 2;
+const TOKEN_TYPE_STRUCT: u32 = 3;
+const TOKEN_TYPE_ENUM: u32 = 4;
+const TOKEN_TYPE_ENUM_MEMBER: u32 = 5;
+const TOKEN_TYPE_FUNCTION: u32 = 6;
+const TOKEN_TYPE_METHOD: u32 = 7;
+const TOKEN_TYPE_EVENT: u32 = 8;
+const TOKEN_TYPE_PARAMETER: u32 = 9;
+const TOKEN_TYPE_VARIABLE: u32 = 10;
+const TOKEN_TYPE_PROPERTY: u32 = 11;
+const TOKEN_TYPE_DECORATOR: u32 = 12;
+
+const TOKEN_MODIFIER_DECLARATION: u32 = 1 << 0;
+const TOKEN_MODIFIER_STATIC: u32 = 1 << 1;
+const TOKEN_MODIFIER_READONLY: u32 = 1 << 2;
+const TOKEN_MODIFIER_DEPRECATED: u32 = 1 << 3;
+const TOKEN_MODIFIER_DEFAULT_LIBRARY: u32 = 1 << 4;
+const TOKEN_MODIFIER_ABSTRACT: u32 = 1 << 5;
+
+
+impl Backend {
+    pub async fn semantic_tokens_full_impl(&self, params: lsp::SemanticTokensParams) -> Result<Option<lsp::SemanticTokensResult>> {
+        let doc_path = AbsPath::try_from(params.text_document.uri.clone()).unwrap();
+
+        let tokens;
+        if let Some(t) = self.compute_semantic_tokens(&doc_path).await {
+            tokens = t;
+        } else {
+            return Ok(None);
+        }
+
+        let result = lsp::SemanticTokens {
+            result_id: Some(self.semantic_tokens_cache.next_result_id()),
+            data: tokens
+        };
+
+        self.semantic_tokens_cache.insert(doc_path, result.clone());
+
+        Ok(Some(lsp::SemanticTokensResult::Tokens(result)))
+    }
+
+    pub async fn semantic_tokens_full_delta_impl(&self, params: lsp::SemanticTokensDeltaParams) -> Result<Option<lsp::SemanticTokensFullDeltaResult>> {
+        let doc_path = AbsPath::try_from(params.text_document.uri.clone()).unwrap();
+
+        let tokens;
+        if let Some(t) = self.compute_semantic_tokens(&doc_path).await {
+            tokens = t;
+        } else {
+            return Ok(None);
+        }
+
+        let result_id = self.semantic_tokens_cache.next_result_id();
+        let previous = self.semantic_tokens_cache.insert(doc_path, lsp::SemanticTokens {
+            result_id: Some(result_id.clone()),
+            data: tokens.clone()
+        });
+
+        // the client can only apply the delta on top of the same tokens that we've got cached
+        let previous = previous.filter(|prev| prev.result_id.as_ref() == Some(&params.previous_result_id));
+        if let Some(previous) = previous {
+            Ok(Some(lsp::SemanticTokensFullDeltaResult::TokensDelta(lsp::SemanticTokensDelta {
+                result_id: Some(result_id),
+                edits: semantic_tokens_edits(&previous.data, &tokens)
+            })))
+        } else {
+            Ok(Some(lsp::SemanticTokensFullDeltaResult::Tokens(lsp::SemanticTokens {
+                result_id: Some(result_id),
+                data: tokens
+            })))
+        }
+    }
+
+
+    async fn compute_semantic_tokens(&self, doc_path: &AbsPath) -> Option<Vec<lsp::SemanticToken>> {
+        if doc_path.extension().unwrap_or_default() != "ws" {
+            return None;
+        }
+
+        let content_path = self.scripts.get(doc_path).and_then(|ss| ss.content_info.as_ref().map(|ci| ci.content_path.to_owned()))?;
+
+        let default_library_contents: Vec<AbsPath> = self.content_graph
+            .read().await
+            .nodes()
+            .filter(|n| n.is_native || n.content.content_name() == VANILLA_CONTENT_NAME)
+            .map(|n| n.content.path().to_owned())
+            .collect();
+
+        let symtabs = self.symtabs.read().await;
+        let symtabs_marcher = self.march_symbol_tables(&symtabs, &content_path).await;
+
+        let default_library_roots: Vec<AbsPath> = default_library_contents.iter()
+            .filter_map(|p| symtabs.get(p))
+            .map(|symtab| symtab.script_root().to_owned())
+            .collect();
+
+        let script_state = self.scripts.get(doc_path)?;
+        let targets = collect_token_targets(&script_state, symtabs_marcher.clone());
+        drop(script_state);
+
+        let mut classified: HashMap<SymbolPathBuf, Option<(u32, u32)>> = HashMap::new();
+        let mut tokens = Vec::with_capacity(targets.len());
+        for target in targets {
+            let is_declaration = matches!(target.kind,
+                PositionTargetKind::StateDeclarationNameIdentifier |
+                PositionTargetKind::DataDeclarationNameIdentifier(_) |
+                PositionTargetKind::CallableDeclarationNameIdentifier
+            );
+
+            let sympath;
+            if let Some(p) = target.target_symbol_path(&symtabs_marcher) {
+                sympath = p;
+            } else {
+                continue;
+            }
+
+            let classification = if let Some(c) = classified.get(&sympath) {
+                *c
+            } else {
+                let c = symtabs_marcher.get_symbol(&sympath).and_then(|symvar| {
+                    let typ = semantic_token_type(symvar)?;
+                    let mut modifiers = semantic_token_modifiers(symvar);
+
+                    let is_default_library = symvar.location()
+                        .map(|loc| default_library_roots.iter().any(|root| root == loc.scripts_root.as_ref()))
+                        .unwrap_or(true);
+                    if is_default_library {
+                        modifiers |= TOKEN_MODIFIER_DEFAULT_LIBRARY;
+                    }
+
+                    if symvar.doc_comment().map(is_marked_deprecated).unwrap_or(false) {
+                        modifiers |= TOKEN_MODIFIER_DEPRECATED;
+                    }
+
+                    Some((typ, modifiers))
+                });
+
+                classified.insert(sympath, c);
+                c
+            };
+
+            if let Some((typ, mut modifiers)) = classification {
+                if is_declaration {
+                    modifiers |= TOKEN_MODIFIER_DECLARATION;
+                }
+
+                tokens.push((target.range, typ, modifiers));
+            }
+        }
+
+        Some(encode_semantic_tokens(tokens))
+    }
+}
+
+
+fn semantic_token_type(symvar: &SymbolVariant) -> Option<u32> {
+    let typ = match symvar {
+        SymbolVariant::Class(_) => TOKEN_TYPE_CLASS,
+        SymbolVariant::State(_) => TOKEN_TYPE_STATE,
+        SymbolVariant::Struct(_) => TOKEN_TYPE_STRUCT,
+        SymbolVariant::Enum(_) => TOKEN_TYPE_ENUM,
+        SymbolVariant::Array(_) => TOKEN_TYPE_TYPE,
+        SymbolVariant::Primitive(_) => TOKEN_TYPE_TYPE,
+        SymbolVariant::ArrayFunc(_) => TOKEN_TYPE_METHOD,
+        SymbolVariant::ArrayFuncParam(_) => TOKEN_TYPE_PARAMETER,
+        SymbolVariant::GlobalFunc(_) => TOKEN_TYPE_FUNCTION,
+        SymbolVariant::MemberFunc(_) => TOKEN_TYPE_METHOD,
+        SymbolVariant::Event(_) => TOKEN_TYPE_EVENT,
+        SymbolVariant::Constructor(_) => TOKEN_TYPE_STRUCT,
+        SymbolVariant::MemberFuncInjector(_) => TOKEN_TYPE_METHOD,
+        SymbolVariant::MemberFuncReplacer(_) => TOKEN_TYPE_METHOD,
+        SymbolVariant::GlobalFuncReplacer(_) => TOKEN_TYPE_FUNCTION,
+        SymbolVariant::MemberFuncWrapper(_) => TOKEN_TYPE_METHOD,
+        SymbolVariant::WrappedMethod(_) => TOKEN_TYPE_DECORATOR,
+        SymbolVariant::EnumVariant(_) => TOKEN_TYPE_ENUM_MEMBER,
+        SymbolVariant::FuncParam(_) => TOKEN_TYPE_PARAMETER,
+        SymbolVariant::GlobalVar(_) => TOKEN_TYPE_VARIABLE,
+        SymbolVariant::MemberVar(_) => TOKEN_TYPE_PROPERTY,
+        SymbolVariant::Autobind(_) => TOKEN_TYPE_PROPERTY,
+        SymbolVariant::LocalVar(_) => TOKEN_TYPE_VARIABLE,
+        SymbolVariant::MemberVarInjector(_) => TOKEN_TYPE_PROPERTY,
+        // keywords are already taken care of by syntax highlighting
+        SymbolVariant::ThisVar(_) |
+        SymbolVariant::SuperVar(_) |
+        SymbolVariant::StateSuperVar(_) |
+        SymbolVariant::ParentVar(_) |
+        SymbolVariant::VirtualParentVar(_) => return None
+    };
+
+    Some(typ)
+}
+
+fn semantic_token_modifiers(symvar: &SymbolVariant) -> u32 {
+    match symvar {
+        SymbolVariant::Class(s) if s.specifiers.contains(ClassSpecifier::Abstract) => TOKEN_MODIFIER_ABSTRACT,
+        SymbolVariant::State(s) if s.specifiers.contains(StateSpecifier::Abstract) => TOKEN_MODIFIER_ABSTRACT,
+        // global symbols don't need any object to be accessed
+        SymbolVariant::GlobalFunc(_) |
+        SymbolVariant::GlobalFuncReplacer(_) |
+        SymbolVariant::GlobalVar(_) => TOKEN_MODIFIER_STATIC,
+        SymbolVariant::MemberVar(s) if s.specifiers.contains(MemberVarSpecifier::Const) => TOKEN_MODIFIER_READONLY,
+        _ => 0
+    }
+}
+
+/// Whether a line of the documentation starts with an explicit marker, i.e. `@deprecated` or `Deprecated:`
+fn is_marked_deprecated(doc_comment: &str) -> bool {
+    doc_comment.lines().any(|line| {
+        let line = line.trim_start().to_lowercase();
+        line.starts_with("deprecated:")
+        || line.strip_prefix("@deprecated").map(|rest| !rest.starts_with(|c: char| c.is_alphanumeric() || c == '_')).unwrap_or(false)
+    })
+}
+
+/// Converts tokens into the relative format expected by the protocol
+fn encode_semantic_tokens(mut tokens: Vec<(lsp::Range, u32, u32)>) -> Vec<lsp::SemanticToken> {
+    tokens.sort_by_key(|(range, _, _)| (range.start.line, range.start.character));
+    tokens.dedup_by_key(|(range, _, _)| range.start);
+
+    let mut encoded = Vec::with_capacity(tokens.len());
+    let mut prev_pos = lsp::Position::new(0, 0);
+    for (range, typ, modifiers) in tokens {
+        // multiline tokens are not supported by every client
+        if range.start.line != range.end.line {
+            continue;
+        }
+
+        let delta_line = range.start.line - prev_pos.line;
+        let delta_start = if delta_line == 0 {
+            range.start.character - prev_pos.character
+        } else {
+            range.start.character
+        };
+
+        encoded.push(lsp::SemanticToken {
+            delta_line,
+            delta_start,
+            length: range.end.character - range.start.character,
+            token_type: typ,
+            token_modifiers_bitset: modifiers
+        });
+
+        prev_pos = range.start;
+    }
+
+    encoded
+}
+
+/// Produces a single edit that replaces the part of previous tokens that differs from the current ones
+fn semantic_tokens_edits(previous: &[lsp::SemanticToken], current: &[lsp::SemanticToken]) -> Vec<lsp::SemanticTokensEdit> {
+    let common_prefix = previous.iter()
+        .zip(current.iter())
+        .take_while(|(p, c)| p == c)
+        .count();
+
+    let common_suffix = previous[common_prefix..].iter().rev()
+        .zip(current[common_prefix..].iter().rev())
+        .take_while(|(p, c)| p == c)
+        .count();
+
+    let deleted = &previous[common_prefix..previous.len() - common_suffix];
+    let inserted = &current[common_prefix..current.len() - common_suffix];
+    if deleted.is_empty() && inserted.is_empty() {
+        return Vec::new();
+    }
+
+    // edits operate on the flattened array of integers, each token is made of 5 of them
+    vec![lsp::SemanticTokensEdit {
+        start: common_prefix as u32 * 5,
+        delete_count: deleted.len() as u32 * 5,
+        data: Some(inserted.to_vec())
+    }]
+}
+
+
+/// A node visitor that gathers all identifiers in the script that can be given a semantic token.
+/// Expects to work after SymbolPathBuilder and UnqualifiedNameLookupBuilder in visitor chain.
+struct TokenTargetCollector<'a> {
+    doc: &'a ScriptDocument,
+    symtab_marcher: SymbolTableMarcher<'a>,
+    sympath_builder_payload: Rc<RefCell<SymbolPathBuilderPayload>>,
+    unl_builder_payload: Rc<RefCell<UnqualifiedNameLookup>>,
+    targets: Vec<PositionTarget>
+}
+
+impl TokenTargetCollector<'_> {
+    fn push(&mut self, range: lsp::Range, kind: PositionTargetKind) {
+        self.targets.push(PositionTarget {
+            range,
+            kind,
+            sympath_ctx: self.sympath_builder_payload.borrow().current_sympath.clone()
+        });
+    }
+
+    fn push_type_ident(&mut self, n: &IdentifierNode) {
+        let name = n.value(self.doc);
+        if name != ArrayTypeSymbol::TYPE_NAME {
+            self.push(n.range(), PositionTargetKind::TypeIdentifier(name.to_string()));
+        }
+    }
+
+    fn push_data_decl_ident(&mut self, n: &IdentifierNode) {
+        self.push(n.range(), PositionTargetKind::DataDeclarationNameIdentifier(n.value(self.doc).to_string()));
+    }
+
+    fn push_expression_ident(&mut self, n: &IdentifierNode, expr: ExpressionNode, ctx: TraversalContext) {
+        let sympath = evaluate_expression(
+            expr, ctx,
+            self.doc,
+            self.symtab_marcher.clone(),
+            self.sympath_builder_payload.clone(),
+            self.unl_builder_payload.clone()
+        );
+
+        self.push(n.range(), PositionTargetKind::ExpressionIdentifier(sympath));
+    }
+
+    fn push_type_annotation(&mut self, n: &TypeAnnotationNode) {
+        self.push_type_ident(&n.type_name());
+
+        if let Some(type_arg) = n.type_arg() {
+            self.push_type_annotation(&type_arg);
+        }
+    }
+
+    fn push_annotation(&mut self, n: &AnnotationNode) {
+        if let Some(arg) = n.arg() {
+            self.push_type_ident(&arg);
+        }
+    }
+}
+
+impl SyntaxNodeVisitor for TokenTargetCollector<'_> {
+    fn traversal_policy_default(&self) -> bool {
+        true
+    }
+
+
+    fn visit_class_decl(&mut self, n: &ClassDeclarationNode) -> ClassDeclarationTraversalPolicy {
+        self.push_type_ident(&n.name());
+
+        if let Some(base) = n.base() {
+            self.push_type_ident(&base);
+        }
+
+        TraversalPolicy::default_to(true)
+    }
+
+    fn visit_state_decl(&mut self, n: &StateDeclarationNode) -> StateDeclarationTraversalPolicy {
+        self.push(n.name().range(), PositionTargetKind::StateDeclarationNameIdentifier);
+        self.push_type_ident(&n.parent());
+
+        if let Some(base) = n.base() {
+            self.push(base.range(), PositionTargetKind::StateDeclarationBaseIdentifier);
+        }
+
+        TraversalPolicy::default_to(true)
+    }
+
+    fn visit_struct_decl(&mut self, n: &StructDeclarationNode) -> StructDeclarationTraversalPolicy {
+        self.push_type_ident(&n.name());
+
+        TraversalPolicy::default_to(true)
+    }
+
+    fn visit_enum_decl(&mut self, n: &EnumDeclarationNode) -> EnumDeclarationTraversalPolicy {
+        self.push_type_ident(&n.name());
+
+        TraversalPolicy::default_to(true)
+    }
+
+    fn visit_enum_variant_decl(&mut self, n: &EnumVariantDeclarationNode) {
+        self.push_data_decl_ident(&n.name());
+    }
+
+    fn visit_global_var_decl(&mut self, n: &MemberVarDeclarationNode) {
+        self.push_type_annotation(&n.var_type());
+
+        let mut class_path = SymbolPathBuf::empty();
+        if let Some(annot) = n.annotation() {
+            self.push_annotation(&annot);
+
+            if let Some(arg) = annot.arg() {
+                class_path = SymbolPathBuf::new(&arg.value(self.doc), SymbolCategory::Type);
+            }
+        }
+
+        for name in n.names() {
+            self.targets.push(PositionTarget {
+                range: name.range(),
+                kind: PositionTargetKind::DataDeclarationNameIdentifier(name.value(self.doc).to_string()),
+                sympath_ctx: class_path.clone()
+            });
+        }
+    }
+
+    fn visit_member_var_decl(&mut self, n: &MemberVarDeclarationNode, _: &TraversalContextStack) {
+        self.push_type_annotation(&n.var_type());
+
+        for name in n.names() {
+            self.push_data_decl_ident(&name);
+        }
+    }
+
+    fn visit_autobind_decl(&mut self, n: &AutobindDeclarationNode, _: &TraversalContextStack) {
+        self.push_data_decl_ident(&n.name());
+        self.push_type_annotation(&n.autobind_type());
+    }
+
+    fn visit_member_default_val(&mut self, n: &MemberDefaultValueNode, ctx: &TraversalContextStack) -> MemberDefaultValueTraversalPolicy {
+        let member = n.member();
+        self.push_expression_ident(&member, member.clone().into(), ctx.top());
+
+        TraversalPolicy::default_to(true)
+    }
+
+    fn visit_member_defaults_block_assignment(&mut self, n: &MemberDefaultsBlockAssignmentNode, ctx: &TraversalContextStack) -> MemberDefaultValueTraversalPolicy {
+        let member = n.member();
+        self.push_expression_ident(&member, member.clone().into(), ctx.top());
+
+        TraversalPolicy::default_to(true)
+    }
+
+    fn visit_member_hint(&mut self, n: &MemberHintNode, ctx: &TraversalContextStack) {
+        let member = n.member();
+        self.push_expression_ident(&member, member.clone().into(), ctx.top());
+    }
+
+    fn visit_global_func_decl(&mut self, n: &FunctionDeclarationNode) -> FunctionDeclarationTraversalPolicy {
+        self.push(n.name().range(), PositionTargetKind::CallableDeclarationNameIdentifier);
+
+        if let Some(annot) = n.annotation() {
+            self.push_annotation(&annot);
+        }
+
+        if let Some(rt) = n.return_type() {
+            self.push_type_annotation(&rt);
+        }
+
+        TraversalPolicy::default_to(true)
+    }
+
+    fn visit_member_func_decl(&mut self, n: &FunctionDeclarationNode, _: &TraversalContextStack) -> FunctionDeclarationTraversalPolicy {
+        self.push(n.name().range(), PositionTargetKind::CallableDeclarationNameIdentifier);
+
+        if let Some(rt) = n.return_type() {
+            self.push_type_annotation(&rt);
+        }
+
+        TraversalPolicy::default_to(true)
+    }
+
+    fn visit_event_decl(&mut self, n: &EventDeclarationNode, _: &TraversalContextStack) -> EventDeclarationTraversalPolicy {
+        self.push(n.name().range(), PositionTargetKind::CallableDeclarationNameIdentifier);
+
+        if let Some(rt) = n.return_type() {
+            self.push_type_annotation(&rt);
+        }
+
+        TraversalPolicy::default_to(true)
+    }
+
+    fn visit_func_param_group(&mut self, n: &FunctionParameterGroupNode, _: &TraversalContextStack) {
+        self.push_type_annotation(&n.param_type());
+
+        for name in n.names() {
+            self.push_data_decl_ident(&name);
+        }
+    }
+
+    fn visit_local_var_decl_stmt(&mut self, n: &LocalVarDeclarationNode, _: &TraversalContextStack) -> VarDeclarationTraversalPolicy {
+        self.push_type_annotation(&n.var_type());
+
+        for name in n.names() {
+            self.push_data_decl_ident(&name);
+        }
+
+        TraversalPolicy::default_to(true)
+    }
+
+
+    fn visit_identifier_expr(&mut self, n: &IdentifierNode, ctx: &TraversalContextStack) {
+        self.push_expression_ident(n, n.clone().into(), ctx.top());
+    }
+
+    fn visit_member_access_expr(&mut self, n: &MemberAccessExpressionNode, ctx: &TraversalContextStack) -> MemberFieldExpressionTraversalPolicy {
+        self.push_expression_ident(&n.member(), n.clone().into(), ctx.top());
+
+        TraversalPolicy::default_to(true)
+    }
+
+    fn visit_new_expr(&mut self, n: &NewExpressionNode, _: &TraversalContextStack) -> NewExpressionTraversalPolicy {
+        self.push_type_ident(&n.class());
+
+        TraversalPolicy::default_to(true)
+    }
+
+    fn visit_type_cast_expr(&mut self, n: &TypeCastExpressionNode, _: &TraversalContextStack) -> TypeCastExpressionTraversalPolicy {
+        self.push_type_ident(&n.target_type());
+
+        TraversalPolicy::default_to(true)
+    }
+}
+
+impl SyntaxNodeVisitorChainLink for TokenTargetCollector<'_> {}
+
+
+fn collect_token_targets<'a>(script_state: &'a ScriptState, symtab_marcher: SymbolTableMarcher<'a>) -> Vec<PositionTarget> {
+    let (sympath_builder, sympath_builder_payload) = SymbolPathBuilder::new(&script_state.buffer);
+    let (unl_builder, unl_payload) = UnqualifiedNameLookupBuilder::new(&script_state.buffer, sympath_builder_payload.clone(), symtab_marcher.clone());
+    let collector = Rc::new(RefCell::new(TokenTargetCollector {
+        doc: &script_state.buffer,
+        symtab_marcher,
+        sympath_builder_payload,
+        unl_builder_payload: unl_payload,
+        targets: Vec::new()
+    }));
+
+    let mut chain = SyntaxNodeVisitorChain::new()
+        .link(sympath_builder)
+        .link(unl_builder)
+        .link_rc(collector.clone());
+
+    script_state.script.visit_nodes(&mut chain);
+
+    let mut collector_ref = collector.borrow_mut();
+    std::mem::take(&mut collector_ref.targets)
+}
+
+
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+
+    #[test]
+    fn test_deprecated_marker() {
+        assert!(is_marked_deprecated("@deprecated"));
+        assert!(is_marked_deprecated("@deprecated use Bar instead"));
+        assert!(is_marked_deprecated("Does things.\n@Deprecated"));
+        assert!(is_marked_deprecated("Deprecated: use Bar instead"));
+        assert!(is_marked_deprecated("Does things.\n  DEPRECATED: use Bar instead"));
+
+        assert!(!is_marked_deprecated("Replaces the deprecated Foo"));
+        assert!(!is_marked_deprecated("Deprecated since 1.31, see Bar"));
+        assert!(!is_marked_deprecated("@deprecatedFoo"));
+        assert!(!is_marked_deprecated("Use this instead of Foo, which is deprecated: it leaks"));
+    }
+}
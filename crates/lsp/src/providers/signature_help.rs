@@ -0,0 +1,309 @@
+use std::{cell::RefCell, rc::Rc};
+use abs_path::AbsPath;
+use tower_lsp::lsp_types as lsp;
+use tower_lsp::jsonrpc::Result;
+use witcherscript::{ast::*, script_document::ScriptDocument};
+use witcherscript_analysis::symbol_analysis::symbol_path::SymbolPathBuf;
+use witcherscript_analysis::symbol_analysis::symbol_table::marcher::SymbolTableMarcher;
+use witcherscript_analysis::symbol_analysis::symbols::*;
+use witcherscript_analysis::symbol_analysis::unqualified_name_lookup::*;
+use witcherscript_analysis::utils::*;
+use crate::{Backend, ScriptState};
+use super::hover::RenderPartialTooltip;
+
+
+impl Backend {
+    pub async fn signature_help_impl(&self, params: lsp::SignatureHelpParams) -> Result<Option<lsp::SignatureHelp>> {
+        let doc_path = AbsPath::try_from(params.text_document_position_params.text_document.uri.clone()).unwrap();
+
+        if doc_path.extension().unwrap_or_default() != "ws" {
+            return Ok(None);
+        }
+
+        let content_path;
+        if let Some(path) = self.scripts.get(&doc_path).and_then(|ss| ss.content_info.as_ref().map(|ci| ci.content_path.to_owned())) {
+            content_path = path;
+        }
+        else {
+            return Ok(None);
+        }
+
+        let symtabs = self.symtabs.read().await;
+        let symtabs_marcher = self.march_symbol_tables(&symtabs, &content_path).await;
+
+        let script_state;
+        if let Some(ss) = self.scripts.get(&doc_path) {
+            script_state = ss;
+        } else {
+            return Ok(None);
+        }
+
+        let call_at_pos = resolve_call_at_position(params.text_document_position_params.position, &script_state, symtabs_marcher.clone());
+        drop(script_state);
+
+        let call;
+        if let Some(c) = call_at_pos {
+            call = c;
+        } else {
+            return Ok(None);
+        }
+
+        let signature = resolve_callee(&call.callee_path, &symtabs_marcher).and_then(|callee| signature_information(&callee));
+
+        Ok(signature.map(|signature| {
+            let param_count = signature.parameters.as_ref().map(|params| params.len()).unwrap_or(0);
+            // out of range index is supposed to mean that no parameter is active,
+            // but some clients highlight the last parameter instead, so better not to send it at all
+            let active_parameter = (call.active_param < param_count).then_some(call.active_param as u32);
+
+            lsp::SignatureHelp {
+                signatures: vec![signature],
+                active_signature: Some(0),
+                active_parameter
+            }
+        }))
+    }
+}
+
+
+struct CallAtPosition {
+    /// Path to the callable symbol
+    callee_path: SymbolPathBuf,
+    /// Index of the argument in which the position is placed
+    active_param: usize
+}
+
+/// A node visitor that finds the innermost function call, which argument list contains a given position.
+/// Expects to work after PositionFilter, SymbolPathBuilder and UnqualifiedNameLookupBuilder in visitor chain.
+struct CallResolver<'a> {
+    pos: lsp::Position,
+    doc: &'a ScriptDocument,
+    symtab_marcher: SymbolTableMarcher<'a>,
+    sympath_builder_payload: Rc<RefCell<SymbolPathBuilderPayload>>,
+    unl_builder_payload: Rc<RefCell<UnqualifiedNameLookup>>,
+    found_call: Option<CallAtPosition>
+}
+
+impl<'a> CallResolver<'a> {
+    fn new_rc(
+        pos: lsp::Position,
+        doc: &'a ScriptDocument,
+        symtab_marcher: SymbolTableMarcher<'a>,
+        sympath_builder_payload: Rc<RefCell<SymbolPathBuilderPayload>>,
+        unl_builder_payload: Rc<RefCell<UnqualifiedNameLookup>>
+    ) -> Rc<RefCell<Self>> {
+        Rc::new(RefCell::new(Self {
+            pos,
+            doc,
+            symtab_marcher,
+            sympath_builder_payload,
+            unl_builder_payload,
+            found_call: None
+        }))
+    }
+
+    /// Returns the index of the argument at the position if the position is between the parentheses of the call
+    fn active_arg_index(&self, n: &FunctionCallExpressionNode) -> Option<usize> {
+        let call_range = n.range();
+        let func_end = n.func().range().end;
+        if self.pos <= func_end || self.pos > call_range.end {
+            return None;
+        }
+
+        let is_closed = self.doc.text_at(lsp::Range::new(func_end, call_range.end)).trim_end().ends_with(')');
+        if is_closed && self.pos == call_range.end {
+            return None;
+        }
+
+        let args_text = self.doc.text_at(lsp::Range::new(func_end, self.pos));
+        arg_index_at_end(&args_text)
+    }
+}
+
+/// Given the text of a call starting after the called expression,
+/// returns the index of the argument that the end of the text is in.
+/// Returns None if the argument list hasn't been opened yet.
+fn arg_index_at_end(call_text: &str) -> Option<usize> {
+    let mut chars = call_text.chars().skip_while(|c| *c != '(');
+    chars.next()?;
+
+    // count commas that are not a part of nested expressions
+    let mut depth = 0;
+    let mut quote = None;
+    let mut index = 0;
+    for c in chars {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {},
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '(' | '[') => depth += 1,
+            (None, ')' | ']') => depth -= 1,
+            (None, ',') if depth == 0 => index += 1,
+            _ => {}
+        }
+    }
+
+    Some(index)
+}
+
+impl SyntaxNodeVisitor for CallResolver<'_> {
+    fn traversal_policy_default(&self) -> bool {
+        true
+    }
+
+    fn visit_func_call_expr(&mut self, n: &FunctionCallExpressionNode, _: &TraversalContextStack) -> FunctionCallExpressionTraversalPolicy {
+        // nested calls are visited later, so the innermost call will overwrite the outer ones
+        if let Some(active_param) = self.active_arg_index(n) {
+            let callee_path = evaluate_expression(
+                n.func(), TraversalContext::FunctionCallExpressionFunc,
+                self.doc,
+                self.symtab_marcher.clone(),
+                self.sympath_builder_payload.clone(),
+                self.unl_builder_payload.clone()
+            );
+
+            self.found_call = Some(CallAtPosition {
+                callee_path,
+                active_param
+            });
+        }
+
+        TraversalPolicy::default_to(true)
+    }
+}
+
+impl SyntaxNodeVisitorChainLink for CallResolver<'_> {}
+
+
+fn resolve_call_at_position<'a>(position: lsp::Position, script_state: &'a ScriptState, symtab_marcher: SymbolTableMarcher<'a>) -> Option<CallAtPosition> {
+    let (mut pos_filter, _) = PositionFilter::new(position);
+    pos_filter.filter_statements = false;
+
+    let (sympath_builder, sympath_builder_payload) = SymbolPathBuilder::new(&script_state.buffer);
+    let (unl_builder, unl_payload) = UnqualifiedNameLookupBuilder::new(&script_state.buffer, sympath_builder_payload.clone(), symtab_marcher.clone());
+    let resolver = CallResolver::new_rc(
+        position,
+        &script_state.buffer,
+        symtab_marcher,
+        sympath_builder_payload.clone(),
+        unl_payload.clone()
+    );
+
+    let mut chain = SyntaxNodeVisitorChain::new()
+        .link(pos_filter)
+        .link(sympath_builder)
+        .link(unl_builder)
+        .link_rc(resolver.clone());
+
+    script_state.script.visit_nodes(&mut chain);
+
+    let mut resolver_ref = resolver.borrow_mut();
+    resolver_ref.found_call.take()
+}
+
+
+fn signature_information(callee: &Callee) -> Option<lsp::SignatureInformation> {
+    let symvar = callee.symvar;
+    let return_type_name = match symvar {
+        SymbolVariant::GlobalFunc(s) => s.return_type_name(),
+        SymbolVariant::MemberFunc(s) => s.return_type_name(),
+        SymbolVariant::Event(_) => "void",
+        SymbolVariant::MemberFuncInjector(s) => s.return_type_name(),
+        SymbolVariant::MemberFuncReplacer(s) => s.return_type_name(),
+        SymbolVariant::GlobalFuncReplacer(s) => s.return_type_name(),
+        SymbolVariant::MemberFuncWrapper(s) => s.return_type_name(),
+        SymbolVariant::ArrayFunc(s) => if s.was_return_type_generic { "T" } else { s.return_type_name() },
+        _ => return None
+    };
+
+    let params = callee.params.iter()
+        .map(|param| {
+            let mut buf = String::new();
+            param.render_partial(&mut buf);
+            buf
+        })
+        .collect::<Vec<_>>();
+
+    let mut label = String::new();
+    label.push_str(symvar.name());
+    label.push('(');
+
+    let mut param_infos = Vec::with_capacity(params.len());
+    for (i, param) in params.into_iter().enumerate() {
+        if i > 0 {
+            label.push_str(", ");
+        }
+
+        // offsets are expected to be in UTF-16 code units
+        let start = label.encode_utf16().count() as u32;
+        label.push_str(&param);
+        let end = label.encode_utf16().count() as u32;
+
+        param_infos.push(lsp::ParameterInformation {
+            label: lsp::ParameterLabel::LabelOffsets([start, end]),
+            documentation: None
+        });
+    }
+
+    label.push(')');
+    label.push_str(" : ");
+    label.push_str(return_type_name);
+
+    Some(lsp::SignatureInformation {
+        label,
+        documentation: None,
+        parameters: Some(param_infos),
+        active_parameter: None
+    })
+}
+
+
+impl RenderPartialTooltip for CalleeParam<'_> {
+    fn render_partial(&self, buf: &mut String) {
+        match self {
+            CalleeParam::Func(s) => s.render_partial(buf),
+            CalleeParam::ArrayFunc(s) => s.render_partial(buf),
+            CalleeParam::StructField(s) => {
+                buf.push_str(s.name());
+                buf.push(' ');
+                buf.push(':');
+                buf.push(' ');
+                buf.push_str(s.type_name());
+            }
+        }
+    }
+}
+
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+
+    #[test]
+    fn test_arg_index_at_end() {
+        assert_eq!(arg_index_at_end(""), None);
+        assert_eq!(arg_index_at_end(" "), None);
+        assert_eq!(arg_index_at_end("("), Some(0));
+        assert_eq!(arg_index_at_end("(a"), Some(0));
+        assert_eq!(arg_index_at_end("(a,"), Some(1));
+        assert_eq!(arg_index_at_end("( a, b, "), Some(2));
+        assert_eq!(arg_index_at_end("(a, , "), Some(2));
+    }
+
+    #[test]
+    fn test_arg_index_at_end_nested() {
+        assert_eq!(arg_index_at_end("(Foo(a, b), "), Some(1));
+        assert_eq!(arg_index_at_end("(Foo(a, b"), Some(0));
+        assert_eq!(arg_index_at_end("(arr[Max(i, j)], x"), Some(1));
+        assert_eq!(arg_index_at_end("((a + b) * c, "), Some(1));
+    }
+
+    #[test]
+    fn test_arg_index_at_end_literals() {
+        assert_eq!(arg_index_at_end("(\"a, b\", "), Some(1));
+        assert_eq!(arg_index_at_end("('Name,Other', \"(\""), Some(1));
+        assert_eq!(arg_index_at_end("(\"unclosed, "), Some(0));
+    }
+}
@@ -0,0 +1,210 @@
+use std::collections::HashSet;
+use std::sync::atomic::Ordering;
+use abs_path::AbsPath;
+use tower_lsp::lsp_types as lsp;
+use tower_lsp::lsp_types::request::Request;
+use tower_lsp::jsonrpc::Result;
+use witcherscript_analysis::symbol_analysis::symbol_path::SymbolPathBuf;
+use witcherscript_analysis::symbol_analysis::symbols::*;
+use crate::Backend;
+use super::common::resolve_text_document_position;
+
+
+impl Backend {
+    /// lsp-types does not provide a static server capability for type hierarchy yet,
+    /// so it can only be registered dynamically if the client allows it.
+    pub async fn register_type_hierarchy(&self) {
+        if !self.type_hierarchy_support.load(Ordering::Relaxed) {
+            return;
+        }
+
+        let registration = lsp::Registration {
+            id: lsp::request::TypeHierarchyPrepare::METHOD.to_string(),
+            method: lsp::request::TypeHierarchyPrepare::METHOD.to_string(),
+            register_options: serde_json::to_value(lsp::TypeHierarchyRegistrationOptions {
+                text_document_registration_options: lsp::TextDocumentRegistrationOptions {
+                    document_selector: Some(vec![lsp::DocumentFilter {
+                        language: Some(Backend::LANGUAGE_ID.into()),
+                        scheme: None,
+                        pattern: None
+                    }])
+                },
+                ..Default::default()
+            }).ok()
+        };
+
+        if let Err(err) = self.client.register_capability(vec![registration]).await {
+            self.reporter.log_error(format!("Failed to register type hierarchy: {}", err)).await;
+        }
+    }
+
+    pub async fn prepare_type_hierarchy_impl(&self, params: lsp::TypeHierarchyPrepareParams) -> Result<Option<Vec<lsp::TypeHierarchyItem>>> {
+        let doc_path = AbsPath::try_from(params.text_document_position_params.text_document.uri.clone()).unwrap();
+
+        if doc_path.extension().unwrap_or_default() != "ws" {
+            return Ok(None);
+        }
+
+        let content_path;
+        if let Some(path) = self.scripts.get(&doc_path).and_then(|ss| ss.content_info.as_ref().map(|ci| ci.content_path.to_owned())) {
+            content_path = path;
+        }
+        else {
+            return Ok(None);
+        }
+
+        let symtabs = self.symtabs.read().await;
+        let symtabs_marcher = self.march_symbol_tables(&symtabs, &content_path).await;
+
+        let script_state;
+        if let Some(ss) = self.scripts.get(&doc_path) {
+            script_state = ss;
+        } else {
+            return Ok(None);
+        }
+
+        let position_target = resolve_text_document_position(params.text_document_position_params.position, &script_state, symtabs_marcher.clone());
+        drop(script_state);
+
+        let item = position_target
+            .and_then(|t| t.target_symbol_path(&symtabs_marcher))
+            .and_then(|sympath| symtabs_marcher.get_symbol(&sympath))
+            .and_then(make_type_hierarchy_item);
+
+        Ok(item.map(|item| vec![item]))
+    }
+
+    pub async fn supertypes_impl(&self, params: lsp::TypeHierarchySupertypesParams) -> Result<Option<Vec<lsp::TypeHierarchyItem>>> {
+        let symtabs = self.symtabs.read().await;
+
+        let (content_path, sympath);
+        if let Some((p, s)) = self.resolve_hierarchy_item_target(&symtabs, &params.item.uri, params.item.selection_range.start).await {
+            content_path = p;
+            sympath = s;
+        } else {
+            return Ok(None);
+        }
+
+        let symtabs_marcher = self.march_symbol_tables(&symtabs, &content_path).await;
+
+        let supertypes: Vec<&SymbolVariant> = match symtabs_marcher.get_symbol(&sympath) {
+            Some(SymbolVariant::Class(_)) => {
+                symtabs_marcher
+                    .class_hierarchy(&sympath)
+                    .nth(1)
+                    .and_then(|base| symtabs_marcher.get_symbol(base.path()))
+                    .into_iter()
+                    .collect()
+            },
+            Some(SymbolVariant::State(state)) => {
+                // a state without an explicit base derives from the default state base class
+                let base = symtabs_marcher
+                    .state_hierarchy(&sympath)
+                    .nth(1)
+                    .map(|base| base.path().to_owned())
+                    .unwrap_or_else(|| BasicTypeSymbolPath::new(StateSymbol::DEFAULT_STATE_BASE_NAME).into());
+
+                // the class that the state belongs to is shown alongside so that both directions of the hierarchy are consistent
+                [base.as_sympath(), state.parent_class_path()].into_iter()
+                    .filter_map(|p| symtabs_marcher.get_symbol(p))
+                    .collect()
+            },
+            _ => return Ok(None)
+        };
+
+        Ok(Some(supertypes.into_iter().filter_map(make_type_hierarchy_item).collect()))
+    }
+
+    pub async fn subtypes_impl(&self, params: lsp::TypeHierarchySubtypesParams) -> Result<Option<Vec<lsp::TypeHierarchyItem>>> {
+        let symtabs = self.symtabs.read().await;
+
+        let (content_path, sympath);
+        if let Some((p, s)) = self.resolve_hierarchy_item_target(&symtabs, &params.item.uri, params.item.selection_range.start).await {
+            content_path = p;
+            sympath = s;
+        } else {
+            return Ok(None);
+        }
+
+        let state_name = match self.march_symbol_tables(&symtabs, &content_path).await.get_symbol(&sympath) {
+            Some(SymbolVariant::Class(_)) => None,
+            Some(SymbolVariant::State(state)) => Some(state.state_name().to_string()),
+            _ => return Ok(None)
+        };
+
+        // derived types can only be declared in the content that declares the type or in contents that depend on it
+        let decl_content_path = self.declaring_content_path(&symtabs, &content_path, &sympath).await;
+        let searched_contents: Vec<AbsPath> =
+            [decl_content_path.clone()].into_iter()
+            .chain(self.content_graph
+                .read().await
+                .walk_dependants(&decl_content_path)
+                .map(|n| n.content.path().to_owned()))
+            .collect();
+
+        let mut subtypes = Vec::new();
+        let mut visited: HashSet<SymbolPathBuf> = HashSet::new();
+        for content_path in searched_contents {
+            let symtab;
+            if let Some(st) = symtabs.get(&content_path) {
+                symtab = st;
+            } else {
+                continue;
+            }
+
+            let extends_index = symtab.extends_index();
+            let derived_paths: Vec<SymbolPathBuf> = if let Some(state_name) = &state_name {
+                // states extend other states by name, so it has to be checked which state the name actually resolves to
+                let symtabs_marcher = self.march_symbol_tables(&symtabs, &content_path).await;
+                extends_index
+                    .get_derived_state_candidates(state_name)
+                    .filter(|candidate| symtabs_marcher
+                        .state_hierarchy(candidate)
+                        .nth(1)
+                        .map(|base| base.path() == sympath.as_sympath())
+                        .unwrap_or(false))
+                    .map(|p| p.to_owned())
+                    .collect()
+            } else {
+                extends_index
+                    .get_derived_classes(&sympath)
+                    .chain(extends_index.get_class_states(&sympath))
+                    .map(|p| p.to_owned())
+                    .collect()
+            };
+
+            for derived_path in derived_paths {
+                if !visited.insert(derived_path.clone()) {
+                    continue;
+                }
+
+                if let Some(item) = symtab.get_symbol(&derived_path).and_then(make_type_hierarchy_item) {
+                    subtypes.push(item);
+                }
+            }
+        }
+
+        Ok(Some(subtypes))
+    }
+}
+
+
+fn make_type_hierarchy_item(symvar: &SymbolVariant) -> Option<lsp::TypeHierarchyItem> {
+    let (name, detail) = match symvar {
+        SymbolVariant::Class(_) => (symvar.name().to_string(), None),
+        SymbolVariant::State(state) => (state.state_name().to_string(), Some(format!("state in {}", state.parent_class_name()))),
+        _ => return None
+    };
+
+    let loc = symvar.location()?;
+    Some(lsp::TypeHierarchyItem {
+        name,
+        kind: lsp::SymbolKind::CLASS,
+        tags: None,
+        detail,
+        uri: loc.abs_source_path().to_uri(),
+        range: loc.range,
+        selection_range: loc.label_range,
+        data: None
+    })
+}
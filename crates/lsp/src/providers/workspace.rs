@@ -1,9 +1,15 @@
+use std::collections::HashSet;
+use std::sync::atomic::Ordering;
 use abs_path::AbsPath;
 use tower_lsp::lsp_types as lsp;
+use tower_lsp::lsp_types::notification::Notification;
+use witcherscript_project::{redkit::RedkitManifest, Manifest};
 use crate::Backend;
 
 
 impl Backend {
+    const FILE_WATCHERS_REGISTRATION_ID: &'static str = "witcherscript-ide/fileWatchers";
+
     pub async fn did_change_workspace_folders_impl(&self, params: lsp::DidChangeWorkspaceFoldersParams) {
         let added: Vec<_> = params.event.added.into_iter()
             .map(|f| AbsPath::try_from(f.uri).unwrap())
@@ -24,4 +30,114 @@ impl Backend {
     
         self.reporter.commit_all_diagnostics().await;
     }
-}
\ No newline at end of file
+
+    // Changes made outside of the editor (e.g. by source control or a game update) are only known through file watchers
+    pub async fn did_change_watched_files_impl(&self, params: lsp::DidChangeWatchedFilesParams) {
+        let mut contents_to_update = HashSet::new();
+        let mut should_rebuild_graph = false;
+        for change in params.changes {
+            let path;
+            if let Ok(p) = AbsPath::try_from(change.uri) {
+                path = p;
+            } else {
+                continue;
+            }
+
+            if path.extension().unwrap_or_default() == "ws" {
+                if let Some(content_path) = self.content_graph.read().await.strip_content_path_prefix(&path) {
+                    contents_to_update.insert(content_path);
+                }
+            }
+            else if path.file_name().unwrap_or_default() == Manifest::FILE_NAME || path.extension().unwrap_or_default() == RedkitManifest::EXTENSION {
+                // manifests edited in the editor get handled when they are saved
+                if change.typ == lsp::FileChangeType::CHANGED && self.manifests.contains_key(&path) {
+                    continue;
+                }
+
+                should_rebuild_graph = true;
+            }
+        }
+
+        if should_rebuild_graph {
+            self.build_content_graph(true).await;
+        }
+
+        for content_path in contents_to_update {
+            self.scan_source_tree(&content_path).await;
+        }
+
+        self.reporter.commit_all_diagnostics().await;
+    }
+
+    pub async fn work_done_progress_cancel(&self, params: lsp::WorkDoneProgressCancelParams) {
+        self.cancel_workspace_analysis(Some(&params.token));
+    }
+
+    /// Registers watchers for files relevant to the server both in the workspace and in content repositories.
+    /// Repositories can change with configuration, so any previously registered watchers are replaced.
+    /// Does nothing if the client doesn't support registering them dynamically.
+    pub async fn register_file_watchers(&self) {
+        if !self.file_watchers_support.load(Ordering::Relaxed) {
+            return;
+        }
+
+        let patterns = [
+            "**/*.ws".to_string(),
+            format!("**/{}", Manifest::FILE_NAME),
+            format!("**/*.{}", RedkitManifest::EXTENSION)
+        ];
+
+        let repo_roots: Vec<AbsPath> = self.content_graph
+            .read().await
+            .repository_scanners()
+            .map(|scanner| scanner.scan_root().to_owned())
+            .collect();
+
+        let mut watchers: Vec<lsp::FileSystemWatcher> = patterns.iter()
+            .map(|pattern| lsp::FileSystemWatcher {
+                glob_pattern: lsp::GlobPattern::String(pattern.clone()),
+                kind: None
+            })
+            .collect();
+
+        // repositories are usually outside of the workspace, so they need to be watched explicitly
+        let supports_relative_patterns = self.relative_pattern_support.load(Ordering::Relaxed);
+        for root in repo_roots {
+            watchers.extend(patterns.iter().map(|pattern| {
+                let glob_pattern = if supports_relative_patterns {
+                    lsp::GlobPattern::Relative(lsp::RelativePattern {
+                        base_uri: lsp::OneOf::Right(root.to_uri()),
+                        pattern: pattern.clone()
+                    })
+                } else {
+                    // glob patterns only use forward slashes as separators
+                    let root = root.to_string().replace('\\', "/");
+                    lsp::GlobPattern::String(format!("{}/{}", root.trim_end_matches('/'), pattern))
+                };
+
+                lsp::FileSystemWatcher {
+                    glob_pattern,
+                    kind: None
+                }
+            }));
+        }
+
+        // the client simply ignores the request if watchers haven't been registered yet
+        let _ = self.client.unregister_capability(vec![
+            lsp::Unregistration {
+                id: Self::FILE_WATCHERS_REGISTRATION_ID.to_string(),
+                method: lsp::notification::DidChangeWatchedFiles::METHOD.to_string()
+            }
+        ]).await;
+
+        let registration = lsp::Registration {
+            id: Self::FILE_WATCHERS_REGISTRATION_ID.to_string(),
+            method: lsp::notification::DidChangeWatchedFiles::METHOD.to_string(),
+            register_options: serde_json::to_value(lsp::DidChangeWatchedFilesRegistrationOptions { watchers }).ok()
+        };
+
+        if let Err(err) = self.client.register_capability(vec![registration]).await {
+            self.reporter.log_error(format!("Failed to register file watchers: {}", err)).await;
+        }
+    }
+}
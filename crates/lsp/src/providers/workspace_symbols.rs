@@ -0,0 +1,60 @@
+use std::cmp::Reverse;
+use tower_lsp::lsp_types as lsp;
+use tower_lsp::jsonrpc::Result;
+use witcherscript_analysis::symbol_analysis::symbols::*;
+use crate::Backend;
+
+
+impl Backend {
+    /// Upper limit for the number of returned symbols.
+    /// Without it a short query could return a big chunk of content0.
+    const MAX_WORKSPACE_SYMBOLS: usize = 256;
+
+    pub async fn workspace_symbol_impl(&self, params: lsp::WorkspaceSymbolParams) -> Result<Option<Vec<lsp::SymbolInformation>>> {
+        let content_graph = self.content_graph.read().await;
+        let symtabs = self.symtabs.read().await;
+
+        let mut matches = Vec::new();
+        for (content_path, symtab) in symtabs.iter() {
+            let in_workspace = content_graph
+                .get_node_by_path(content_path)
+                .map(|n| n.in_workspace)
+                .unwrap_or(false);
+
+            matches.extend(symtab
+                .name_index()
+                .fuzzy_search(&params.query)
+                .map(|(score, entry)| (in_workspace, score, entry, symtab)));
+        }
+
+        // workspace content goes first, then the best matches
+        matches.sort_by_key(|(in_workspace, score, entry, _)| (Reverse(*in_workspace), Reverse(*score), entry.name.len()));
+
+        let symbols = matches.into_iter()
+            .filter_map(|(_, _, entry, symtab)| {
+                let loc = symtab.get_symbol(&entry.path).and_then(|v| v.location())?;
+                let kind = match entry.typ {
+                    SymbolType::Class | SymbolType::State => lsp::SymbolKind::CLASS,
+                    SymbolType::Struct => lsp::SymbolKind::STRUCT,
+                    SymbolType::Enum => lsp::SymbolKind::ENUM,
+                    SymbolType::GlobalFunction => lsp::SymbolKind::FUNCTION,
+                    SymbolType::MemberFunction => lsp::SymbolKind::METHOD,
+                    _ => return None
+                };
+
+                #[allow(deprecated)]
+                Some(lsp::SymbolInformation {
+                    name: entry.name.clone(),
+                    kind,
+                    tags: None,
+                    deprecated: None,
+                    location: lsp::Location::new(loc.abs_source_path().to_uri(), loc.label_range),
+                    container_name: entry.container_name.clone()
+                })
+            })
+            .take(Self::MAX_WORKSPACE_SYMBOLS)
+            .collect();
+
+        Ok(Some(symbols))
+    }
+}
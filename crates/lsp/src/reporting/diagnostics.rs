@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::sync::atomic::Ordering;
 use tower_lsp::lsp_types as lsp;
 use abs_path::AbsPath;
 use witcherscript_diagnostics::{Diagnostic, DiagnosticDomain};
@@ -9,7 +10,32 @@ use super::Reporter;
 pub struct BufferedDiagnostics {
     diags: Vec<BufferedDiagnostic>,
     changed: bool,
-    should_purge: bool
+    should_purge: bool,
+    /// Revision of the last change made to diagnostics of each domain
+    domain_revisions: HashMap<DiagnosticDomain, u64>
+}
+
+impl BufferedDiagnostics {
+    /// Result ID is composed of revisions of all domains,
+    /// so a change in any of them invalidates diagnostics previously pulled by the client.
+    /// Revisions start over with each server process, hence the nonce prefix.
+    fn result_id(&self, nonce: u64) -> String {
+        let mut revisions: Vec<_> = self.domain_revisions.iter().collect();
+        revisions.sort_by_key(|(domain, _)| **domain as u8);
+        let revisions = revisions.into_iter()
+            .map(|(domain, rev)| format!("{}:{}", *domain as u8, rev))
+            .collect::<Vec<_>>()
+            .join(",");
+
+        format!("{}/{}", nonce, revisions)
+    }
+
+    fn full_report(&self, nonce: u64) -> lsp::FullDocumentDiagnosticReport {
+        lsp::FullDocumentDiagnosticReport {
+            result_id: Some(self.result_id(nonce)),
+            items: self.diags.iter().map(|d| d.lsp_diag.clone()).collect()
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -22,11 +48,14 @@ impl Reporter {
     pub fn push_diagnostic(&self, path: &AbsPath, diag: Diagnostic) {
         let bd = BufferedDiagnostic { domain: diag.kind.domain(), lsp_diag: diag.into() };
         let mut diags = self.buffered_diagnostics.lock().unwrap();
+        let revision = self.next_diagnostics_revision();
         if let Some(v) = diags.get_mut(path) {
+            v.domain_revisions.insert(bd.domain, revision);
             v.diags.push(bd);
             v.changed = true;
         } else {
             diags.insert(path.clone(), BufferedDiagnostics {
+                domain_revisions: HashMap::from([(bd.domain, revision)]),
                 diags: vec![bd],
                 changed: true,
                 should_purge: false
@@ -35,14 +64,18 @@ impl Reporter {
     }
 
     pub fn push_diagnostics(&self, path: &AbsPath, diags: impl IntoIterator<Item = Diagnostic>) {
-        let bds = diags.into_iter().map(|diag| BufferedDiagnostic { domain: diag.kind.domain(), lsp_diag: diag.into() });
+        let bds: Vec<_> = diags.into_iter().map(|diag| BufferedDiagnostic { domain: diag.kind.domain(), lsp_diag: diag.into() }).collect();
         let mut diags = self.buffered_diagnostics.lock().unwrap();
+        let revision = self.next_diagnostics_revision();
+        let pushed_domains: Vec<_> = bds.iter().map(|bd| (bd.domain, revision)).collect();
         if let Some(v) = diags.get_mut(path) {
+            v.domain_revisions.extend(pushed_domains);
             v.diags.extend(bds);
             v.changed = true;
         } else {
             diags.insert(path.clone(), BufferedDiagnostics {
-                diags: bds.collect(),
+                domain_revisions: pushed_domains.into_iter().collect(),
+                diags: bds,
                 changed: true,
                 should_purge: false
             });
@@ -52,8 +85,14 @@ impl Reporter {
     pub fn clear_diagnostics(&self, path: &AbsPath, domain: DiagnosticDomain) {
         let mut diags = self.buffered_diagnostics.lock().unwrap();
         if let Some(v) = diags.get_mut(path) {
+            let prev_len = v.diags.len();
             v.diags.retain(|d| d.domain != domain);
             v.changed = true;
+
+            // clearing a domain that had nothing to report doesn't make previously pulled results outdated
+            if v.diags.len() != prev_len {
+                v.domain_revisions.insert(domain, self.next_diagnostics_revision());
+            }
         }
     }
 
@@ -64,20 +103,24 @@ impl Reporter {
             v.diags.clear();
             v.changed = true;
             v.should_purge = true;
+            v.domain_revisions.clear();
         }
     }
 
     pub fn clear_all_diagnostics(&self) {
         let mut diags = self.buffered_diagnostics.lock().unwrap();
+        let revision = self.next_diagnostics_revision();
         for (_, v) in diags.iter_mut() {
             v.diags.clear();
             v.changed = true;
+            v.domain_revisions.values_mut().for_each(|rev| *rev = revision);
         }
     }
 
 
     pub async fn commit_diagnostics(&self, path: &AbsPath) {
         let mut to_publish = Vec::new();
+        let mut changed = false;
         {
             let mut diags = self.buffered_diagnostics.lock().unwrap();
             let mut should_purge = false;
@@ -85,6 +128,7 @@ impl Reporter {
                 if v.changed {
                     to_publish = v.diags.iter().map(|d| d.lsp_diag.clone()).collect();
                     v.changed = false;
+                    changed = true;
                 }
     
                 should_purge = v.should_purge;            
@@ -95,8 +139,14 @@ impl Reporter {
             }
         }
 
-        let uri = path.to_uri();
-        self.client.publish_diagnostics(uri, to_publish, None).await;
+        if self.pull_diagnostics.load(Ordering::Relaxed) {
+            if changed {
+                self.refresh_diagnostics().await;
+            }
+        } else {
+            let uri = path.to_uri();
+            self.client.publish_diagnostics(uri, to_publish, None).await;
+        }
     }
 
     pub async fn commit_all_diagnostics(&self) {
@@ -114,8 +164,96 @@ impl Reporter {
             diags.retain(|_, v| !v.should_purge);
         }
 
-        for (uri, diags) in to_publish {
-            self.client.publish_diagnostics(uri, diags, None).await;
+        if self.pull_diagnostics.load(Ordering::Relaxed) {
+            if !to_publish.is_empty() {
+                self.refresh_diagnostics().await;
+            }
+        } else {
+            for (uri, diags) in to_publish {
+                self.client.publish_diagnostics(uri, diags, None).await;
+            }
         }
     }
+
+
+    /// Switches the reporter to the pull model, where diagnostics are no longer published, but requested by the client instead
+    pub fn enable_pull_diagnostics(&self, refresh_support: bool) {
+        self.pull_diagnostics.store(true, Ordering::Relaxed);
+        self.diagnostics_refresh_support.store(refresh_support, Ordering::Relaxed);
+    }
+
+    pub fn document_diagnostic_report(&self, path: &AbsPath, previous_result_id: Option<&str>) -> lsp::DocumentDiagnosticReport {
+        let diags = self.buffered_diagnostics.lock().unwrap();
+        if let Some(v) = diags.get(path) {
+            let result_id = v.result_id(self.diagnostics_nonce);
+            if previous_result_id == Some(result_id.as_str()) {
+                lsp::DocumentDiagnosticReport::Unchanged(lsp::RelatedUnchangedDocumentDiagnosticReport {
+                    related_documents: None,
+                    unchanged_document_diagnostic_report: lsp::UnchangedDocumentDiagnosticReport {
+                        result_id
+                    }
+                })
+            } else {
+                lsp::DocumentDiagnosticReport::Full(lsp::RelatedFullDocumentDiagnosticReport {
+                    related_documents: None,
+                    full_document_diagnostic_report: v.full_report(self.diagnostics_nonce)
+                })
+            }
+        } else {
+            lsp::DocumentDiagnosticReport::Full(lsp::RelatedFullDocumentDiagnosticReport::default())
+        }
+    }
+
+    pub fn workspace_diagnostic_report(&self, previous_result_ids: Vec<lsp::PreviousResultId>) -> lsp::WorkspaceDiagnosticReport {
+        let mut previous_result_ids: HashMap<lsp::Url, String> = previous_result_ids.into_iter()
+            .map(|prev| (prev.uri, prev.value))
+            .collect();
+
+        let mut items = Vec::new();
+        {
+            let diags = self.buffered_diagnostics.lock().unwrap();
+            for (path, v) in diags.iter() {
+                let uri = path.to_uri();
+                let result_id = v.result_id(self.diagnostics_nonce);
+                if previous_result_ids.remove(&uri).as_ref() == Some(&result_id) {
+                    items.push(lsp::WorkspaceDocumentDiagnosticReport::Unchanged(lsp::WorkspaceUnchangedDocumentDiagnosticReport {
+                        uri,
+                        version: None,
+                        unchanged_document_diagnostic_report: lsp::UnchangedDocumentDiagnosticReport {
+                            result_id
+                        }
+                    }));
+                } else {
+                    items.push(lsp::WorkspaceDocumentDiagnosticReport::Full(lsp::WorkspaceFullDocumentDiagnosticReport {
+                        uri,
+                        version: None,
+                        full_document_diagnostic_report: v.full_report(self.diagnostics_nonce)
+                    }));
+                }
+            }
+        }
+
+        // files that the client knows about, but the server doesn't anymore have no diagnostics
+        for (uri, _) in previous_result_ids {
+            items.push(lsp::WorkspaceDocumentDiagnosticReport::Full(lsp::WorkspaceFullDocumentDiagnosticReport {
+                uri,
+                version: None,
+                full_document_diagnostic_report: lsp::FullDocumentDiagnosticReport::default()
+            }));
+        }
+
+        lsp::WorkspaceDiagnosticReport { items }
+    }
+
+    async fn refresh_diagnostics(&self) {
+        if self.diagnostics_refresh_support.load(Ordering::Relaxed) {
+            if let Err(err) = self.client.workspace_diagnostic_refresh().await {
+                self.log_error(format!("Failed to request diagnostic refresh: {}", err)).await;
+            }
+        }
+    }
+
+    fn next_diagnostics_revision(&self) -> u64 {
+        self.diagnostics_revision.fetch_add(1, Ordering::Relaxed) + 1
+    }
 }
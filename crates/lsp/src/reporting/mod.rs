@@ -1,6 +1,6 @@
 //! Utilities for sending informational messages to the client.
 
-use std::{collections::HashMap, sync::Mutex};
+use std::{collections::HashMap, sync::{atomic::{AtomicBool, AtomicU64}, Mutex}, time::{SystemTime, UNIX_EPOCH}};
 use tower_lsp::Client;
 use abs_path::AbsPath;
 
@@ -16,13 +16,27 @@ mod notifications;
 pub struct Reporter {
     client: Client,
     buffered_diagnostics: Mutex<HashMap<AbsPath, BufferedDiagnostics>>,
+    /// Source of versions used to compose diagnostic result IDs
+    diagnostics_revision: AtomicU64,
+    /// Unique to this server process, so that result IDs kept by the client from before a restart never match new ones
+    diagnostics_nonce: u64,
+    /// If the client pulls diagnostics by itself they should not be published
+    pull_diagnostics: AtomicBool,
+    diagnostics_refresh_support: AtomicBool
 }
 
 impl Reporter {
     pub fn new(client: Client) -> Self {
         Self {
             client,
-            buffered_diagnostics: Mutex::default()
+            buffered_diagnostics: Mutex::default(),
+            diagnostics_revision: AtomicU64::new(0),
+            diagnostics_nonce: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_millis() as u64)
+                .unwrap_or_default(),
+            pull_diagnostics: AtomicBool::new(false),
+            diagnostics_refresh_support: AtomicBool::new(false)
         }
     }
 }
\ No newline at end of file
@@ -4,6 +4,7 @@ use tower_lsp::lsp_types as lsp;
 use abs_path::AbsPath;
 use witcherscript_analysis::jobs;
 use witcherscript_analysis::symbol_analysis::symbol_table::SymbolTable;
+use witcherscript_analysis::symbol_analysis::references::ReferenceIndex;
 use witcherscript_diagnostics::*;
 use witcherscript_project::content::{ContentScanError, ProjectDirectory, RedkitProjectDirectory};
 use witcherscript_project::source_tree::SourceTreeDifference;
@@ -112,7 +113,23 @@ impl Backend {
             .map(|kv| kv.key().to_owned())
             .collect();
 
-        self.run_script_analysis(script_paths, true).await;
+        if !self.config.read().await.enable_workspace_analysis {
+            self.cancel_workspace_analysis(None);
+            self.run_script_analysis(script_paths, true).await;
+            return;
+        }
+
+        // references are needed by navigation in all scripts, 
+        // but diagnostics only for scripts opened in the editor, the rest of the workspace is analyzed in the background
+        self.reference_analysis(script_paths).await;
+
+        let open_script_paths = self.open_scripts.iter()
+            .map(|p| p.key().to_owned())
+            .filter(|p| self.scripts.contains_key(p))
+            .collect();
+
+        self.run_script_analysis(open_script_paths, true).await;
+        self.start_workspace_analysis().await;
     }
 
     async fn on_content_graph_changed(&self, diff: ContentGraphDifference) {
@@ -173,7 +190,7 @@ impl Backend {
             self.source_trees.insert(added_content_path.clone(), source_tree);
 
             let mut symtabs = self.symtabs.write().await;
-            let mut symtab = SymbolTable::new(scripts_root);
+            let mut symtab = SymbolTable::new(scripts_root.clone());
 
             if added_node.is_native {
                 jobs::inject_primitives(&mut symtab);
@@ -181,6 +198,10 @@ impl Backend {
             }
 
             symtabs.insert(added_content_path.clone(), symtab);
+            drop(symtabs);
+
+            let mut ref_indices = self.ref_indices.write().await;
+            ref_indices.insert(added_content_path.clone(), ReferenceIndex::new(scripts_root));
         }
 
         // handling source tree changes in a seperate step to not lock resources for too long
@@ -207,6 +228,11 @@ impl Backend {
 
             let mut symtabs = self.symtabs.write().await;
             symtabs.remove(removed_content_path);
+            drop(symtabs);
+
+            let mut ref_indices = self.ref_indices.write().await;
+            ref_indices.remove(removed_content_path);
+            drop(ref_indices);
             
             if !removed_content_path.exists() || !removed_node.in_workspace {
                 let mut manifest_path = None;
@@ -262,9 +288,13 @@ impl Backend {
     
     
                 let mut symtabs = self.symtabs.write().await;
-                let symtab = SymbolTable::new(new_scripts_root);
+                let symtab = SymbolTable::new(new_scripts_root.clone());
     
                 symtabs.insert(modified_content_path.clone(), symtab);
+                drop(symtabs);
+
+                let mut ref_indices = self.ref_indices.write().await;
+                ref_indices.insert(modified_content_path.clone(), ReferenceIndex::new(new_scripts_root));
             }
         }
 
@@ -333,10 +363,10 @@ impl Backend {
                     kind: DiagnosticKind::ProjectDependencyNameNotFound(content_name)
                 });
             },
-            ContentGraphError::DependencyNameNotFoundAtPath { content_name, manifest_path, manifest_range } => {
+            ContentGraphError::DependencyNameNotFoundAtPath { content_name, name_at_path, manifest_path, manifest_range } => {
                 self.reporter.push_diagnostic(&manifest_path, Diagnostic {
                     range: manifest_range,
-                    kind: DiagnosticKind::ProjectDependencyNameNotFoundAtPath(content_name)
+                    kind: DiagnosticKind::ProjectDependencyNameNotFoundAtPath { content_name, name_at_path }
                 });
             },
             ContentGraphError::MultipleMatchingDependencies { content_name, manifest_path, manifest_range, matching_paths } => {
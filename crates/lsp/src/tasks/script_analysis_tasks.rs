@@ -1,10 +1,19 @@
 use std::{collections::HashMap, path::PathBuf, sync::Arc};
-use rayon::iter::{IntoParallelIterator, ParallelIterator};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use rayon::iter::{IntoParallelIterator, IntoParallelRefIterator, ParallelIterator};
 use tokio::{sync::oneshot, time::Instant};
+use tower_lsp::lsp_types as lsp;
 use abs_path::AbsPath;
 use witcherscript_analysis::jobs;
 use witcherscript_diagnostics::*;
-use crate::Backend;
+use witcherscript_project::SourceTreePath;
+use crate::{Backend, WorkspaceAnalysisRun};
+
+
+/// Number of scripts analyzed between progress reports and checks for cancellation
+const WORKSPACE_ANALYSIS_BATCH_SIZE: usize = 64;
+
+static WORKSPACE_ANALYSIS_COUNTER: AtomicU64 = AtomicU64::new(0);
 
 
 impl Backend {
@@ -19,16 +28,142 @@ impl Backend {
         if do_syntax_analysis {
             self.syntax_analysis(script_paths.clone()).await;
         }
+
+        self.reference_analysis(script_paths.clone()).await;
         
         if full {
             // here should go more expensive analytics that should be done only when the file is explicitly saved
             self.workspace_symbol_analysis(script_paths.clone()).await;
+            self.expression_analysis(script_paths.clone()).await;
         }
 
         let duration = Instant::now() - start;
         self.reporter.log_info(format!("Analysis finished in {:.3}s", duration.as_secs_f32())).await;
     }
 
+    /// Starts analysis of all scripts in workspace contents in the background if it is enabled.
+    /// Analysis that is already running gets cancelled.
+    pub async fn start_workspace_analysis(&self) {
+        self.cancel_workspace_analysis(None);
+
+        if !self.config.read().await.enable_workspace_analysis {
+            return;
+        }
+
+        let run_id = WORKSPACE_ANALYSIS_COUNTER.fetch_add(1, Ordering::Relaxed);
+        let progress_token = lsp::NumberOrString::String(format!("{}/workspaceAnalysis/{}", Backend::SERVER_NAME, run_id));
+        let cancelled = Arc::new(AtomicBool::new(false));
+
+        *self.workspace_analysis.lock().unwrap() = Some(WorkspaceAnalysisRun {
+            progress_token: progress_token.clone(),
+            cancelled: cancelled.clone()
+        });
+
+        let backend = self.clone();
+        tokio::spawn(async move {
+            backend.workspace_analysis(progress_token, cancelled).await;
+        });
+    }
+
+    /// Cancels workspace analysis if it is running.
+    /// If a progress token is given, analysis is cancelled only if it reports progress with that token.
+    pub fn cancel_workspace_analysis(&self, progress_token: Option<&lsp::NumberOrString>) {
+        let mut current_run = self.workspace_analysis.lock().unwrap();
+        let should_cancel = current_run.as_ref()
+            .map(|run| progress_token.map(|token| token == &run.progress_token).unwrap_or(true))
+            .unwrap_or(false);
+
+        if should_cancel {
+            if let Some(run) = current_run.take() {
+                run.cancelled.store(true, Ordering::Relaxed);
+            }
+        }
+    }
+
+    async fn workspace_analysis(&self, progress_token: lsp::NumberOrString, cancelled: Arc<AtomicBool>) {
+        let workspace_content_paths: Vec<AbsPath> = self.content_graph
+            .read().await
+            .nodes()
+            .filter(|n| n.in_workspace)
+            .map(|n| n.content.path().to_owned())
+            .collect();
+
+        let script_paths: Vec<AbsPath> = workspace_content_paths.iter()
+            .filter_map(|content_path| self.source_trees.get(content_path))
+            .flat_map(|source_tree| source_tree.iter().map(|f| f.path.absolute().to_owned()).collect::<Vec<_>>())
+            .collect();
+
+        let do_syntax_analysis = self.config.read().await.enable_syntax_analysis;
+        let start = Instant::now();
+
+        // client may not support server initiated progress, in which case analysis simply goes on without it
+        let report_progress = self.client
+            .send_request::<lsp::request::WorkDoneProgressCreate>(lsp::WorkDoneProgressCreateParams { 
+                token: progress_token.clone() 
+            })
+            .await
+            .is_ok();
+
+        if report_progress {
+            self.send_workspace_analysis_progress(&progress_token, lsp::WorkDoneProgress::Begin(lsp::WorkDoneProgressBegin {
+                title: "Analyzing workspace scripts".into(),
+                cancellable: Some(true),
+                message: Some(format!("0/{}", script_paths.len())),
+                percentage: Some(0)
+            })).await;
+        }
+
+        let mut analyzed_count = 0;
+        for batch in script_paths.chunks(WORKSPACE_ANALYSIS_BATCH_SIZE) {
+            if cancelled.load(Ordering::Relaxed) {
+                break;
+            }
+
+            if do_syntax_analysis {
+                self.syntax_analysis(batch.to_vec()).await;
+            }
+
+            self.workspace_symbol_analysis(batch.to_vec()).await;
+            self.expression_analysis(batch.to_vec()).await;
+            self.reporter.commit_all_diagnostics().await;
+
+            analyzed_count += batch.len();
+            if report_progress {
+                self.send_workspace_analysis_progress(&progress_token, lsp::WorkDoneProgress::Report(lsp::WorkDoneProgressReport {
+                    cancellable: Some(true),
+                    message: Some(format!("{}/{}", analyzed_count, script_paths.len())),
+                    percentage: Some((analyzed_count * 100 / script_paths.len()) as u32)
+                })).await;
+            }
+        }
+
+        let was_cancelled = cancelled.load(Ordering::Relaxed);
+        if report_progress {
+            self.send_workspace_analysis_progress(&progress_token, lsp::WorkDoneProgress::End(lsp::WorkDoneProgressEnd {
+                message: was_cancelled.then(|| "Cancelled".into())
+            })).await;
+        }
+
+        let duration = Instant::now() - start;
+        if was_cancelled {
+            self.reporter.log_info(format!("Workspace analysis cancelled after analyzing {}/{} scripts", analyzed_count, script_paths.len())).await;
+        } else {
+            self.reporter.log_info(format!("Workspace analysis of {} scripts finished in {:.3}s", analyzed_count, duration.as_secs_f32())).await;
+        }
+
+        let mut current_run = self.workspace_analysis.lock().unwrap();
+        if current_run.as_ref().map(|run| run.progress_token == progress_token).unwrap_or(false) {
+            *current_run = None;
+        }
+    }
+
+    async fn send_workspace_analysis_progress(&self, progress_token: &lsp::NumberOrString, progress: lsp::WorkDoneProgress) {
+        self.client.send_notification::<lsp::notification::Progress>(lsp::ProgressParams {
+            token: progress_token.clone(),
+            value: lsp::ProgressParamsValue::WorkDone(progress)
+        }).await;
+    }
+
     
     async fn syntax_analysis(&self, script_paths: Vec<AbsPath>) {
         for path in &script_paths {
@@ -69,6 +204,24 @@ impl Backend {
         }
     }  
 
+    pub(super) async fn reference_analysis(&self, script_paths: Vec<AbsPath>) {
+        let mut grouped_by_content: HashMap<AbsPath, Vec<SourceTreePath>> = HashMap::new();
+
+        for path in script_paths {
+            if let Some(script_state) = self.scripts.get(&path) {
+                if let Some(content_info) = &script_state.content_info {
+                    grouped_by_content.entry(content_info.content_path.clone())
+                        .or_default()
+                        .push(content_info.source_tree_path.clone());
+                }
+            }
+        }
+
+        for (content_path, source_paths) in grouped_by_content {
+            self.scan_references(&content_path, source_paths).await;
+        }
+    }
+
     async fn workspace_symbol_analysis(&self, script_paths: Vec<AbsPath>) {
         let mut grouped_by_content: HashMap<AbsPath, Vec<PathBuf>> = HashMap::new();
 
@@ -110,5 +263,46 @@ impl Backend {
                 self.reporter.push_diagnostic(&current_path, ld.diagnostic);
             }
         }
-    } 
-}
\ No newline at end of file
+    }
+
+    async fn expression_analysis(&self, script_paths: Vec<AbsPath>) {
+        let mut grouped_by_content: HashMap<AbsPath, Vec<SourceTreePath>> = HashMap::new();
+
+        for path in script_paths {
+            self.reporter.clear_diagnostics(&path, DiagnosticDomain::ExpressionAnalysis);
+
+            if let Some(script_state) = self.scripts.get(&path) {
+                if let Some(content_info) = &script_state.content_info {
+                    grouped_by_content.entry(content_info.content_path.clone())
+                        .or_default()
+                        .push(content_info.source_tree_path.clone());
+                }
+            }
+        }
+
+        let symtabs = self.symtabs.read().await;
+
+        let mut results = Vec::new();
+        for (content_path, source_paths) in grouped_by_content {
+            let marcher = self.march_symbol_tables(&symtabs, &content_path).await;
+
+            let content_results: Vec<_> = source_paths.par_iter()
+                .filter_map(|p| {
+                    let script_state = self.scripts.get(p.absolute())?;
+                    let mut diags = Vec::new();
+                    jobs::expression_analysis(&script_state.script, &script_state.buffer, marcher.clone(), &mut diags);
+
+                    Some((p.absolute().to_owned(), diags))
+                })
+                .collect();
+
+            results.extend(content_results);
+        }
+
+        drop(symtabs);
+
+        for (script_path, diags) in results {
+            self.reporter.push_diagnostics(&script_path, diags);
+        }
+    }
+}
@@ -3,7 +3,7 @@ use abs_path::AbsPath;
 use rayon::prelude::*;
 use tokio::{sync::oneshot, time::Instant};
 use witcherscript_diagnostics::*;
-use witcherscript_analysis::{jobs, symbol_analysis::symbol_table::SymbolTable};
+use witcherscript_analysis::{jobs, symbol_analysis::symbol_table::SymbolTable, symbol_analysis::references::ReferenceIndex};
 use witcherscript_project::SourceTreePath;
 use crate::{Backend, ScriptStates};
 
@@ -105,6 +105,64 @@ impl Backend {
             symtab.remove_symbols_for_source(p.local());
             self.reporter.clear_diagnostics(p.absolute(), DiagnosticDomain::SymbolAnalysis);
         }
+
+        drop(symtabs);
+
+        let mut ref_indices = self.ref_indices.write().await;
+        if let Some(ref_index) = ref_indices.get_mut(content_path) {
+            for p in removed_source_paths.iter() {
+                ref_index.remove_references_for_source(p.local());
+            }
+        }
+    }
+
+    /// Updates the reference index of the content for given scripts.
+    /// Symbol tables of the content and its dependencies should already be up to date at this point.
+    pub async fn scan_references(&self, content_path: &AbsPath, source_paths: Vec<SourceTreePath>) {
+        if source_paths.is_empty() {
+            return;
+        }
+
+        let start = Instant::now();
+
+        let symtabs = self.symtabs.read().await;
+        let symtabs_marcher = self.march_symbol_tables(&symtabs, content_path).await;
+        let scripts_root = source_paths.first().unwrap().script_root_arc();
+
+        let scanning_index = source_paths.par_iter()
+            .fold(|| ReferenceIndex::new(scripts_root.clone()), |mut ref_index, p| {
+                if let Some(script_state) = self.scripts.get(p.absolute()) {
+                    jobs::scan_references(
+                        &script_state.script, 
+                        &script_state.buffer, 
+                        p.local(), 
+                        symtabs_marcher.clone(), 
+                        &mut ref_index
+                    );
+                }
+                ref_index
+            })
+            .reduce(|| ReferenceIndex::new(scripts_root.clone()), |mut merged_index, ref_index| {
+                merged_index.merge(ref_index);
+                merged_index
+            });
+
+        drop(symtabs_marcher);
+        drop(symtabs);
+
+        let mut ref_indices = self.ref_indices.write().await;
+        if let Some(ref_index) = ref_indices.get_mut(content_path) {
+            for p in source_paths.iter() {
+                ref_index.remove_references_for_source(p.local());
+            }
+
+            ref_index.merge(scanning_index);
+        }
+
+        drop(ref_indices);
+
+        let duration = Instant::now() - start;
+        self.reporter.log_info(format!("Updated reference index for content {} in {:.3}s", content_path, duration.as_secs_f32())).await;
     }
 }
 
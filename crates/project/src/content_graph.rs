@@ -35,6 +35,8 @@ pub enum ContentGraphError {
     #[error("project dependency with name \"{}\" could not be found at specified path", .content_name)]
     DependencyNameNotFoundAtPath {
         content_name: String,
+        /// Name of the content that was found at the path instead
+        name_at_path: String,
         /// Manifest from which this error originated
         manifest_path: AbsPath,
         // Location in the manifest where the name is present
@@ -66,6 +68,8 @@ pub struct ContentGraph {
     repo_scanners: Vec<ContentScanner>,
     workspace_scanners: Vec<ContentScanner>,
     native_content_path: Option<AbsPath>,
+    /// All contents found by repository scanners during the last build, including those that are not needed by the workspace
+    repo_contents: Vec<Arc<dyn Content>>,
 
     nodes: Vec<GraphNode>,
     edges: Vec<GraphEdge>,
@@ -109,6 +113,7 @@ impl ContentGraph {
             repo_scanners: Vec::new(),
             workspace_scanners: Vec::new(),
             native_content_path: None,
+            repo_contents: Vec::new(),
 
             nodes: Vec::new(),
             edges: Vec::new(),
@@ -125,6 +130,10 @@ impl ContentGraph {
         self.repo_scanners.push(scanner);
     }
 
+    pub fn repository_scanners(&self) -> impl Iterator<Item = &ContentScanner> {
+        self.repo_scanners.iter()
+    }
+
     pub fn clear_workspace_scanners(&mut self) {
         self.workspace_scanners.clear();
     }
@@ -145,6 +154,7 @@ impl ContentGraph {
 
     pub fn build(&mut self) -> ContentGraphDifference {
         self.errors.clear();
+        self.repo_contents.clear();
 
         let prev_nodes: Vec<_> = self.nodes.drain(..).collect();
         let prev_edges: Vec<_> = self.edges.drain(..).collect();
@@ -199,6 +209,12 @@ impl ContentGraph {
     }
 
 
+    /// Iterator over all contents found in repositories during the last build, regardless of whether they are in the graph or not.
+    pub fn repository_contents(&self) -> impl Iterator<Item = &Arc<dyn Content>> {
+        self.repo_contents.iter()
+    }
+
+
     /// Iterate over direct dependencies of specified content. Order of nodes depends on dependency priority.
     /// Iterator will be empty when either the specified content doesn't exist or it has no dependencies.
     /// If a circular dependency occurs a reference to the parameter content will not be included.
@@ -321,8 +337,11 @@ impl ContentGraph {
             let (contents, errors) = scanner.scan();
 
             for content in contents {
+                let content: Arc<dyn Content> = Arc::from(content);
+                self.repo_contents.push(content.clone());
+
                 repo_nodes.push(GraphNode { 
-                    content: Arc::from(content),
+                    content,
                     in_workspace: false, 
                     in_repository: true,
                     is_native: false
@@ -563,6 +582,7 @@ impl ContentGraph {
             if self.nodes[dep_idx].content.content_name() != dependency_name {
                 self.errors.push(ContentGraphError::DependencyNameNotFoundAtPath { 
                     content_name: dependency_name.to_owned(), 
+                    name_at_path: self.nodes[dep_idx].content.content_name().to_owned(),
                     manifest_path: manifest_path.to_owned(), 
                     manifest_range: dependency_name_range.to_owned()
                 });
@@ -598,6 +618,7 @@ impl ContentGraph {
                     } else {
                         self.errors.push(ContentGraphError::DependencyNameNotFoundAtPath { 
                             content_name: dependency_name.to_owned(), 
+                            name_at_path: content.content_name().to_owned(),
                             manifest_path: manifest_path.to_owned(), 
                             manifest_range: dependency_name_range.to_owned()
                         });
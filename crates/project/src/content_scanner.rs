@@ -25,6 +25,10 @@ impl ContentScanner {
         })
     }
 
+    pub fn scan_root(&self) -> &AbsPath {
+        &self.scan_root
+    }
+
     pub fn recursive(self, val: bool) -> Self {
         Self {
             recursive: val,
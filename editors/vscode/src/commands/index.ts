@@ -29,6 +29,7 @@ export function registerCommands(context: vscode.ExtensionContext) {
         vscode.commands.registerCommand("witcherscript-ide.misc.openGameHostSettings", misc.commandOpenGameHostSettings()),
         vscode.commands.registerCommand("witcherscript-ide.misc.openFileReadOnly", misc.commandOpenFileReadOnly()),
         vscode.commands.registerCommand("witcherscript-ide.misc.runDashboardOption", misc.commandRunDashboardOption()),
+        vscode.commands.registerCommand("witcherscript-ide.misc.showReferences", misc.commandShowReferences()),
     );
 
     const cfg = getConfiguration();
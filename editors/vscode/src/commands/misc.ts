@@ -1,4 +1,5 @@
 import * as vscode from 'vscode';
+import * as lsp from 'vscode-languageclient/node';
 
 import { Cmd } from './index'
 import { getLanguageClient } from '../lsp/lang_client';
@@ -48,4 +49,18 @@ export function commandRunDashboardOption(): Cmd {
     return (item: DashboardCommandOptionItem) => {
         vscode.commands.executeCommand(item.btnCommand)
     }
-}
\ No newline at end of file
+}
+
+export function commandShowReferences(): Cmd {
+    return (uri: string, position: lsp.Position, locations: lsp.Location[]) => {
+        const client = getLanguageClient();
+        if (client != undefined) {
+            const conv = client.protocol2CodeConverter;
+            vscode.commands.executeCommand('editor.action.showReferences', 
+                conv.asUri(uri), 
+                conv.asPosition(position), 
+                locations.map(loc => conv.asLocation(loc))
+            );
+        }
+    }
+}
@@ -43,13 +43,38 @@ export class Config {
         return this.config.get<boolean>('languageServer.syntaxAnalysis') ?? true;
     }
 
+    get enableWorkspaceAnalysis(): boolean {
+        return this.config.get<boolean>('languageServer.workspaceAnalysis') ?? true;
+    }
+
     get enableDebugFeatures(): boolean {
         return this.config.get<boolean>('debug.enableDebugFeatures') ?? false;
     }
+
+    get formatting(): FormattingConfig {
+        return {
+            braceStyle: this.config.get<BraceStyle>('formatting.braceStyle') ?? BraceStyle.NextLine,
+            spaceAroundOperators: this.config.get<boolean>('formatting.spaceAroundOperators') ?? true,
+            spaceBeforeTypeColon: this.config.get<boolean>('formatting.spaceBeforeTypeColon') ?? true,
+            spaceAfterControlKeywords: this.config.get<boolean>('formatting.spaceAfterControlKeywords') ?? true
+        };
+    }
 }
 
 export enum GameHostType {
     Standalone = "standalone",
     Editor = "editor",
     Auto = "auto"
-}
\ No newline at end of file
+}
+
+export enum BraceStyle {
+    SameLine = "sameLine",
+    NextLine = "nextLine"
+}
+
+export interface FormattingConfig {
+    braceStyle: BraceStyle,
+    spaceAroundOperators: boolean,
+    spaceBeforeTypeColon: boolean,
+    spaceAfterControlKeywords: boolean
+}
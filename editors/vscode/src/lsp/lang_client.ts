@@ -36,7 +36,9 @@ export async function createLanguageClient(ctx: vscode.ExtensionContext, cfg: co
 		nativeContentPath: nativeContentPath,
 		gameDirectory: cfg.gameDirectory,
 		contentRepositories: cfg.contentRepositories,
-		enableSyntaxAnalysis: cfg.enableSyntaxAnalysis
+		enableSyntaxAnalysis: cfg.enableSyntaxAnalysis,
+		enableWorkspaceAnalysis: cfg.enableWorkspaceAnalysis,
+		formatting: cfg.formatting
 	};
 
 	const clientOptions: lsp.LanguageClientOptions = {
@@ -49,14 +51,6 @@ export async function createLanguageClient(ctx: vscode.ExtensionContext, cfg: co
 			{ scheme: tdcp.ReadOnlyContentProvider.scheme, pattern: '**/*.w3edit' },
 			{ scheme: tdcp.ReadOnlyContentProvider.scheme, pattern: '**/witcherscript.toml' }
 		],
-		synchronize: {
-			// Notify the server about file changes to files we care about
-			fileEvents: [
-				vscode.workspace.createFileSystemWatcher('**/*.ws'),
-				vscode.workspace.createFileSystemWatcher('**/witcherscript.toml', false, true),
-				vscode.workspace.createFileSystemWatcher('**/*.w3edit', false, true)
-			]
-		},
 		initializationOptions: initializationOptions
 	};
 
@@ -96,7 +90,9 @@ interface InitializationOptions {
 	nativeContentPath: string,
 	gameDirectory: string,
     contentRepositories: string[]
-	enableSyntaxAnalysis: boolean
+	enableSyntaxAnalysis: boolean,
+	enableWorkspaceAnalysis: boolean,
+	formatting: config.FormattingConfig
 }
 
 